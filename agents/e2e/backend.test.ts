@@ -23,7 +23,7 @@ test.if(runE2E)("e2e: health returns 200 when backend is up", async () => {
 test.if(runE2E)("e2e: create session and play one step", async () => {
   const config = new Configuration({ basePath, apiKey });
   const api = new DefaultApi(config);
-  const gameId = "4f9b8e88-1f2a-4c34-8e2a-a7fe9ef7b654";
+  const gameId = "0b6f4c8e-1d2a-4f3b-9c5e-7a8d9e0f1a01";
   const createRes = await api.createSession({
     gameId,
     playerProfile: { behaviorType: "conservative" },
@@ -43,7 +43,7 @@ test.if(runE2E)("e2e: full orchestration run until Completed or timeout", async
   const { finalObservation } = await orchestrate(
     api,
     {
-      gameId: "4f9b8e88-1f2a-4c34-8e2a-a7fe9ef7b654",
+      gameId: "0b6f4c8e-1d2a-4f3b-9c5e-7a8d9e0f1a01",
       playerProfile: { behaviorType: "conservative" },
     },
     profile,
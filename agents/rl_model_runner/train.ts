@@ -3,7 +3,7 @@
  * Each episode: orchestrate a session, adapt the profile based on reward signal,
  * decay epsilon, and export experience records to JSONL for offline Gymnasium training.
  */
-import type { DefaultApi } from "../ts-client";
+import type { BehaviorType, DefaultApi } from "../ts-client";
 import type { ProfileConfig } from "../strategic_planner/profiles/types";
 import { adaptProfile } from "./adapt";
 import { decayEpsilon, initialPolicyState, type PolicyState } from "./infer";
@@ -19,7 +19,7 @@ export interface TrainingConfig {
   /** Game ID passed to createSession on each episode. */
   gameId: string;
   /** Behavior type string forwarded to the player profile. Default: "conservative". */
-  behaviorType?: string;
+  behaviorType?: BehaviorType;
   /**
    * Override inter-action delay (ms). Useful in tests to pass `() => 0`.
    * If omitted, the orchestrator uses the profile's delayMsMin/delayMsMax range.
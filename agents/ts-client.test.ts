@@ -38,6 +38,9 @@ test("Wallet type is usable for typed payloads", () => {
     balance: { amount: 100, currency: "AUD" },
     dailyLimit: { amount: 500, currency: "AUD" },
     dailySpent: { amount: 0, currency: "AUD" },
+    weeklySpent: { amount: 0, currency: "AUD" },
+    monthlySpent: { amount: 0, currency: "AUD" },
+    timezone: "UTC",
   };
   expect(w.walletId).toBeDefined();
   expect(w.balance.amount).toBe(100);
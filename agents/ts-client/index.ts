@@ -3,7 +3,20 @@
  * Regenerate with: bun run generate:client (from agents/).
  */
 
-export type Currency = "AUD" | "USD" | "EUR";
+/** ISO 4217 code; amounts have 2 decimal places except JPY (0) and KWD (3). */
+export type Currency =
+  | "AUD"
+  | "USD"
+  | "EUR"
+  | "GBP"
+  | "NZD"
+  | "CAD"
+  | "CHF"
+  | "JPY"
+  | "CNY"
+  | "HKD"
+  | "SGD"
+  | "KWD";
 
 export interface Money {
   amount: number;
@@ -13,11 +26,21 @@ export interface Money {
 export type GameId = string;
 export type SessionId = string;
 
+/** Spend totals reset at the end of the local day, ISO week and month in `timezone`. */
 export interface Wallet {
   walletId: SessionId;
   balance: Money;
   dailyLimit: Money;
   dailySpent: Money;
+  weeklyLimit?: Money;
+  weeklySpent: Money;
+  monthlyLimit?: Money;
+  monthlySpent: Money;
+  /** IANA timezone, e.g. "Australia/Sydney". */
+  timezone: string;
+  spentAt?: string;
+  /** Frozen wallets refuse debits; credits still land. */
+  frozen?: boolean;
 }
 
 export interface ErrorResponse {
@@ -46,11 +69,24 @@ export type GameState =
   | "Probing"
   | "Playing"
   | "Evaluating"
-  | "Completed";
+  | "Completed"
+  | "Expired"
+  | "Suspended"
+  | "Aborted";
 
 export interface SessionMetrics {
   totalSpins: number;
   totalPayout: number;
+  totalStaked?: number;
+  /** totalPayout − totalStaked. */
+  netResult?: number;
+  biggestWin?: number;
+  peakNetResult?: number;
+  /** peakNetResult − netResult. */
+  currentDrawdown?: number;
+  lastActionAt?: string;
+  /** When the session was expired for inactivity. */
+  expiredAt?: string;
 }
 
 export interface Session {
@@ -58,16 +94,46 @@ export interface Session {
   gameId: GameId;
   state: GameState;
   metrics: SessionMetrics;
+  playerProfile?: PlayerProfile;
+  version?: number;
+  updatedAt?: string;
+  createdAt?: string;
+  tags?: string[];
+  walletId?: SessionId;
+}
+
+/** Filters and paging for GET /sessions. */
+export interface ListSessionsParams {
+  state?: GameState;
+  gameId?: GameId;
+  createdFrom?: string;
+  createdTo?: string;
+  tags?: string[];
+  sort?: "createdAt" | "updatedAt";
+  order?: "asc" | "desc";
+  cursor?: string;
+  limit?: number;
+}
+
+export interface SessionListResponse {
+  sessions: Session[];
+  nextCursor?: string;
 }
 
+/** Simulated player behaviour; "mixed" is also accepted for mixed_adaptive. */
+export type BehaviorType = "conservative" | "aggressive" | "mixed_adaptive";
+
 export interface PlayerProfile {
-  behaviorType: string;
+  behaviorType: BehaviorType;
+  /** Larger PlaceBet amounts are rejected with INVALID_INPUT. */
   maxBet?: Money;
 }
 
 export interface CreateSessionRequest {
   gameId: GameId;
   playerProfile: PlayerProfile;
+  tags?: string[];
+  walletId?: SessionId;
 }
 
 export interface CreateSessionResponse {
@@ -109,18 +175,92 @@ export type WalletOperationType = "debit" | "credit";
 export interface WalletOperationRequest {
   operation: WalletOperationType;
   amount: Money;
+  /** Cost borne by the house, booked to the fees account; the wallet moves `amount` either way. */
+  fee?: Money;
 }
 
 export interface WalletOperationResponse {
   wallet: Wallet;
 }
 
+/** One leg of a ledger entry; `amount` is negative when money leaves `account`. */
+export interface LedgerPosting {
+  /** `wallet:<id>`, `house:<gameId>`, `house` or `fees`. */
+  account: string;
+  amount: Money;
+}
+
+/** Immutable record of one wallet balance change; its postings sum to zero. */
+export interface LedgerEntry {
+  entryId: string;
+  walletId: SessionId;
+  sequence: number;
+  kind: "opening" | "debit" | "credit";
+  postings: LedgerPosting[];
+  balanceAfter: Money;
+  sessionId?: SessionId;
+  gameId?: GameId;
+  eventId?: string;
+  createdAt: string;
+}
+
+export interface WalletTransactionsResponse {
+  transactions: LedgerEntry[];
+  nextCursor?: string;
+}
+
+export interface WalletReconciliation {
+  walletId: SessionId;
+  balance: Money;
+  ledgerBalance: Money;
+  consistent: boolean;
+}
+
+export interface WalletListResponse {
+  wallets: Wallet[];
+  nextCursor?: string;
+}
+
+/** Request body for PUT /wallets/{id}/limits; omitted weekly/monthly limits are removed. */
+export interface SetWalletLimitsRequest {
+  dailyLimit: Money;
+  weeklyLimit?: Money;
+  monthlyLimit?: Money;
+  reason?: string;
+}
+
+export type WalletAdminAction =
+  | { type: "setLimits"; dailyLimit: Money; weeklyLimit?: Money; monthlyLimit?: Money }
+  | { type: "freeze" }
+  | { type: "unfreeze" }
+  | { type: "resetDailySpend" };
+
+/** One admin change to a wallet, with the wallet before and after it. */
+export interface WalletAuditRecord {
+  walletId: SessionId;
+  action: WalletAdminAction;
+  /** Id of the admin's API key. */
+  actor: string;
+  reason?: string;
+  before: Wallet;
+  after: Wallet;
+  timestamp: string;
+}
+
+export interface WalletAuditResponse {
+  records: WalletAuditRecord[];
+}
+
 /** Request body for POST /wallets — create a new wallet. */
 export interface CreateWalletRequest {
   /** Client-supplied wallet ID; server generates one if absent. */
   walletId?: SessionId;
   balance: Money;
   dailyLimit: Money;
+  weeklyLimit?: Money;
+  monthlyLimit?: Money;
+  /** Defaults to "UTC". */
+  timezone?: string;
 }
 
 /** Single event record from GET /sessions/{id}/events. */
@@ -243,6 +383,19 @@ export class DefaultApi {
     return handleResponse<CreateSessionResponse>(res);
   }
 
+  async listSessions(params: ListSessionsParams = {}): Promise<SessionListResponse> {
+    const query = new URLSearchParams();
+    for (const [key, value] of Object.entries(params)) {
+      if (value === undefined) continue;
+      query.set(key, Array.isArray(value) ? value.join(",") : String(value));
+    }
+    const res = await fetch(
+      buildUrl(this.config, `/sessions?${query.toString()}`),
+      withAuth(this.config)
+    );
+    return handleResponse<SessionListResponse>(res);
+  }
+
   async getSession(sessionId: SessionId): Promise<Session> {
     const res = await fetch(
       buildUrl(this.config, `/sessions/${encodeURIComponent(sessionId)}`),
@@ -283,6 +436,28 @@ export class DefaultApi {
     return handleResponse<WalletOperationResponse>(res);
   }
 
+  async listWalletTransactions(
+    walletId: SessionId,
+    params: { cursor?: string; limit?: number } = {}
+  ): Promise<WalletTransactionsResponse> {
+    const query = new URLSearchParams();
+    if (params.cursor !== undefined) query.set("cursor", params.cursor);
+    if (params.limit !== undefined) query.set("limit", String(params.limit));
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}/transactions?${query.toString()}`),
+      withAuth(this.config)
+    );
+    return handleResponse<WalletTransactionsResponse>(res);
+  }
+
+  async reconcileWallet(walletId: SessionId): Promise<WalletReconciliation> {
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}/reconciliation`),
+      withAuth(this.config)
+    );
+    return handleResponse<WalletReconciliation>(res);
+  }
+
   async createWallet(req: CreateWalletRequest): Promise<Wallet> {
     const res = await fetch(buildUrl(this.config, "/wallets"), {
       method: "POST",
@@ -293,6 +468,63 @@ export class DefaultApi {
     return handleResponse<Wallet>(res);
   }
 
+  async getWallet(walletId: SessionId): Promise<Wallet> {
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}`),
+      withAuth(this.config)
+    );
+    return handleResponse<Wallet>(res);
+  }
+
+  async listWallets(params: { cursor?: string; limit?: number } = {}): Promise<WalletListResponse> {
+    const query = new URLSearchParams();
+    if (params.cursor !== undefined) query.set("cursor", params.cursor);
+    if (params.limit !== undefined) query.set("limit", String(params.limit));
+    const res = await fetch(buildUrl(this.config, `/wallets?${query.toString()}`), withAuth(this.config));
+    return handleResponse<WalletListResponse>(res);
+  }
+
+  /** Admin only. */
+  async setWalletLimits(walletId: SessionId, req: SetWalletLimitsRequest): Promise<Wallet> {
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}/limits`),
+      {
+        method: "PUT",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(req),
+        ...withAuth(this.config),
+      }
+    );
+    return handleResponse<Wallet>(res);
+  }
+
+  /** Admin only: `freeze`, `unfreeze` or `reset-daily-spend`. */
+  async administerWallet(
+    walletId: SessionId,
+    action: "freeze" | "unfreeze" | "reset-daily-spend",
+    reason?: string
+  ): Promise<Wallet> {
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}/${action}`),
+      {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ reason }),
+        ...withAuth(this.config),
+      }
+    );
+    return handleResponse<Wallet>(res);
+  }
+
+  /** Admin only. */
+  async getWalletAudit(walletId: SessionId): Promise<WalletAuditResponse> {
+    const res = await fetch(
+      buildUrl(this.config, `/wallets/${encodeURIComponent(walletId)}/audit`),
+      withAuth(this.config)
+    );
+    return handleResponse<WalletAuditResponse>(res);
+  }
+
   async getSessionEvents(sessionId: SessionId): Promise<SessionEventsResponse> {
     const res = await fetch(
       buildUrl(this.config, `/sessions/${encodeURIComponent(sessionId)}/events`),
@@ -22,9 +22,14 @@ impl IntoResponse for HttpError {
     fn into_response(self) -> Response {
         let (status, body) = match &self.0 {
             DomainError::NotFound(_) => (StatusCode::NOT_FOUND, ErrorResponse::not_found(self.0.to_string())),
-            DomainError::InvalidTransition { .. } => (StatusCode::CONFLICT, ErrorResponse::state_error(self.0.to_string())),
+            DomainError::InvalidTransition { .. }
+            | DomainError::VersionConflict { .. }
+            | DomainError::ActionNotAllowed { .. }
+            | DomainError::WalletFrozen(_)
+            | DomainError::Conflict(_) => (StatusCode::CONFLICT, ErrorResponse::state_error(self.0.to_string())),
             DomainError::WalletLimitExceeded => (StatusCode::PAYMENT_REQUIRED, ErrorResponse::wallet_limit_exceeded(self.0.to_string())),
             DomainError::InvalidInput(_) => (StatusCode::BAD_REQUEST, ErrorResponse::invalid_input(self.0.to_string())),
+            DomainError::Forbidden(_) => (StatusCode::FORBIDDEN, ErrorResponse::unauthorized(self.0.to_string())),
             DomainError::RateLimitExceeded => (StatusCode::TOO_MANY_REQUESTS, ErrorResponse::from_code(ErrorCode::RateLimit, self.0.to_string())),
             DomainError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, ErrorResponse::internal_error(self.0.to_string())),
         };
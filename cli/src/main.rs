@@ -1,13 +1,19 @@
 //! CLI entrypoint for the gaming fingerprinting system.
 
 use clap::Parser;
-use controller::app_state::AppState;
-use controller::event_store::InMemoryEventStore;
-use controller::fingerprinter::InMemoryFingerprintStore;
-use controller::persistence_metrics::{InMemorySessionStore, InMemoryWalletStore};
+use controller::app_state::{AppState, GameRepository, SessionRepository, WalletRepository};
+use controller::event_store::{EventStore, InMemoryEventStore, PostgresEventStore};
+use controller::fingerprinter::{FingerprintStore, InMemoryFingerprintStore, PostgresFingerprintStore};
+use controller::fx::StaticFxRates;
+use controller::persistence_metrics::{
+    InMemoryGameStore, InMemorySessionStore, InMemoryWalletStore, PostgresGameStore,
+    PostgresSessionStore, PostgresWalletStore,
+};
 use controller::rl_feedback_loop::{ExperienceStore, InMemoryStore as InMemoryRlStore, PostgresRlStore};
+use controller::slot_engine::{theoretical_profile, GameDefinition, DEFAULT_ENUMERATION_LIMIT};
 use sqlx::postgres::PgPoolOptions;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 mod config;
@@ -25,6 +31,17 @@ enum Cli {
         #[arg(long)]
         bind: Option<SocketAddr>,
     },
+    /// Compute theoretical RTP, hit frequency and volatility for a game definition.
+    Rtp {
+        /// Path to a .json or .toml game definition.
+        definition: PathBuf,
+        /// Enumerate every reel-stop combination up to this count; above it use exact analytics.
+        #[arg(long, default_value_t = DEFAULT_ENUMERATION_LIMIT)]
+        max_combinations: u64,
+        /// Print the profile as JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[tokio::main]
@@ -43,7 +60,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             let addr = bind.unwrap_or(cfg.bind);
             let app_config = cfg.to_app_config();
 
-            let rl_store: Arc<dyn ExperienceStore> = if let Some(ref db_url) = cfg.database_url {
+            let pool = if let Some(ref db_url) = cfg.database_url {
                 tracing::info!("Connecting to database");
                 let pool = PgPoolOptions::new()
                     .max_connections(5)
@@ -53,23 +70,85 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     .run(&pool)
                     .await?;
                 tracing::info!("Migrations applied successfully");
-                Arc::new(PostgresRlStore::new(pool))
+                Some(pool)
             } else {
-                tracing::warn!("DATABASE_URL not set — using in-memory RL store (ephemeral)");
-                Arc::new(InMemoryRlStore::new())
+                tracing::warn!("DATABASE_URL not set — using in-memory sessions, games, wallets, events, fingerprints and RL store (ephemeral)");
+                None
+            };
+
+            #[allow(clippy::type_complexity)]
+            let (session_repo, game_repo, wallet_repo, event_store, fingerprint_store, rl_store): (
+                Arc<dyn SessionRepository>,
+                Arc<dyn GameRepository>,
+                Arc<dyn WalletRepository>,
+                Arc<dyn EventStore>,
+                Arc<dyn FingerprintStore>,
+                Arc<dyn ExperienceStore>,
+            ) = match pool {
+                Some(pool) => (
+                    Arc::new(PostgresSessionStore::new(pool.clone())),
+                    Arc::new(PostgresGameStore::new(pool.clone())),
+                    Arc::new(PostgresWalletStore::new(pool.clone())),
+                    Arc::new(PostgresEventStore::new(pool.clone())),
+                    Arc::new(PostgresFingerprintStore::new(pool.clone())),
+                    Arc::new(PostgresRlStore::new(pool)),
+                ),
+                None => (
+                    Arc::new(InMemorySessionStore::new()),
+                    Arc::new(InMemoryGameStore::new()),
+                    Arc::new(InMemoryWalletStore::new()),
+                    Arc::new(InMemoryEventStore::new()),
+                    Arc::new(InMemoryFingerprintStore::new()),
+                    Arc::new(InMemoryRlStore::new()),
+                ),
             };
 
-            let state = AppState::with_config(
-                Arc::new(InMemorySessionStore::new()),
-                Arc::new(InMemoryWalletStore::new()),
-                Arc::new(InMemoryEventStore::new()),
-                Arc::new(InMemoryFingerprintStore::new()),
+            let mut state = AppState::with_config(
+                session_repo,
+                game_repo,
+                wallet_repo,
+                event_store,
+                fingerprint_store,
                 rl_store,
                 cfg.api_keys.as_deref(),
                 app_config,
             );
+            if let Some(path) = &cfg.fx_rates_file {
+                state = state.with_fx_rates(Arc::new(StaticFxRates::load_file(path)?));
+                tracing::info!(file = %path.display(), "exchange rates loaded");
+            }
+
+            if cfg.games_dir.is_dir() {
+                for def in GameDefinition::load_dir(&cfg.games_dir)? {
+                    let game = state.game_catalog.register(def)?;
+                    // Definitions seed the registry once; later PATCHes to the record are kept.
+                    if state.game_repo.get_by_id(game.definition.game_id).await?.is_none() {
+                        state.game_repo.create(game.to_game()).await?;
+                    }
+                    tracing::info!(game_id = %game.definition.game_id, name = %game.definition.name, "game registered");
+                }
+            } else {
+                tracing::warn!(dir = %cfg.games_dir.display(), "games directory not found — no game definitions registered");
+            }
             server::serve(addr, state).await?;
         }
+        Cli::Rtp { definition, max_combinations, json } => {
+            let def = GameDefinition::load_file(&definition)?;
+            let profile = theoretical_profile(&def.to_machine()?, max_combinations);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&profile)?);
+            } else {
+                println!("game            {} ({})", def.name, def.game_id);
+                println!("method          {:?} over {} combinations", profile.method, profile.combinations);
+                println!("rtp             {:.6} (target {:.4}, delta {:+.6})", profile.rtp, def.rtp_target, profile.rtp - def.rtp_target);
+                println!("hit frequency   {:.6}", profile.hit_frequency);
+                println!("variance        {:.6}", profile.variance);
+                println!("std dev         {:.6}", profile.std_dev);
+                if let Some(max) = profile.max_multiplier {
+                    println!("max win         {max:.2}x stake");
+                }
+            }
+        }
     }
     Ok(())
 }
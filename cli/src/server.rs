@@ -2,31 +2,49 @@
 
 use axum::{
     extract::{Path, Query, Request, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::{get, post},
-    Json, Router,
+    routing::{get, post, put},
+    Extension, Json, Router,
 };
 use controller::api::{
-    CreateSessionRequest, CreateSessionResponse, CreateWalletRequest, Currency, ErrorCode,
-    ErrorResponse, GameFingerprintResponse, GameplayAction, GameplayActionType, GameplayResult,
-    HealthResponse, Money, PlayActionRequest, PlayActionResponse, Session, SessionEventRecord,
-    SessionEventsResponse, SessionId, WalletOperationRequest, WalletOperationResponse,
+    CreateGameRequest, CreateSessionRequest, CreateSessionResponse, CreateWalletRequest, Currency,
+    ErrorCode, ErrorResponse, FingerprintVersionsResponse, Game, GameFingerprintResponse, GameId, GameplayAction,
+    GameplayActionType, GameplayResult, GamesResponse, HealthResponse, Money, PlayActionRequest,
+    PlayActionResponse, Session, SessionEventRecord, SessionEventsResponse, SessionId,
+    SessionListResponse, SessionMetrics, SessionTransitionsResponse,
+    SetWalletLimitsRequest, UpdateGameRequest, Wallet, WalletAdminAction, WalletAdminRequest,
+    WalletAuditResponse, WalletListResponse, WalletOperationRequest, WalletOperationResponse,
+    WalletOperationType, WalletReconciliation, WalletTransactionsResponse,
+};
+use controller::app_state::{
+    AppState, DomainError, SessionCursor, SessionQuery, SessionSort, SortOrder, TransitionCause,
+};
+use controller::auth::{api_key_id, parse_bearer_token, validate_token, Caller, Role};
+use controller::fingerprinter::{
+    detect_drift, diff_fingerprints, fairness_audit, fingerprint_from_samples, timed_spin_samples,
+    AuditConfig, DriftConfig, DriftReport, FairnessReport, FingerprintDiff, GameFingerprint,
+    SpinSample,
+};
+use controller::ledger::LedgerCause;
+use controller::game_session_manager::{
+    recover_sessions, spawn_reaper, EffectExecutor, GameSessionManager,
 };
-use controller::app_state::{AppState, DomainError};
-use controller::auth::{parse_bearer_token, validate_token, Role};
-use controller::fingerprinter::GameFingerprint;
-use controller::game_session_manager::GameSessionManager;
 use controller::event_store::GameplayEvent;
 use controller::rl_feedback_loop::{
     compute_reward_safe, export_experiences, Experience, ExportParams,
 };
-use controller::state_engine::GameState;
+use controller::slot_engine::SlotMachine;
+use controller::state_engine::{Effect, GameEvent, GameState, TransitionContext};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::net::SocketAddr;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 use crate::error::HttpError;
@@ -35,13 +53,33 @@ use crate::error::HttpError;
 
 pub fn app(state: AppState) -> Router {
     let protected = Router::new()
-        .route("/sessions", post(create_session_handler))
+        .route("/sessions", post(create_session_handler).get(list_sessions_handler))
         .route("/sessions/:id", get(get_session_handler))
         .route("/sessions/:id/action", post(play_action_handler))
         .route("/sessions/:id/events", get(session_events_handler))
-        .route("/wallets", post(create_wallet_handler))
+        .route("/sessions/:id/transitions", get(session_transitions_handler))
+        .route("/sessions/:id/suspend", post(suspend_session_handler))
+        .route("/sessions/:id/resume", post(resume_session_handler))
+        .route("/sessions/:id/abort", post(abort_session_handler))
+        .route("/wallets", post(create_wallet_handler).get(list_wallets_handler))
+        .route("/wallets/:id", get(get_wallet_handler))
         .route("/wallets/:id/operations", post(wallet_operation_handler))
+        .route("/wallets/:id/limits", put(set_wallet_limits_handler))
+        .route("/wallets/:id/freeze", post(freeze_wallet_handler))
+        .route("/wallets/:id/unfreeze", post(unfreeze_wallet_handler))
+        .route("/wallets/:id/reset-daily-spend", post(reset_daily_spend_handler))
+        .route("/wallets/:id/audit", get(wallet_audit_handler))
+        .route("/wallets/:id/transactions", get(wallet_transactions_handler))
+        .route("/wallets/:id/reconciliation", get(wallet_reconciliation_handler))
+        .route("/games", post(create_game_handler).get(list_games_handler))
+        .route("/games/:id", get(get_game_handler).patch(update_game_handler))
         .route("/games/:id/fingerprint", get(game_fingerprint_handler))
+        .route("/games/:id/fingerprint/run", post(run_fingerprint_handler))
+        .route("/games/:id/fingerprint/audit", get(fairness_audit_handler))
+        .route("/games/:id/fingerprint/versions", get(fingerprint_versions_handler))
+        .route("/games/:id/fingerprint/versions/:version", get(fingerprint_version_handler))
+        .route("/games/:id/fingerprint/diff", get(fingerprint_diff_handler))
+        .route("/games/:id/fingerprint/drift", get(fingerprint_drift_handler))
         .route("/rl/export", get(rl_export_handler))
         .route("/metrics", get(metrics_handler))
         .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit_middleware))
@@ -61,6 +99,9 @@ pub fn v1_app(state: AppState) -> Router {
 }
 
 pub async fn serve(addr: SocketAddr, state: AppState) -> Result<(), std::io::Error> {
+    // Settle what a previous process left half-done before taking new actions.
+    recover_sessions(&state).await.map_err(std::io::Error::other)?;
+    spawn_reaper(state.clone());
     let router = v1_app(state);
     info!(%addr, "listening");
     let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -71,7 +112,7 @@ pub async fn serve(addr: SocketAddr, state: AppState) -> Result<(), std::io::Err
 
 async fn auth_middleware(
     State(state): State<AppState>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Response {
     let auth_header = request.headers().get("Authorization").and_then(|v| v.to_str().ok());
@@ -85,13 +126,14 @@ async fn auth_middleware(
                 .into_response();
         }
     };
-    if validate_token(&token, &state.api_keys).is_err() {
+    let Ok(role) = validate_token(&token, &state.api_keys) else {
         return (
             StatusCode::UNAUTHORIZED,
             Json(ErrorResponse::unauthorized("Missing or invalid Authorization")),
         )
             .into_response();
-    }
+    };
+    request.extensions_mut().insert(Caller { key_id: api_key_id(&token), role });
     next.run(request).await
 }
 
@@ -135,38 +177,225 @@ async fn health_handler() -> Json<HealthResponse> {
 async fn create_session_handler(
     State(state): State<AppState>,
     Json(req): Json<CreateSessionRequest>,
-) -> Result<(StatusCode, Json<CreateSessionResponse>), HttpError> {
-    let mgr = GameSessionManager::new(state.session_repo.clone());
+) -> Result<(StatusCode, ETagHeader, Json<CreateSessionResponse>), HttpError> {
+    if let Some(wallet_id) = req.wallet_id {
+        state
+            .wallet_repo
+            .get_by_id(wallet_id)
+            .await?
+            .ok_or(DomainError::NotFound(wallet_id))?;
+    }
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
     let resp = mgr.create_session(req).await?;
     state.metrics.record_session_created();
-    Ok((StatusCode::CREATED, Json(resp)))
+    // New sessions start at version 1.
+    Ok((StatusCode::CREATED, etag(1), Json(resp)))
+}
+
+/// Query params for GET /sessions
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListSessionsQuery {
+    state: Option<GameState>,
+    game_id: Option<Uuid>,
+    /// Inclusive.
+    created_from: Option<DateTime<Utc>>,
+    /// Exclusive.
+    created_to: Option<DateTime<Utc>>,
+    /// Comma-separated; sessions must carry all of them.
+    tags: Option<String>,
+    #[serde(default)]
+    sort: SessionSort,
+    #[serde(default)]
+    order: SortOrder,
+    /// `nextCursor` of the previous page.
+    cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: u32,
+}
+
+/// GET /sessions — filtered sessions, newest first by default, paged by cursor.
+#[tracing::instrument(skip(state))]
+async fn list_sessions_handler(
+    State(state): State<AppState>,
+    Query(q): Query<ListSessionsQuery>,
+) -> Result<Json<SessionListResponse>, HttpError> {
+    let query = SessionQuery {
+        state: q.state,
+        game_id: q.game_id,
+        created_from: q.created_from,
+        created_to: q.created_to,
+        tags: q
+            .tags
+            .iter()
+            .flat_map(|t| t.split(','))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+        sort: q.sort,
+        order: q.order,
+        after: q.cursor.as_deref().map(SessionCursor::decode).transpose()?,
+        limit: q.limit.clamp(1, 1_000),
+    };
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+    let (sessions, next) = mgr.list_sessions(query).await?;
+    Ok(Json(SessionListResponse {
+        sessions,
+        next_cursor: next.map(|c| c.encode()),
+    }))
 }
 
 #[tracing::instrument(skip(state), fields(session_id = %id))]
 async fn get_session_handler(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Session>, HttpError> {
-    let mgr = GameSessionManager::new(state.session_repo.clone());
+) -> Result<(ETagHeader, Json<Session>), HttpError> {
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
     let session = mgr
         .get_session(SessionId(id))
         .await
         .map_err(HttpError::from)?
         .ok_or(HttpError::from(DomainError::NotFound(id)))?;
-    Ok(Json(session))
+    Ok((etag(session.version), Json(session)))
 }
 
-#[tracing::instrument(skip(state), fields(session_id = %id))]
+/// Session lifecycle operations outside gameplay.
+#[derive(Debug, Clone, Copy)]
+enum Lifecycle {
+    Suspend,
+    Resume,
+    Abort,
+}
+
+#[tracing::instrument(skip(state, headers), fields(session_id = %id))]
+async fn suspend_session_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+) -> Result<(ETagHeader, Json<Session>), HttpError> {
+    lifecycle(&state, id, caller, &headers, Lifecycle::Suspend).await
+}
+
+#[tracing::instrument(skip(state, headers), fields(session_id = %id))]
+async fn resume_session_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+) -> Result<(ETagHeader, Json<Session>), HttpError> {
+    lifecycle(&state, id, caller, &headers, Lifecycle::Resume).await
+}
+
+#[tracing::instrument(skip(state, headers), fields(session_id = %id))]
+async fn abort_session_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
+) -> Result<(ETagHeader, Json<Session>), HttpError> {
+    lifecycle(&state, id, caller, &headers, Lifecycle::Abort).await
+}
+
+/// Applies a lifecycle event with the same If-Match handling as gameplay actions.
+/// Resume returns the session to the state its latest Suspend came from.
+async fn lifecycle(
+    state: &AppState,
+    id: Uuid,
+    caller: Caller,
+    headers: &HeaderMap,
+    op: Lifecycle,
+) -> Result<(ETagHeader, Json<Session>), HttpError> {
+    let if_match = if_match_versions(headers)?;
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+    let current = mgr
+        .get_session(SessionId(id))
+        .await?
+        .ok_or(DomainError::NotFound(id))?;
+    if let Some(versions) = &if_match {
+        if !versions.contains(&current.version) {
+            return Err(HttpError::from(DomainError::VersionConflict {
+                expected: versions[0],
+                current: current.version,
+            }));
+        }
+    }
+    let event = match op {
+        Lifecycle::Suspend => GameEvent::Suspend,
+        Lifecycle::Abort => GameEvent::Abort,
+        Lifecycle::Resume => GameEvent::Resume {
+            // Outside Suspended the event is rejected whatever its target.
+            to: mgr.suspended_from(SessionId(id)).await?.unwrap_or(current.state),
+        },
+    };
+    let wallet = match current.wallet_id {
+        Some(wallet_id) => state.wallet_repo.get_by_id(wallet_id).await?,
+        None => None,
+    };
+    let currency = wallet.map(|w| w.balance.currency);
+    let mut effects = ActionEffects::new(state, &current, &state.slot_machine, currency, None);
+    let cause = TransitionCause { actor: Some(caller.key_id), ..Default::default() };
+    let session = mgr
+        .apply_event(SessionId(id), &event, &TransitionContext::default(), Some(current.version), cause, &mut effects)
+        .await?;
+    Ok((etag(session.version), Json(session)))
+}
+
+/// `ETag` response header carrying a session version.
+type ETagHeader = [(header::HeaderName, HeaderValue); 1];
+
+/// Strong ETag for a session version.
+fn etag(version: u64) -> ETagHeader {
+    let value = HeaderValue::from_str(&format!("\"{version}\"")).expect("quoted integer is a valid header value");
+    [(header::ETAG, value)]
+}
+
+/// Session versions listed in `If-Match`; None when absent or `*`.
+fn if_match_versions(headers: &HeaderMap) -> Result<Option<Vec<u64>>, HttpError> {
+    let Some(raw) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || HttpError::from(DomainError::InvalidInput("malformed If-Match header".into()));
+    let raw = raw.to_str().map_err(|_| invalid())?.trim();
+    if raw == "*" {
+        return Ok(None);
+    }
+    raw.split(',')
+        .map(|tag| {
+            tag.trim()
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .and_then(|t| t.parse().ok())
+                .ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<u64>, _>>()
+        .map(Some)
+}
+
+/// POST /sessions/{id}/action. `If-Match` pins the session version the client last saw;
+/// a stale version (or a concurrent action winning the race) is 409 STATE_ERROR.
+#[tracing::instrument(skip(state, headers), fields(session_id = %id))]
 async fn play_action_handler(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    headers: HeaderMap,
     Json(req): Json<PlayActionRequest>,
-) -> Result<Json<PlayActionResponse>, HttpError> {
-    let mgr = GameSessionManager::new(state.session_repo.clone());
-    let next_state = match req.action.action_type {
-        GameplayActionType::PlaceBet => GameState::Playing,
-        GameplayActionType::Spin => GameState::Evaluating,
-        GameplayActionType::CashOut => GameState::Completed,
+) -> Result<(ETagHeader, Json<PlayActionResponse>), HttpError> {
+    let if_match = if_match_versions(&headers)?;
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+    let event = match req.action.action_type {
+        GameplayActionType::PlaceBet => {
+            let amount = req.action.amount.as_ref().ok_or_else(|| {
+                HttpError::from(DomainError::InvalidInput("PlaceBet requires an amount".into()))
+            })?;
+            GameEvent::PlaceBet { amount: amount.amount }
+        }
+        GameplayActionType::Spin => GameEvent::Spin {
+            stake: req.action.amount.as_ref().map(|m| m.amount),
+        },
+        GameplayActionType::CashOut => GameEvent::CashOut,
     };
 
     // Capture previous state for the RL experience record.
@@ -176,35 +405,110 @@ async fn play_action_handler(
         .map_err(HttpError::from)?
         .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
     let prev_state = prev_session.state;
+    if let Some(versions) = &if_match {
+        if !versions.contains(&prev_session.version) {
+            return Err(HttpError::from(DomainError::VersionConflict {
+                expected: versions[0],
+                current: prev_session.version,
+            }));
+        }
+    }
 
-    let session = mgr.transition_session(SessionId(id), next_state).await?;
-
-    // Update lifecycle metrics.
-    match session.state {
-        GameState::Playing => state.metrics.record_session_playing(),
-        GameState::Completed => state.metrics.record_session_completed(),
-        _ => {}
+    let game = state.game_catalog.get(prev_session.game_id.0);
+    if let (Some(game), Some(amount)) = (&game, &req.action.amount) {
+        let ladder = &game.definition.bet_ladder;
+        let on_ladder = |b: &f64| Money::from_f64(*b, amount.currency).is_ok_and(|b| b.amount == amount.amount);
+        if !ladder.iter().any(on_ladder) {
+            return Err(HttpError::from(DomainError::InvalidInput(format!(
+                "bet {} is not on the bet ladder {:?}",
+                amount.amount, ladder
+            ))));
+        }
+    }
+    // The profile's max bet caps every explicit stake: a PlaceBet amount or a Spin override.
+    if let (Some(max), Some(amount)) = (&prev_session.player_profile.max_bet, &req.action.amount) {
+        if amount.currency != max.currency {
+            return Err(HttpError::from(DomainError::InvalidInput(format!(
+                "bet currency {} does not match max bet currency {}",
+                amount.currency, max.currency
+            ))));
+        }
+        if amount.amount > max.amount {
+            return Err(HttpError::from(DomainError::InvalidInput(format!(
+                "bet {} exceeds the player's max bet {}",
+                amount.amount, max.amount
+            ))));
+        }
     }
 
-    let result = simulate_result(&req.action);
+    // A Spin without an amount stakes the session's most recent PlaceBet.
+    let bet = match (&event, &req.action.amount) {
+        (GameEvent::Spin { .. }, None) => last_bet_amount(&state, id).await,
+        _ => req.action.amount.clone(),
+    };
+    // The wallet's balance, in the bet's currency, arms the state machine's balance guards: a
+    // stake it cannot cover is WALLET_LIMIT_EXCEEDED before any transition. A bet in a currency
+    // the FX rates cannot convert is INVALID_INPUT.
+    let wallet = match prev_session.wallet_id {
+        Some(wallet_id) => Some(
+            state
+                .wallet_repo
+                .get_by_id(wallet_id)
+                .await?
+                .ok_or(DomainError::NotFound(wallet_id))?,
+        ),
+        None => None,
+    };
+    let balance = match (&wallet, &bet) {
+        (Some(wallet), Some(bet)) => Some(state.convert(&wallet.balance, bet.currency).await?.amount),
+        (Some(wallet), None) => Some(wallet.balance.amount),
+        (None, _) => None,
+    };
+    let ctx = TransitionContext {
+        current_bet: bet.as_ref().map(|m| m.amount),
+        balance,
+    };
+    let machine = game.as_ref().map_or(state.slot_machine.as_ref(), |g| &g.machine);
+    // Money moves in the bet's currency, else the wallet's; an unbound session without a bet
+    // moves none.
+    let currency = bet.as_ref().map(|m| m.currency).or(wallet.map(|w| w.balance.currency));
+    // The transition names the event it awaits so startup recovery can spot an interrupted
+    // action, and records the stake actually played so recovery can refund a voided spin.
+    let event_id = Uuid::new_v4();
+    let mut effects = ActionEffects::new(&state, &prev_session, machine, currency, Some(event_id));
+    let played = GameplayAction { action_type: req.action.action_type.clone(), amount: bet };
+    let cause = TransitionCause {
+        actor: Some(caller.key_id),
+        event_id: Some(event_id),
+        request: serde_json::to_value(&played).ok(),
+        ..Default::default()
+    };
+    let session = mgr
+        .apply_event(SessionId(id), &event, &ctx, Some(prev_session.version), cause, &mut effects)
+        .await?;
+    let result = effects.result;
 
     // Compute reward and persist event + experience.
-    let payout = result.payout.as_ref().map(|m| m.amount).unwrap_or(0.0);
-    let stake = req.action.amount.as_ref().map(|m| m.amount).unwrap_or(0.0);
+    let payout = result.payout.as_ref().map_or(Decimal::ZERO, |m| m.amount);
+    // The stake actually played: an amount-less Spin stakes the session's last bet.
+    let stake = played.amount.as_ref().map_or(Decimal::ZERO, |m| m.amount);
     let cost = state.config.cost_per_spin;
     let likeness = req.human_likeness.unwrap_or(0.5).clamp(0.0, 1.0);
     let reward = compute_reward_safe(payout, stake, cost, likeness);
 
     let event = GameplayEvent {
-        event_id: Uuid::new_v4(),
+        event_id,
         session_id: id,
         action: serde_json::to_value(&req.action).unwrap_or_default(),
         result: serde_json::to_value(&result).unwrap_or_default(),
         timestamp: Some(chrono::Utc::now()),
         reward: Some(reward),
     };
-    if let Err(e) = state.event_store.insert(event) {
-        tracing::warn!(%id, error = %e, "failed to persist gameplay event");
+    // The action is committed with its transition (and the spin's money moved with it), so a
+    // failed event write cannot undo it: the result is still reported, and startup recovery
+    // writes the missing event from the ledger.
+    if let Err(e) = state.event_store.insert(event).await {
+        warn!(%id, %event_id, error = %e, "failed to persist gameplay event; left for recovery");
     }
 
     let done = session.state == GameState::Completed;
@@ -220,7 +524,7 @@ async fn play_action_handler(
         tracing::warn!(%id, error = %e, "failed to persist RL experience");
     }
 
-    Ok(Json(PlayActionResponse { session, result }))
+    Ok((etag(session.version), Json(PlayActionResponse { session, result })))
 }
 
 #[tracing::instrument(skip(state), fields(wallet_id = %id))]
@@ -229,10 +533,179 @@ async fn wallet_operation_handler(
     Path(id): Path<Uuid>,
     Json(req): Json<WalletOperationRequest>,
 ) -> Result<Json<WalletOperationResponse>, HttpError> {
-    let wallet = state.wallet_repo.apply_operation(id, req.operation, req.amount).await?;
+    let cause = LedgerCause { fee: req.fee, ..Default::default() };
+    let (wallet, _) = state.wallet_operation(id, req.operation, &req.amount, cause).await?;
     Ok(Json(WalletOperationResponse { wallet }))
 }
 
+#[tracing::instrument(skip(state), fields(wallet_id = %id))]
+async fn get_wallet_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Wallet>, HttpError> {
+    let wallet = state.wallet_repo.get_by_id(id).await?.ok_or(DomainError::NotFound(id))?;
+    Ok(Json(wallet))
+}
+
+/// Query params for GET /wallets
+#[derive(Debug, Deserialize)]
+struct ListWalletsQuery {
+    /// `nextCursor` of the previous page.
+    cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: u32,
+}
+
+/// GET /wallets — wallets ordered by id, paged by cursor.
+#[tracing::instrument(skip(state))]
+async fn list_wallets_handler(
+    State(state): State<AppState>,
+    Query(q): Query<ListWalletsQuery>,
+) -> Result<Json<WalletListResponse>, HttpError> {
+    let after = q
+        .cursor
+        .as_deref()
+        .map(|c| Uuid::try_parse(c).map_err(|_| DomainError::InvalidInput(format!("invalid cursor {c:?}"))))
+        .transpose()?;
+    let limit = q.limit.clamp(1, 1_000);
+    let mut wallets = state.wallet_repo.list(after, limit + 1).await?;
+    let next_cursor = if wallets.len() > limit as usize {
+        wallets.truncate(limit as usize);
+        wallets.last().map(|w| w.wallet_id.to_string())
+    } else {
+        None
+    };
+    Ok(Json(WalletListResponse { wallets, next_cursor }))
+}
+
+/// Admin-only endpoints: Forbidden for other callers.
+fn require_admin(caller: &Caller) -> Result<(), HttpError> {
+    if caller.role != Role::Admin {
+        return Err(HttpError::from(DomainError::Forbidden("admin role required".into())));
+    }
+    Ok(())
+}
+
+/// Applies an admin action to a wallet, audited under the caller's key id.
+async fn administer_wallet(
+    state: &AppState,
+    id: Uuid,
+    caller: Caller,
+    action: WalletAdminAction,
+    reason: Option<String>,
+) -> Result<Json<Wallet>, HttpError> {
+    require_admin(&caller)?;
+    let wallet = state.wallet_repo.administer(id, action.clone(), caller.key_id.clone(), reason).await?;
+    info!(wallet_id = %id, actor = %caller.key_id, ?action, "wallet administered");
+    Ok(Json(wallet))
+}
+
+/// PUT /wallets/{id}/limits — replaces the wallet's limits (admin).
+#[tracing::instrument(skip(state, req), fields(wallet_id = %id))]
+async fn set_wallet_limits_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    Json(req): Json<SetWalletLimitsRequest>,
+) -> Result<Json<Wallet>, HttpError> {
+    let action = WalletAdminAction::SetLimits {
+        daily_limit: req.daily_limit,
+        weekly_limit: req.weekly_limit,
+        monthly_limit: req.monthly_limit,
+    };
+    administer_wallet(&state, id, caller, action, req.reason).await
+}
+
+/// POST /wallets/{id}/freeze — refuses further debits (admin).
+#[tracing::instrument(skip(state, req), fields(wallet_id = %id))]
+async fn freeze_wallet_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    req: Option<Json<WalletAdminRequest>>,
+) -> Result<Json<Wallet>, HttpError> {
+    let reason = req.and_then(|Json(r)| r.reason);
+    administer_wallet(&state, id, caller, WalletAdminAction::Freeze, reason).await
+}
+
+/// POST /wallets/{id}/unfreeze (admin).
+#[tracing::instrument(skip(state, req), fields(wallet_id = %id))]
+async fn unfreeze_wallet_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    req: Option<Json<WalletAdminRequest>>,
+) -> Result<Json<Wallet>, HttpError> {
+    let reason = req.and_then(|Json(r)| r.reason);
+    administer_wallet(&state, id, caller, WalletAdminAction::Unfreeze, reason).await
+}
+
+/// POST /wallets/{id}/reset-daily-spend — zeroes today's spend total (admin).
+#[tracing::instrument(skip(state, req), fields(wallet_id = %id))]
+async fn reset_daily_spend_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+    req: Option<Json<WalletAdminRequest>>,
+) -> Result<Json<Wallet>, HttpError> {
+    let reason = req.and_then(|Json(r)| r.reason);
+    administer_wallet(&state, id, caller, WalletAdminAction::ResetDailySpend, reason).await
+}
+
+/// GET /wallets/{id}/audit — the wallet's admin changes, oldest first (admin).
+#[tracing::instrument(skip(state), fields(wallet_id = %id))]
+async fn wallet_audit_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(caller): Extension<Caller>,
+) -> Result<Json<WalletAuditResponse>, HttpError> {
+    require_admin(&caller)?;
+    state.wallet_repo.get_by_id(id).await?.ok_or(DomainError::NotFound(id))?;
+    let records = state.wallet_repo.list_audit(id).await?;
+    Ok(Json(WalletAuditResponse { records }))
+}
+
+/// Query params for GET /wallets/{id}/transactions
+#[derive(Debug, Deserialize)]
+struct WalletTransactionsQuery {
+    /// `nextCursor` of the previous page.
+    cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: u32,
+}
+
+/// GET /wallets/{id}/transactions — the wallet's ledger entries, newest first, paged by cursor.
+#[tracing::instrument(skip(state), fields(wallet_id = %id))]
+async fn wallet_transactions_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<WalletTransactionsQuery>,
+) -> Result<Json<WalletTransactionsResponse>, HttpError> {
+    let before = q
+        .cursor
+        .as_deref()
+        .map(|c| c.parse::<u64>().map_err(|_| DomainError::InvalidInput(format!("invalid cursor {c:?}"))))
+        .transpose()?;
+    let (transactions, next) = state.wallet_transactions(id, before, q.limit.clamp(1, 1_000)).await?;
+    Ok(Json(WalletTransactionsResponse {
+        transactions,
+        next_cursor: next.map(|s| s.to_string()),
+    }))
+}
+
+/// GET /wallets/{id}/reconciliation — the stored balance checked against its ledger.
+#[tracing::instrument(skip(state), fields(wallet_id = %id))]
+async fn wallet_reconciliation_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WalletReconciliation>, HttpError> {
+    let reconciliation = state.reconcile_wallet(id).await?;
+    if !reconciliation.consistent {
+        warn!(wallet_id = %id, balance = %reconciliation.balance.amount, ledger = %reconciliation.ledger_balance.amount, "wallet balance disagrees with its ledger");
+    }
+    Ok(Json(reconciliation))
+}
+
 /// Query params for GET /rl/export
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -254,7 +727,7 @@ async fn session_events_handler(
     Path(id): Path<Uuid>,
 ) -> Result<Json<SessionEventsResponse>, HttpError> {
     // Verify session exists first
-    let mgr = GameSessionManager::new(state.session_repo.clone());
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
     mgr.get_session(SessionId(id))
         .await
         .map_err(HttpError::from)?
@@ -263,6 +736,7 @@ async fn session_events_handler(
     let raw = state
         .event_store
         .list_by_session(id)
+        .await
         .map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?;
 
     let events = raw
@@ -280,6 +754,85 @@ async fn session_events_handler(
     Ok(Json(SessionEventsResponse { events }))
 }
 
+#[tracing::instrument(skip(state), fields(session_id = %id))]
+async fn session_transitions_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SessionTransitionsResponse>, HttpError> {
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+    mgr.get_session(SessionId(id))
+        .await
+        .map_err(HttpError::from)?
+        .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
+    let transitions = state.session_repo.list_transitions(id).await?;
+    Ok(Json(SessionTransitionsResponse { transitions }))
+}
+
+/// Query params for GET /games
+#[derive(Debug, Deserialize)]
+struct ListGamesQuery {
+    #[serde(default = "default_limit")]
+    limit: u32,
+    #[serde(default)]
+    offset: u32,
+}
+
+#[tracing::instrument(skip(state))]
+async fn create_game_handler(
+    State(state): State<AppState>,
+    Json(req): Json<CreateGameRequest>,
+) -> Result<(StatusCode, Json<Game>), HttpError> {
+    if req.name.trim().is_empty() {
+        return Err(HttpError::from(DomainError::InvalidInput("name must not be empty".into())));
+    }
+    let empty = || serde_json::json!({});
+    let game = Game {
+        game_id: req.game_id.unwrap_or_else(|| GameId(Uuid::new_v4())),
+        name: req.name,
+        rng_signature: req.rng_signature.unwrap_or_else(empty),
+        symbol_map: req.symbol_map.unwrap_or_else(empty),
+        statistical_profile: req.statistical_profile.unwrap_or_else(empty),
+        created_at: chrono::Utc::now(),
+    };
+    state.game_repo.create(game.clone()).await?;
+    Ok((StatusCode::CREATED, Json(game)))
+}
+
+#[tracing::instrument(skip(state))]
+async fn list_games_handler(
+    State(state): State<AppState>,
+    Query(q): Query<ListGamesQuery>,
+) -> Result<Json<GamesResponse>, HttpError> {
+    let games = state.game_repo.list(q.limit.clamp(1, 1_000), q.offset).await?;
+    Ok(Json(GamesResponse { games }))
+}
+
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn get_game_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Game>, HttpError> {
+    let game = state
+        .game_repo
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
+    Ok(Json(game))
+}
+
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn update_game_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateGameRequest>,
+) -> Result<Json<Game>, HttpError> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(HttpError::from(DomainError::InvalidInput("name must not be empty".into())));
+    }
+    let game = state.game_repo.update(id, req).await?;
+    Ok(Json(game))
+}
+
 #[tracing::instrument(skip(state), fields(game_id = %id))]
 async fn game_fingerprint_handler(
     State(state): State<AppState>,
@@ -288,15 +841,243 @@ async fn game_fingerprint_handler(
     let fp: GameFingerprint = state
         .fingerprint_store
         .get(id)
+        .await
         .map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?
         .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
 
-    Ok(Json(GameFingerprintResponse {
+    Ok(Json(fingerprint_response(fp)))
+}
+
+/// POST /games/{id}/fingerprint/run — fingerprints every Spin recorded for the game's
+/// sessions and saves the result as a new run.
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn run_fingerprint_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<GameFingerprintResponse>), HttpError> {
+    state
+        .game_repo
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
+
+    let samples = game_spin_samples(&state, id).await?;
+    let fp = state
+        .fingerprint_store
+        .save(fingerprint_from_samples(id, &samples))
+        .await
+        .map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?;
+    info!(run_id = %fp.run_id, version = fp.version, sample_size = fp.sample_size, "fingerprint run saved");
+    Ok((StatusCode::CREATED, Json(fingerprint_response(fp))))
+}
+
+/// GET /games/{id}/fingerprint/versions — every fingerprint run, oldest first.
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn fingerprint_versions_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FingerprintVersionsResponse>, HttpError> {
+    let versions = state
+        .fingerprint_store
+        .list_versions(id)
+        .await
+        .map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?;
+    if versions.is_empty() {
+        return Err(HttpError::from(DomainError::NotFound(id)));
+    }
+    Ok(Json(FingerprintVersionsResponse {
+        versions: versions.into_iter().map(fingerprint_response).collect(),
+    }))
+}
+
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn fingerprint_version_handler(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(Uuid, u32)>,
+) -> Result<Json<GameFingerprintResponse>, HttpError> {
+    Ok(Json(fingerprint_response(fingerprint_version(&state, id, Some(version)).await?)))
+}
+
+/// The given fingerprint version of a game, or its latest when `version` is None.
+async fn fingerprint_version(
+    state: &AppState,
+    game_id: Uuid,
+    version: Option<u32>,
+) -> Result<GameFingerprint, HttpError> {
+    let fp = match version {
+        Some(v) => state.fingerprint_store.get_version(game_id, v).await,
+        None => state.fingerprint_store.get(game_id).await,
+    };
+    fp.map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?
+        .ok_or_else(|| HttpError::from(DomainError::NotFound(game_id)))
+}
+
+/// Query params for GET /games/{id}/fingerprint/diff
+#[derive(Debug, Deserialize)]
+struct FingerprintDiffQuery {
+    /// Defaults to the version before `to`.
+    from: Option<u32>,
+    /// Defaults to the latest version.
+    to: Option<u32>,
+    #[serde(default = "default_diff_significance")]
+    significance: f64,
+}
+
+fn default_diff_significance() -> f64 {
+    0.01
+}
+
+/// GET /games/{id}/fingerprint/diff — compares two fingerprint versions.
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn fingerprint_diff_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<FingerprintDiffQuery>,
+) -> Result<Json<FingerprintDiff>, HttpError> {
+    if !(q.significance > 0.0 && q.significance < 1.0) {
+        return Err(HttpError::from(DomainError::InvalidInput(
+            "significance must be in (0, 1)".into(),
+        )));
+    }
+    let to = fingerprint_version(&state, id, q.to).await?;
+    let from_version = match q.from {
+        Some(v) => v,
+        None if to.version > 1 => to.version - 1,
+        None => {
+            return Err(HttpError::from(DomainError::InvalidInput(
+                "game has a single fingerprint version; nothing to diff against".into(),
+            )))
+        }
+    };
+    let from = fingerprint_version(&state, id, Some(from_version)).await?;
+    Ok(Json(diff_fingerprints(&from, &to, q.significance)))
+}
+
+/// Query params for GET /games/{id}/fingerprint/drift
+#[derive(Debug, Deserialize)]
+struct DriftQuery {
+    /// Baseline fingerprint version; defaults to the latest.
+    baseline: Option<u32>,
+}
+
+/// GET /games/{id}/fingerprint/drift — CUSUM of spins recorded after the baseline run against
+/// its profile.
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn fingerprint_drift_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<DriftQuery>,
+) -> Result<Json<DriftReport>, HttpError> {
+    let baseline = fingerprint_version(&state, id, q.baseline).await?;
+    let mut live = game_timed_spin_samples(&state, id).await?;
+    if let Some(since) = baseline.created_at {
+        live.retain(|(ts, _)| ts.is_some_and(|t| t > since));
+    }
+    live.sort_by_key(|(ts, _)| *ts);
+    let live: Vec<SpinSample> = live.into_iter().map(|(_, s)| s).collect();
+
+    let report = detect_drift(&baseline, &live, &DriftConfig::default());
+    if report.drifted {
+        warn!(
+            baseline_version = report.baseline_version,
+            observations = report.observations,
+            "live outcomes drifted from the baseline fingerprint"
+        );
+    }
+    Ok(Json(report))
+}
+
+/// Query params for GET /games/{id}/fingerprint/audit
+#[derive(Debug, Deserialize)]
+struct AuditQuery {
+    significance: Option<f64>,
+}
+
+/// GET /games/{id}/fingerprint/audit — goodness-of-fit of the game's recorded spins
+/// against its registered definition.
+#[tracing::instrument(skip(state), fields(game_id = %id))]
+async fn fairness_audit_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<AuditQuery>,
+) -> Result<Json<FairnessReport>, HttpError> {
+    let mut config = AuditConfig::default();
+    if let Some(significance) = q.significance {
+        if !(significance > 0.0 && significance < 1.0) {
+            return Err(HttpError::from(DomainError::InvalidInput(
+                "significance must be in (0, 1)".into(),
+            )));
+        }
+        config.significance = significance;
+    }
+    state
+        .game_repo
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| HttpError::from(DomainError::NotFound(id)))?;
+    let game = state.game_catalog.get(id).ok_or_else(|| {
+        HttpError::from(DomainError::InvalidInput(format!(
+            "game {id} has no registered definition to audit against"
+        )))
+    })?;
+
+    let samples = game_spin_samples(&state, id).await?;
+    let report = fairness_audit(id, &game.machine, &samples, &config);
+    info!(sample_size = report.sample_size, passed = report.passed, "fairness audit complete");
+    Ok(Json(report))
+}
+
+/// Spin samples from every session of a game; InvalidInput if none were recorded.
+async fn game_spin_samples(state: &AppState, game_id: Uuid) -> Result<Vec<SpinSample>, HttpError> {
+    let samples: Vec<SpinSample> = game_timed_spin_samples(state, game_id)
+        .await?
+        .into_iter()
+        .map(|(_, s)| s)
+        .collect();
+    if samples.is_empty() {
+        return Err(HttpError::from(DomainError::InvalidInput(format!(
+            "no Spin outcomes recorded for game {game_id}"
+        ))));
+    }
+    Ok(samples)
+}
+
+/// Spin samples with their event timestamps from every session of a game, session by session.
+async fn game_timed_spin_samples(
+    state: &AppState,
+    game_id: Uuid,
+) -> Result<Vec<(Option<DateTime<Utc>>, SpinSample)>, HttpError> {
+    let sessions: Vec<Uuid> = state
+        .session_repo
+        .list_by_game(game_id)
+        .await?
+        .iter()
+        .map(|s| s.session_id.0)
+        .collect();
+    let events = state
+        .event_store
+        .list_by_sessions(&sessions)
+        .await
+        .map_err(|e| HttpError::from(DomainError::Internal(e.to_string())))?;
+    // A Spin without an amount stakes its own session's last bet, so sessions are read apart.
+    Ok(events
+        .chunk_by(|a, b| a.session_id == b.session_id)
+        .flat_map(timed_spin_samples)
+        .collect())
+}
+
+fn fingerprint_response(fp: GameFingerprint) -> GameFingerprintResponse {
+    GameFingerprintResponse {
         game_id: fp.game_id,
         rng_signature: fp.rng_signature,
         symbol_map: fp.symbol_map,
         statistical_profile: fp.statistical_profile,
-    }))
+        randomness: fp.randomness,
+        run_id: fp.run_id,
+        version: fp.version,
+        sample_size: fp.sample_size,
+        created_at: fp.created_at,
+    }
 }
 
 #[tracing::instrument(skip(state))]
@@ -325,11 +1106,40 @@ async fn create_wallet_handler(
         .unwrap_or_else(|| SessionId(Uuid::new_v4()));
 
     let currency = req.balance.currency;
+    if req.balance.amount < Decimal::ZERO {
+        return Err(HttpError::from(DomainError::InvalidInput(format!(
+            "balance {} must not be negative",
+            req.balance.amount
+        ))));
+    }
+    let limits = [("daily", Some(&req.daily_limit)), ("weekly", req.weekly_limit.as_ref()), ("monthly", req.monthly_limit.as_ref())];
+    for (window, limit) in limits {
+        let Some(limit) = limit else { continue };
+        if limit.currency != currency {
+            return Err(HttpError::from(DomainError::InvalidInput(format!(
+                "{window} limit currency {} does not match balance currency {currency}",
+                limit.currency
+            ))));
+        }
+        if !limit.is_positive() {
+            return Err(HttpError::from(DomainError::InvalidInput(format!(
+                "{window} limit {} must be positive",
+                limit.amount
+            ))));
+        }
+    }
     let wallet = controller::api::Wallet {
         wallet_id,
         balance: req.balance,
         daily_limit: req.daily_limit,
-        daily_spent: Money { amount: 0.0, currency },
+        daily_spent: Money::zero(currency),
+        weekly_limit: req.weekly_limit,
+        weekly_spent: Money::zero(currency),
+        monthly_limit: req.monthly_limit,
+        monthly_spent: Money::zero(currency),
+        timezone: req.timezone,
+        spent_at: None,
+        frozen: false,
     };
     state.wallet_repo.create(wallet.clone()).await?;
     Ok((StatusCode::CREATED, Json(wallet)))
@@ -377,18 +1187,128 @@ async fn metrics_handler(
         "sessions_created": state.metrics.get_sessions_created(),
         "sessions_completed": state.metrics.get_sessions_completed(),
         "sessions_playing": state.metrics.sessions_playing.load(std::sync::atomic::Ordering::Relaxed),
+        "sessions_expired": state.metrics.get_sessions_expired(),
+        "sessions_aborted": state.metrics.get_sessions_aborted(),
     });
     Json(snapshot).into_response()
 }
 
-fn simulate_result(action: &GameplayAction) -> GameplayResult {
-    match action.action_type {
-        GameplayActionType::Spin => GameplayResult {
-            payout: Some(Money { amount: 0.0, currency: Currency::AUD }),
-            symbols: vec!["A".to_string(), "B".to_string(), "C".to_string()],
-        },
-        _ => GameplayResult { payout: None, symbols: vec![] },
+/// Executes the state machine's effects for one play action. A ResolveSpin debits the stake
+/// from the session's wallet and resolves the spin before the transition is written, leaving its
+/// outcome in `result` and in the session metrics, then credits the payout once the transition
+/// is persisted. Other actions have no outcome and only stamp the metrics' last action time.
+struct ActionEffects<'a> {
+    state: &'a AppState,
+    machine: &'a SlotMachine,
+    /// Currency stakes and payouts are denominated in; None when the action moves no money.
+    currency: Option<Currency>,
+    /// Wallet bound to the session, if any.
+    wallet: Option<Uuid>,
+    /// Session, game and event the action's ledger entries reference.
+    cause: LedgerCause,
+    /// Whether `resolve` debited a stake, which `revert` refunds.
+    debited: bool,
+    result: GameplayResult,
+}
+
+impl<'a> ActionEffects<'a> {
+    fn new(state: &'a AppState, session: &Session, machine: &'a SlotMachine, currency: Option<Currency>, event_id: Option<Uuid>) -> Self {
+        Self {
+            state,
+            machine,
+            currency,
+            wallet: session.wallet_id,
+            cause: LedgerCause {
+                session_id: Some(session.session_id.0),
+                game_id: Some(session.game_id.0),
+                event_id,
+                fee: None,
+            },
+            debited: false,
+            result: GameplayResult::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl EffectExecutor for ActionEffects<'_> {
+    async fn resolve(&mut self, _session: &Session, effect: &Effect, metrics: &mut SessionMetrics) -> Result<(), DomainError> {
+        let now = Utc::now();
+        match *effect {
+            Effect::ResolveSpin { stake } => {
+                let currency = self
+                    .currency
+                    .ok_or_else(|| DomainError::Internal("spin resolved without a currency".into()))?;
+                if let Some(wallet) = self.wallet {
+                    let stake = Money { amount: stake, currency };
+                    self.state
+                        .wallet_operation(wallet, WalletOperationType::Debit, &stake, self.cause.clone())
+                        .await?;
+                    self.debited = true;
+                }
+                let outcome = {
+                    let mut rng = self
+                        .state
+                        .slot_rng
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner);
+                    self.machine.spin(&mut rng, stake.to_f64().unwrap_or_default())
+                };
+                // The engine pays in floating point; the payout is rounded to the currency's
+                // minor unit once, here.
+                let payout = Money::from_f64(outcome.payout, currency)?;
+                metrics.record_spin(stake, payout.amount, now);
+                self.result = GameplayResult {
+                    payout: Some(payout),
+                    symbols: outcome.grid.concat(),
+                    grid: outcome.grid,
+                    line_wins: outcome.line_wins,
+                    voided: false,
+                };
+            }
+            _ => metrics.record_action(now),
+        }
+        Ok(())
     }
+
+    async fn revert(&mut self, _session: &Session) -> Result<(), DomainError> {
+        // The refund also takes the stake off the wallet's spend totals.
+        if let (Some(wallet), true, Some(event_id)) = (self.wallet, std::mem::take(&mut self.debited), self.cause.event_id) {
+            self.state.wallet_repo.refund_debit(wallet, event_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, _session: &Session, effect: &Effect) -> Result<(), DomainError> {
+        match *effect {
+            Effect::RecordBet { .. } => self.state.metrics.record_session_playing(),
+            Effect::ResolveSpin { .. } => {
+                let payout = self.result.payout.clone().filter(Money::is_positive);
+                if let (Some(wallet), Some(payout)) = (self.wallet, payout) {
+                    self.state
+                        .wallet_operation(wallet, WalletOperationType::Credit, &payout, self.cause.clone())
+                        .await?;
+                }
+            }
+            Effect::Settle => self.state.metrics.record_session_completed(),
+            Effect::Void => self.state.metrics.record_session_aborted(),
+        }
+        Ok(())
+    }
+}
+
+/// Amount of the latest PlaceBet recorded for the session, if any.
+async fn last_bet_amount(state: &AppState, session_id: Uuid) -> Option<Money> {
+    state
+        .event_store
+        .list_by_session(session_id)
+        .await
+        .ok()?
+        .into_iter()
+        .rev()
+        .filter_map(|e| serde_json::from_value::<GameplayAction>(e.action).ok())
+        .find(|a| a.action_type == GameplayActionType::PlaceBet)
+        .and_then(|a| a.amount)
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -397,24 +1317,27 @@ fn simulate_result(action: &GameplayAction) -> GameplayResult {
 mod tests {
     use super::*;
     use axum::body::Body;
-    use controller::event_store::InMemoryEventStore;
-    use controller::fingerprinter::InMemoryFingerprintStore;
-    use controller::persistence_metrics::{InMemorySessionStore, InMemoryWalletStore};
-    use controller::rl_feedback_loop::InMemoryStore as InMemoryRlStore;
+    use controller::ledger::LedgerEntryKind;
+    use controller::test_support::{self, session_request, test_wallet};
     use http::Request;
     use http_body_util::BodyExt;
     use std::sync::Arc;
     use tower::ServiceExt;
 
+    /// Game seeded into every test state so sessions can be created against it.
+    const TEST_GAME_ID: Uuid = Uuid::from_u128(0x5e55_1017_0000_4000_8000_0000_0000_0001);
+
     fn test_state() -> AppState {
-        AppState::new(
-            Arc::new(InMemorySessionStore::new()),
-            Arc::new(InMemoryWalletStore::new()),
-            Arc::new(InMemoryEventStore::new()),
-            Arc::new(InMemoryFingerprintStore::new()),
-            Arc::new(InMemoryRlStore::new()),
-            None,
-        )
+        test_support::test_state(TEST_GAME_ID)
+    }
+
+    /// POST /v1/sessions as the test caller with `body` as JSON.
+    fn post_session(body: &impl serde::Serialize) -> Request<Body> {
+        Request::post("http://localhost/v1/sessions")
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(body).unwrap()))
+            .unwrap()
     }
 
     #[tokio::test]
@@ -451,15 +1374,7 @@ mod tests {
     #[tokio::test]
     async fn create_session_returns_201() {
         let app = v1_app(test_state());
-        let body = serde_json::json!({
-            "gameId": Uuid::new_v4().to_string(),
-            "playerProfile": { "behaviorType": "conservative" }
-        });
-        let req = Request::post("http://localhost/v1/sessions")
-            .header("Authorization", "Bearer testkey")
-            .header("Content-Type", "application/json")
-            .body(Body::from(serde_json::to_vec(&body).unwrap()))
-            .unwrap();
+        let req = post_session(&session_request(TEST_GAME_ID, None));
         let res = app.oneshot(req).await.unwrap();
         assert_eq!(res.status(), StatusCode::CREATED);
         let bytes = res.into_body().collect().await.unwrap().to_bytes();
@@ -467,6 +1382,17 @@ mod tests {
         assert_eq!(json["state"].as_str(), Some("Initialized"));
     }
 
+    #[tokio::test]
+    async fn create_session_returns_404_for_unknown_game() {
+        let app = v1_app(test_state());
+        let req = post_session(&session_request(Uuid::new_v4(), None));
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["error"]["code"].as_str(), Some("NOT_FOUND"));
+    }
+
     #[tokio::test]
     async fn get_session_returns_404_for_unknown_id() {
         let app = v1_app(test_state());
@@ -514,21 +1440,196 @@ mod tests {
         let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
         assert_eq!(json["balance"]["amount"].as_f64(), Some(500.0));
         assert_eq!(json["dailySpent"]["amount"].as_f64(), Some(0.0));
+        assert_eq!(json["timezone"], "UTC");
+        assert!(json.get("weeklyLimit").is_none());
     }
 
     #[tokio::test]
-    async fn session_events_returns_empty_for_new_session() {
+    async fn create_wallet_accepts_weekly_and_monthly_limits_in_a_timezone() {
         let app = v1_app(test_state());
-        // Create a session first
+        let create = |body: serde_json::Value| {
+            Request::post("http://localhost/v1/wallets")
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
         let body = serde_json::json!({
-            "gameId": Uuid::new_v4().to_string(),
-            "playerProfile": { "behaviorType": "conservative" }
+            "balance": { "amount": 500.0, "currency": "AUD" },
+            "dailyLimit": { "amount": 100.0, "currency": "AUD" },
+            "weeklyLimit": { "amount": 300.0, "currency": "AUD" },
+            "monthlyLimit": { "amount": 1000.0, "currency": "AUD" },
+            "timezone": "Australia/Sydney"
         });
-        let req = Request::post("http://localhost/v1/sessions")
+        let res = app.clone().oneshot(create(body.clone())).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["timezone"], "Australia/Sydney");
+        assert_eq!(json["weeklyLimit"]["amount"], 300.0);
+        assert_eq!(json["monthlySpent"]["amount"], 0.0);
+
+        let mut other = body.clone();
+        other["monthlyLimit"]["currency"] = "USD".into();
+        let res = app.clone().oneshot(create(other)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let mut unknown = body;
+        unknown["timezone"] = "Mars/Olympus_Mons".into();
+        let res = app.oneshot(create(unknown)).await.unwrap();
+        assert!(res.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn create_wallet_rejects_a_negative_balance_and_non_positive_limits() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let create = |body: serde_json::Value| {
+            Request::post("http://localhost/v1/wallets")
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
+        let body = serde_json::json!({
+            "balance": { "amount": 500.0, "currency": "AUD" },
+            "dailyLimit": { "amount": 100.0, "currency": "AUD" },
+            "weeklyLimit": { "amount": 300.0, "currency": "AUD" },
+            "monthlyLimit": { "amount": 1000.0, "currency": "AUD" }
+        });
+        let invalid = [
+            ("balance", -0.01),
+            ("dailyLimit", 0.0),
+            ("weeklyLimit", -1.0),
+            ("monthlyLimit", 0.0),
+        ];
+        let before = state.wallet_repo.list(None, 100).await.unwrap().len();
+        for (field, amount) in invalid {
+            let mut bad = body.clone();
+            bad[field]["amount"] = amount.into();
+            let res = app.clone().oneshot(create(bad)).await.unwrap();
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{field} {amount}");
+        }
+        assert_eq!(state.wallet_repo.list(None, 100).await.unwrap().len(), before);
+
+        // An empty wallet is fine.
+        let mut empty = body;
+        empty["balance"]["amount"] = 0.0.into();
+        let res = app.oneshot(create(empty)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn create_wallet_refuses_an_existing_id() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let wallet_id = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet_id, Decimal::from(10))).await.unwrap();
+        state.wallet_repo.administer(wallet_id, WalletAdminAction::Freeze, "ops".into(), None).await.unwrap();
+
+        // Re-posting the id must not unfreeze the wallet, reset its limits or fund it.
+        let body = serde_json::json!({
+            "walletId": wallet_id,
+            "balance": { "amount": 1000000.0, "currency": "AUD" },
+            "dailyLimit": { "amount": 1000000.0, "currency": "AUD" }
+        });
+        let req = Request::post("http://localhost/v1/wallets")
             .header("Authorization", "Bearer testkey")
             .header("Content-Type", "application/json")
             .body(Body::from(serde_json::to_vec(&body).unwrap()))
             .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        let wallet = state.wallet_repo.get_by_id(wallet_id).await.unwrap().unwrap();
+        assert!(wallet.frozen);
+        assert_eq!((wallet.balance.amount, wallet.daily_limit.amount), (Decimal::from(10), Decimal::from(1000)));
+        assert_eq!(state.wallet_repo.list_transactions(wallet_id, None, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn wallet_amounts_are_exact_to_the_cent() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let post = |uri: String, body: serde_json::Value| {
+            Request::post(uri)
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
+        let wallet_id = Uuid::new_v4();
+        let body = serde_json::json!({
+            "walletId": wallet_id,
+            "balance": { "amount": 0.3, "currency": "AUD" },
+            "dailyLimit": { "amount": 100.0, "currency": "AUD" }
+        });
+        let res = app.clone().oneshot(post("http://localhost/v1/wallets".into(), body)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        // 0.1 + 0.2 debits leave exactly nothing, where f64 would leave 5.5e-17.
+        let uri = format!("http://localhost/v1/wallets/{wallet_id}/operations");
+        for amount in [0.1, 0.2] {
+            let body = serde_json::json!({ "operation": "debit", "amount": { "amount": amount, "currency": "AUD" } });
+            let res = app.clone().oneshot(post(uri.clone(), body)).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+        }
+        let wallet = state.wallet_repo.get_by_id(wallet_id).await.unwrap().unwrap();
+        assert_eq!(wallet.balance.amount, Decimal::ZERO);
+
+        // Fractions of a cent are not money, and a negative debit is not a credit.
+        let body = serde_json::json!({ "operation": "credit", "amount": { "amount": 0.005, "currency": "AUD" } });
+        let res = app.clone().oneshot(post(uri.clone(), body)).await.unwrap();
+        assert!(res.status().is_client_error());
+        let body = serde_json::json!({ "operation": "debit", "amount": { "amount": -5.0, "currency": "AUD" } });
+        let res = app.oneshot(post(uri, body)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn foreign_currency_operations_need_an_exchange_rate() {
+        use controller::fx::StaticFxRates;
+        use std::collections::HashMap;
+
+        let op = |wallet_id: Uuid, operation: &str, amount: f64, currency: &str| {
+            let body = serde_json::json!({ "operation": operation, "amount": { "amount": amount, "currency": currency } });
+            Request::post(format!("http://localhost/v1/wallets/{wallet_id}/operations"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
+        let wallet_id = Uuid::new_v4();
+
+        // Without rates, 10 EUR is never silently taken as 10 AUD.
+        let state = test_state();
+        state.wallet_repo.create(test_wallet(wallet_id, Decimal::from(100))).await.unwrap();
+        let res = v1_app(state.clone()).oneshot(op(wallet_id, "debit", 10.0, "EUR")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["error"]["code"], "INVALID_INPUT");
+        assert_eq!(state.wallet_repo.get_by_id(wallet_id).await.unwrap().unwrap().balance.amount, Decimal::from(100));
+
+        let rates = StaticFxRates::new(Currency::AUD, HashMap::from([(Currency::EUR, Decimal::new(165, 2))])).unwrap();
+        let state = test_state().with_fx_rates(Arc::new(rates));
+        state.wallet_repo.create(test_wallet(wallet_id, Decimal::from(100))).await.unwrap();
+        let app = v1_app(state.clone());
+        let res = app.clone().oneshot(op(wallet_id, "debit", 10.0, "EUR")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["wallet"]["balance"], serde_json::json!({ "amount": 83.5, "currency": "AUD" }));
+        assert_eq!(json["wallet"]["dailySpent"]["amount"], 16.5);
+        // A pair the rates do not quote is still rejected.
+        let res = app.oneshot(op(wallet_id, "credit", 1.0, "GBP")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn session_events_returns_empty_for_new_session() {
+        let app = v1_app(test_state());
+        // Create a session first
+        let req = post_session(&session_request(TEST_GAME_ID, None));
         let res = app.clone().oneshot(req).await.unwrap();
         let bytes = res.into_body().collect().await.unwrap().to_bytes();
         let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
@@ -545,6 +1646,92 @@ mod tests {
         assert!(json["events"].as_array().map(|a| a.is_empty()).unwrap_or(false));
     }
 
+    #[tokio::test]
+    async fn games_create_get_patch_and_list() {
+        let app = v1_app(test_state());
+        let body = serde_json::json!({
+            "name": "Lucky Sevens",
+            "symbolMap": { "Seven": 0.05 }
+        });
+        let req = Request::post("http://localhost/v1/games")
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let game_id = created["gameId"].as_str().unwrap().to_string();
+        assert_eq!(created["rngSignature"], serde_json::json!({}));
+
+        let patch = serde_json::json!({ "name": "Lucky Sevens Deluxe" });
+        let req = Request::patch(format!("http://localhost/v1/games/{game_id}"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&patch).unwrap()))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = Request::get(format!("http://localhost/v1/games/{game_id}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let game: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(game["name"].as_str(), Some("Lucky Sevens Deluxe"));
+        assert_eq!(game["symbolMap"]["Seven"].as_f64(), Some(0.05));
+
+        let req = Request::get("http://localhost/v1/games?limit=10")
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["games"].as_array().map(Vec::len), Some(2), "seeded game + created game");
+    }
+
+    #[tokio::test]
+    async fn games_reject_empty_name_taken_id_and_unknown_id() {
+        let app = v1_app(test_state());
+        let req = Request::post("http://localhost/v1/games")
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"name":"  "}"#))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let taken = serde_json::json!({ "gameId": TEST_GAME_ID, "name": "Again" });
+        let req = Request::post("http://localhost/v1/games")
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&taken).unwrap()))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        let unknown = Uuid::new_v4();
+        let req = Request::patch(format!("http://localhost/v1/games/{unknown}"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"name":"x"}"#))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = Request::get(format!("http://localhost/v1/games/{unknown}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn game_fingerprint_returns_404_for_unknown_game() {
         let app = v1_app(test_state());
@@ -557,6 +1744,204 @@ mod tests {
         assert_eq!(res.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn fingerprint_run_persists_profile_for_game_sessions() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+        let spin = serde_json::json!({ "action": { "type": "Spin" } });
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&spin).unwrap()))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+        let run = || {
+            Request::post(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint/run"))
+                .header("Authorization", "Bearer testkey")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let res = app.clone().oneshot(run()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(created["sampleSize"].as_u64(), Some(1));
+        assert_eq!(created["rngSignature"].as_str().map(str::len), Some(16));
+        assert_eq!(created["randomness"]["sampleSize"].as_u64(), Some(1));
+
+        let req = Request::get(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let stored: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(stored["runId"], created["runId"]);
+
+        // A second run is a new run id over the same sample.
+        let res = app.oneshot(run()).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let rerun: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_ne!(rerun["runId"], created["runId"]);
+        assert_eq!(rerun["rngSignature"], created["rngSignature"]);
+    }
+
+    #[tokio::test]
+    async fn fingerprint_versions_diff_and_drift() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+        let spin = || {
+            Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"action":{"type":"Spin"}}"#))
+                .unwrap()
+        };
+        let run = || {
+            Request::post(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint/run"))
+                .header("Authorization", "Bearer testkey")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let get = |path: &str| {
+            Request::get(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint{path}"))
+                .header("Authorization", "Bearer testkey")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let json = |res: Response| async move {
+            let bytes = res.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        assert_eq!(app.clone().oneshot(get("/versions")).await.unwrap().status(), StatusCode::NOT_FOUND);
+        app.clone().oneshot(spin()).await.unwrap();
+        let v1 = json(app.clone().oneshot(run()).await.unwrap()).await;
+        assert_eq!(v1["version"].as_u64(), Some(1));
+        // Only one version: nothing to diff against.
+        assert_eq!(app.clone().oneshot(get("/diff")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+
+        app.clone().oneshot(spin()).await.unwrap();
+        let v2 = json(app.clone().oneshot(run()).await.unwrap()).await;
+        assert_eq!(v2["version"].as_u64(), Some(2));
+        assert_eq!(v2["sampleSize"].as_u64(), Some(2));
+
+        let versions = json(app.clone().oneshot(get("/versions")).await.unwrap()).await;
+        let numbers: Vec<u64> = versions["versions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["version"].as_u64().unwrap())
+            .collect();
+        assert_eq!(numbers, vec![1, 2]);
+        let first = json(app.clone().oneshot(get("/versions/1")).await.unwrap()).await;
+        assert_eq!(first["runId"], v1["runId"]);
+        assert_eq!(app.clone().oneshot(get("/versions/9")).await.unwrap().status(), StatusCode::NOT_FOUND);
+
+        let diff = json(app.clone().oneshot(get("/diff")).await.unwrap()).await;
+        assert_eq!((diff["fromVersion"].as_u64(), diff["toVersion"].as_u64()), (Some(1), Some(2)));
+        assert_eq!(diff["fromSampleSize"].as_u64(), Some(1));
+        assert!(diff["rtpShift"]["pValue"].is_number());
+        assert_eq!(
+            app.clone().oneshot(get("/diff?from=1&to=9")).await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+
+        // Spins played after the baseline run are monitored; one spin is less than a batch.
+        app.clone().oneshot(spin()).await.unwrap();
+        let drift = json(app.clone().oneshot(get("/drift?baseline=1")).await.unwrap()).await;
+        assert_eq!(drift["baselineVersion"].as_u64(), Some(1));
+        assert_eq!(drift["observations"].as_u64(), Some(0));
+        assert_eq!(drift["drifted"], false);
+        assert_eq!(drift["monitors"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[tokio::test]
+    async fn fingerprint_run_rejects_unknown_game_and_empty_sample() {
+        let app = v1_app(test_state());
+        let unknown = Uuid::new_v4();
+        let req = Request::post(format!("http://localhost/v1/games/{unknown}/fingerprint/run"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+
+        let req = Request::post(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint/run"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn fairness_audit_reports_tests_for_registered_definition() {
+        let state = test_state();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../game_engine_targets/slot_game_api_simulator/games/classic_fruit.toml");
+        let def = controller::slot_engine::GameDefinition::load_file(&path).unwrap();
+        let game_id = def.game_id;
+        let registered = state.game_catalog.register(def).unwrap();
+        state.game_repo.create(registered.to_game()).await.unwrap();
+        let app = v1_app(state);
+
+        let audit = |query: &str| {
+            Request::get(format!("http://localhost/v1/games/{game_id}/fingerprint/audit{query}"))
+                .header("Authorization", "Bearer testkey")
+                .body(Body::empty())
+                .unwrap()
+        };
+        // Nothing recorded yet.
+        assert_eq!(app.clone().oneshot(audit("")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+
+        let req = post_session(&session_request(game_id, None));
+        let res = app.clone().oneshot(req).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap().to_string();
+        place_bet(&app, &session_id).await;
+        let spin = serde_json::json!({ "action": { "type": "Spin" } });
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&spin).unwrap()))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+        assert_eq!(
+            app.clone().oneshot(audit("?significance=1.5")).await.unwrap().status(),
+            StatusCode::BAD_REQUEST
+        );
+        let res = app.oneshot(audit("?significance=0.05")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let report: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(report["sampleSize"].as_u64(), Some(1));
+        assert_eq!(report["significance"].as_f64(), Some(0.05));
+        // 3x3 grid positions + 5 paylines.
+        assert_eq!(report["targets"].as_array().map(Vec::len), Some(14));
+        assert!(report["targets"][0]["chiSquare"]["pValue"].is_number());
+    }
+
+    #[tokio::test]
+    async fn fairness_audit_requires_a_registered_definition() {
+        let app = v1_app(test_state());
+        let req = Request::get(format!("http://localhost/v1/games/{TEST_GAME_ID}/fingerprint/audit"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::BAD_REQUEST);
+        let unknown = Uuid::new_v4();
+        let req = Request::get(format!("http://localhost/v1/games/{unknown}/fingerprint/audit"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn rl_export_returns_empty_experiences() {
         let app = v1_app(test_state());
@@ -576,15 +1961,7 @@ mod tests {
 
     // Helper: create a session and return its ID.
     async fn create_session(app: &Router) -> String {
-        let body = serde_json::json!({
-            "gameId": Uuid::new_v4().to_string(),
-            "playerProfile": { "behaviorType": "conservative" }
-        });
-        let req = Request::post("http://localhost/v1/sessions")
-            .header("Authorization", "Bearer testkey")
-            .header("Content-Type", "application/json")
-            .body(Body::from(serde_json::to_vec(&body).unwrap()))
-            .unwrap();
+        let req = post_session(&session_request(TEST_GAME_ID, None));
         let res = app.clone().oneshot(req).await.unwrap();
         let bytes = res.into_body().collect().await.unwrap().to_bytes();
         let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
@@ -607,6 +1984,46 @@ mod tests {
         serde_json::from_slice(&bytes).unwrap()
     }
 
+    #[tokio::test]
+    async fn session_etag_and_if_match_guard_actions() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        let req = Request::get(format!("http://localhost/v1/sessions/{session_id}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.headers()["etag"], "\"1\"");
+
+        let bet = |if_match: &str| {
+            Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .header("If-Match", if_match)
+                .body(Body::from(
+                    r#"{"action":{"type":"PlaceBet","amount":{"amount":1.0,"currency":"AUD"}}}"#,
+                ))
+                .unwrap()
+        };
+        let res = app.clone().oneshot(bet("\"1\"")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["etag"], "\"2\"");
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["session"]["version"].as_u64(), Some(2));
+
+        // A client still holding version 1 is rejected, and the session is unchanged.
+        let res = app.clone().oneshot(bet("\"1\"")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let err: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(err["error"]["code"], "STATE_ERROR");
+
+        assert_eq!(app.clone().oneshot(bet("v2")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(app.clone().oneshot(bet("\"7\", \"2\"")).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(app.oneshot(bet("*")).await.unwrap().status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn play_action_stores_event_in_event_store() {
         let app = v1_app(test_state());
@@ -686,15 +2103,7 @@ mod tests {
         let state = test_state();
         let app = v1_app(state);
 
-        let create_body = serde_json::json!({
-            "gameId": Uuid::new_v4().to_string(),
-            "playerProfile": { "behaviorType": "conservative" }
-        });
-        let req = Request::post("http://localhost/v1/sessions")
-            .header("Authorization", "Bearer testkey")
-            .header("Content-Type", "application/json")
-            .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
-            .unwrap();
+        let req = post_session(&session_request(TEST_GAME_ID, None));
         let res = app.clone().oneshot(req).await.unwrap();
         assert_eq!(res.status(), StatusCode::CREATED);
         let bytes = res.into_body().collect().await.unwrap().to_bytes();
@@ -715,4 +2124,590 @@ mod tests {
         let result: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
         assert_eq!(result["session"]["state"].as_str(), Some("Playing"));
     }
+
+    #[tokio::test]
+    async fn spin_returns_engine_grid_and_payout() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+
+        let body = serde_json::json!({ "action": { "type": "Spin" } });
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["session"]["state"].as_str(), Some("Evaluating"));
+        let symbols = json["result"]["symbols"].as_array().expect("symbols array");
+        assert_eq!(symbols.len(), 9, "classic machine shows a 3x3 grid");
+        assert_eq!(json["result"]["grid"].as_array().map(Vec::len), Some(3));
+        assert!(json["result"]["payout"]["amount"].as_f64().is_some_and(|p| p >= 0.0));
+    }
+
+    #[tokio::test]
+    async fn amount_less_spin_is_rewarded_against_the_stake_it_played() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"action":{"type":"Spin"}}"#))
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let payout: Money = serde_json::from_value(json["result"]["payout"].clone()).unwrap();
+
+        let events = state.event_store.list_by_session(session_id.parse().unwrap()).await.unwrap();
+        let expected = compute_reward_safe(payout.amount, Decimal::ONE, state.config.cost_per_spin, 0.5);
+        assert_eq!(events[1].reward, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn place_bet_off_the_bet_ladder_returns_400() {
+        let state = test_state();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../game_engine_targets/slot_game_api_simulator/games/classic_fruit.toml");
+        let def = controller::slot_engine::GameDefinition::load_file(&path).unwrap();
+        let game_id = def.game_id;
+        let registered = state.game_catalog.register(def).unwrap();
+        state.game_repo.create(registered.to_game()).await.unwrap();
+        let app = v1_app(state);
+
+        let req = post_session(&session_request(game_id, None));
+        let res = app.clone().oneshot(req).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap().to_string();
+
+        let bet = |amount: f64| {
+            let body = serde_json::json!({
+                "action": { "type": "PlaceBet", "amount": { "amount": amount, "currency": "AUD" } }
+            });
+            Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
+        let res = app.clone().oneshot(bet(0.3)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res = app.oneshot(bet(1.0)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn actions_rejected_by_state_machine_guards() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        let action = |body: &str| {
+            Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let res = app.clone().oneshot(action(r#"{"action":{"type":"PlaceBet"}}"#)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        // Cashing out before any spin is not a valid transition.
+        let res = app.clone().oneshot(action(r#"{"action":{"type":"CashOut"}}"#)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        // Nor is spinning before a bet; rejected actions leave the session version alone.
+        let res = app.clone().oneshot(action(r#"{"action":{"type":"Spin"}}"#)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let err: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(err["error"]["code"], "STATE_ERROR");
+
+        place_bet(&app, &session_id).await;
+        let res = app.oneshot(action(r#"{"action":{"type":"Spin"}}"#)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let played: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(played["session"]["state"], "Evaluating");
+        assert_eq!(played["session"]["version"], 3);
+    }
+
+    #[tokio::test]
+    async fn session_transitions_record_action_and_actor() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+        for body in [r#"{"action":{"type":"Spin"}}"#, r#"{"action":{"type":"CashOut"}}"#] {
+            let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+        }
+
+        let req = Request::get(format!("http://localhost/v1/sessions/{session_id}/transitions"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let transitions = body["transitions"].as_array().unwrap();
+        let steps: Vec<(&str, &str, &str)> = transitions
+            .iter()
+            .map(|t| (t["from"].as_str().unwrap(), t["to"].as_str().unwrap(), t["action"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("Initialized", "Playing", "PlaceBet"),
+                ("Playing", "Evaluating", "Spin"),
+                ("Evaluating", "Completed", "CashOut"),
+            ]
+        );
+        let actor = api_key_id("testkey");
+        assert!(transitions.iter().all(|t| t["actor"] == actor.as_str()));
+        assert_eq!(transitions[2]["version"], 4);
+        assert!(transitions[0]["timestamp"].is_string());
+
+        let req = Request::get(format!("http://localhost/v1/sessions/{}/transitions", Uuid::new_v4()))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn suspend_resume_and_abort_session() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+        let post = |path: &str, body: &'static str| {
+            Request::post(format!("http://localhost/v1/sessions/{session_id}{path}"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+        let state_of = |res: Response| async move {
+            let bytes = res.into_body().collect().await.unwrap().to_bytes();
+            let v: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            v["state"].as_str().unwrap().to_string()
+        };
+        let spin = r#"{"action":{"type":"Spin"}}"#;
+
+        let res = app.clone().oneshot(post("/suspend", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["etag"], "\"3\"");
+        assert_eq!(state_of(res).await, "Suspended");
+        let res = app.clone().oneshot(post("/action", spin)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        let res = app.clone().oneshot(post("/resume", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(state_of(res).await, "Playing");
+        let res = app.clone().oneshot(post("/resume", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res = app.clone().oneshot(post("/action", spin)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app.clone().oneshot(post("/abort", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(state_of(res).await, "Aborted");
+        let res = app.clone().oneshot(post("/suspend", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res = app.oneshot(post("/action", spin)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn list_sessions_filters_and_pages() {
+        let app = v1_app(test_state());
+        let tagged = CreateSessionRequest { tags: vec!["bot".into()], ..session_request(TEST_GAME_ID, None) };
+        for _ in 0..3 {
+            let req = post_session(&tagged);
+            assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::CREATED);
+        }
+        let playing = create_session(&app).await;
+        place_bet(&app, &playing).await;
+
+        let list = |query: String| {
+            let app = app.clone();
+            async move {
+                let req = Request::get(format!("http://localhost/v1/sessions?{query}"))
+                    .header("Authorization", "Bearer testkey")
+                    .body(Body::empty())
+                    .unwrap();
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (status, serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default())
+            }
+        };
+
+        let (status, body) = list("state=Playing".into()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["sessions"].as_array().unwrap().len(), 1);
+        assert_eq!(body["sessions"][0]["sessionId"], playing.as_str());
+        assert!(body.get("nextCursor").is_none());
+
+        let mut seen = Vec::new();
+        let mut query = format!("tags=bot&gameId={TEST_GAME_ID}&sort=createdAt&order=asc&limit=2");
+        loop {
+            let (status, body) = list(query.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+            for s in body["sessions"].as_array().unwrap() {
+                assert_eq!(s["tags"], serde_json::json!(["bot"]));
+                seen.push(s["sessionId"].as_str().unwrap().to_string());
+            }
+            let Some(cursor) = body["nextCursor"].as_str() else { break };
+            query = format!("tags=bot&sort=createdAt&order=asc&limit=2&cursor={cursor}");
+        }
+        seen.dedup();
+        assert_eq!(seen.len(), 3);
+
+        let (status, body) = list("cursor=nonsense".into()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "INVALID_INPUT");
+    }
+
+    #[tokio::test]
+    async fn player_profile_is_stored_and_max_bet_enforced() {
+        let app = v1_app(test_state());
+        let body = serde_json::json!({
+            "gameId": TEST_GAME_ID.to_string(),
+            "playerProfile": { "behaviorType": "aggressive", "maxBet": { "amount": 0.5, "currency": "AUD" } }
+        });
+        let req = post_session(&body);
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap();
+
+        let req = Request::get(format!("http://localhost/v1/sessions/{session_id}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let bytes = app.clone().oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+        let session: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(session["playerProfile"]["behaviorType"], "aggressive");
+        assert_eq!(session["playerProfile"]["maxBet"]["amount"], 0.5);
+
+        // The ladder's 1.0 bet is above this player's cap.
+        let rejected = place_bet(&app, session_id).await;
+        assert_eq!(rejected["error"]["code"], "INVALID_INPUT");
+        let req = Request::get(format!("http://localhost/v1/sessions/{session_id}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let bytes = app.clone().oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+        let session: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(session["state"], "Initialized");
+
+        let unknown = serde_json::json!({
+            "gameId": TEST_GAME_ID.to_string(),
+            "playerProfile": { "behaviorType": "reckless" }
+        });
+        let req = post_session(&unknown);
+        assert!(app.oneshot(req).await.unwrap().status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn session_metrics_follow_each_action() {
+        let app = v1_app(test_state());
+        let session_id = create_session(&app).await;
+        place_bet(&app, &session_id).await;
+        let mut payouts = Vec::new();
+        for _ in 0..3 {
+            let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"action":{"type":"Spin"}}"#))
+                .unwrap();
+            let bytes = app.clone().oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+            let played: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            payouts.push(played["result"]["payout"]["amount"].as_f64().unwrap());
+            // The action's response already carries the metrics written with its transition.
+            assert_eq!(played["session"]["metrics"]["totalSpins"], payouts.len());
+        }
+
+        let req = Request::get(format!("http://localhost/v1/sessions/{session_id}"))
+            .header("Authorization", "Bearer testkey")
+            .body(Body::empty())
+            .unwrap();
+        let bytes = app.oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+        let session: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let m = &session["metrics"];
+        let total: f64 = payouts.iter().sum();
+        assert_eq!(m["totalSpins"], 3);
+        assert_eq!(m["totalStaked"], 3.0);
+        assert!((m["totalPayout"].as_f64().unwrap() - total).abs() < 1e-9);
+        assert!((m["netResult"].as_f64().unwrap() - (total - 3.0)).abs() < 1e-9);
+        assert_eq!(m["biggestWin"].as_f64(), payouts.iter().copied().reduce(f64::max));
+        assert!(m["currentDrawdown"].as_f64().is_some_and(|d| d >= 0.0));
+        assert!(m["lastActionAt"].is_string());
+    }
+
+    #[tokio::test]
+    async fn wallet_bound_session_debits_stakes_and_credits_payouts() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let session_for = |wallet: Uuid| post_session(&session_request(TEST_GAME_ID, Some(wallet)));
+        let res = app.clone().oneshot(session_for(Uuid::new_v4())).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        // A stake the wallet cannot cover is refused before the session moves.
+        let poor = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(poor, Decimal::new(5, 1))).await.unwrap();
+        let res = app.clone().oneshot(session_for(poor)).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap();
+        let rejected = place_bet(&app, session_id).await;
+        assert_eq!(rejected["error"]["code"], "WALLET_LIMIT_EXCEEDED");
+        let session = state.session_repo.get_by_id(session_id.parse().unwrap()).await.unwrap().unwrap();
+        assert_eq!(session.state, GameState::Initialized);
+
+        let funded = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(funded, Decimal::from(5))).await.unwrap();
+        let res = app.clone().oneshot(session_for(funded)).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap();
+        let session = state.session_repo.get_by_id(session_id.parse().unwrap()).await.unwrap().unwrap();
+        assert_eq!(session.wallet_id, Some(funded));
+        place_bet(&app, session_id).await;
+        // The bet alone only reserves the stake; it is debited when the spin resolves.
+        let wallet = state.wallet_repo.get_by_id(funded).await.unwrap().unwrap();
+        assert_eq!(wallet.balance.amount, Decimal::from(5));
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"action":{"type":"Spin"}}"#))
+            .unwrap();
+        let bytes = app.oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+        let played: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let payout: Money = serde_json::from_value(played["result"]["payout"].clone()).unwrap();
+        let wallet = state.wallet_repo.get_by_id(funded).await.unwrap().unwrap();
+        assert_eq!(wallet.balance.amount, Decimal::from(4) + payout.amount);
+        assert_eq!(wallet.daily_spent.amount, Decimal::ONE);
+        // The stake (and any payout) is booked against the session's game.
+        let entries = state.wallet_repo.list_transactions(funded, None, 10).await.unwrap();
+        let stake = entries.iter().find(|e| e.kind == LedgerEntryKind::Debit).unwrap();
+        assert_eq!(stake.session_id.map(SessionId), Some(session.session_id));
+        assert_eq!(stake.game_id, Some(TEST_GAME_ID));
+        assert!(stake.event_id.is_some());
+    }
+
+    /// Event store whose writes always fail.
+    struct FailingEventStore;
+
+    #[async_trait]
+    impl controller::event_store::EventStore for FailingEventStore {
+        async fn insert(&self, _event: GameplayEvent) -> anyhow::Result<()> {
+            anyhow::bail!("event store unavailable")
+        }
+
+        async fn list_by_session(&self, _session_id: Uuid) -> anyhow::Result<Vec<GameplayEvent>> {
+            Ok(Vec::new())
+        }
+
+        async fn list_by_sessions(&self, _session_ids: &[Uuid]) -> anyhow::Result<Vec<GameplayEvent>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn committed_spin_is_reported_when_its_event_write_fails() {
+        let mut state = test_state();
+        state.event_store = Arc::new(FailingEventStore);
+        let app = v1_app(state.clone());
+        let wallet_id = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet_id, Decimal::from(5))).await.unwrap();
+        let res = app.clone().oneshot(post_session(&session_request(TEST_GAME_ID, Some(wallet_id)))).await.unwrap();
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let session_id = created["sessionId"].as_str().unwrap();
+        place_bet(&app, session_id).await;
+
+        // The stake is explicit: with no events logged there is no bet to recall.
+        let req = Request::post(format!("http://localhost/v1/sessions/{session_id}/action"))
+            .header("Authorization", "Bearer testkey")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"action":{"type":"Spin","amount":{"amount":1.0,"currency":"AUD"}}}"#))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        let played: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let payout: Money = serde_json::from_value(played["result"]["payout"].clone()).unwrap();
+
+        // The reported outcome is the one the session and wallet committed.
+        let session = state.session_repo.get_by_id(session_id.parse().unwrap()).await.unwrap().unwrap();
+        assert_eq!(played["session"]["version"], session.version);
+        assert_eq!(session.metrics.total_spins, 1);
+        let wallet = state.wallet_repo.get_by_id(wallet_id).await.unwrap().unwrap();
+        assert_eq!(wallet.balance.amount, Decimal::from(4) + payout.amount);
+    }
+
+    #[tokio::test]
+    async fn wallet_transactions_page_the_ledger_and_reconcile() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let wallet_id = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet_id, Decimal::from(100))).await.unwrap();
+        let get = |uri: String| {
+            let app = app.clone();
+            async move {
+                let req = Request::get(format!("http://localhost/v1/wallets{uri}"))
+                    .header("Authorization", "Bearer testkey")
+                    .body(Body::empty())
+                    .unwrap();
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (status, serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default())
+            }
+        };
+        for (operation, fee) in [("debit", serde_json::Value::Null), ("credit", serde_json::json!({ "amount": 0.5, "currency": "AUD" }))] {
+            let body = serde_json::json!({ "operation": operation, "amount": { "amount": 10.0, "currency": "AUD" }, "fee": fee });
+            let req = Request::post(format!("http://localhost/v1/wallets/{wallet_id}/operations"))
+                .header("Authorization", "Bearer testkey")
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap();
+            assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+        }
+
+        let (status, page) = get(format!("/{wallet_id}/transactions?limit=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        let credit = &page["transactions"][0];
+        assert_eq!(credit["kind"], "credit");
+        assert_eq!(credit["sequence"], 3);
+        assert_eq!(credit["balanceAfter"]["amount"], 100.0);
+        assert_eq!(
+            credit["postings"],
+            serde_json::json!([
+                { "account": format!("wallet:{wallet_id}"), "amount": { "amount": 10.0, "currency": "AUD" } },
+                { "account": "house", "amount": { "amount": -10.5, "currency": "AUD" } },
+                { "account": "fees", "amount": { "amount": 0.5, "currency": "AUD" } },
+            ])
+        );
+        assert_eq!(page["transactions"][1]["kind"], "debit");
+        let cursor = page["nextCursor"].as_str().unwrap();
+        let (_, rest) = get(format!("/{wallet_id}/transactions?limit=2&cursor={cursor}")).await;
+        assert_eq!(rest["transactions"][0]["kind"], "opening");
+        assert!(rest.get("nextCursor").is_none());
+
+        let (status, check) = get(format!("/{wallet_id}/reconciliation")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(check["consistent"], true);
+        assert_eq!(check["ledgerBalance"]["amount"], 100.0);
+
+        let (status, body) = get(format!("/{wallet_id}/transactions?cursor=latest")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "INVALID_INPUT");
+        let (status, _) = get(format!("/{}/transactions", Uuid::new_v4())).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn wallets_are_readable_and_admins_can_freeze_limit_and_reset_them() {
+        let state = test_state();
+        let app = v1_app(state.clone());
+        let mut ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        ids.sort();
+        for id in ids {
+            state.wallet_repo.create(test_wallet(id, Decimal::from(100))).await.unwrap();
+        }
+        let wallet_id = ids[0];
+        let send = |method: &str, uri: String, token: &str, body: Option<serde_json::Value>| {
+            let req = Request::builder()
+                .method(method)
+                .uri(format!("http://localhost/v1/wallets{uri}"))
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json");
+            let req = match body {
+                Some(body) => req.body(Body::from(serde_json::to_vec(&body).unwrap())),
+                None => req.body(Body::empty()),
+            }
+            .unwrap();
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(req).await.unwrap();
+                let status = res.status();
+                let bytes = res.into_body().collect().await.unwrap().to_bytes();
+                (status, serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default())
+            }
+        };
+        let admin = "admin:ops";
+        let debit = serde_json::json!({ "operation": "debit", "amount": { "amount": 5.0, "currency": "AUD" } });
+
+        let (status, wallet) = send("GET", format!("/{wallet_id}"), "testkey", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((wallet["balance"]["amount"].as_f64(), wallet["frozen"].as_bool()), (Some(100.0), Some(false)));
+        let (status, _) = send("GET", format!("/{}", Uuid::new_v4()), "testkey", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, page) = send("GET", "?limit=2".into(), "testkey", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["wallets"][1]["walletId"], ids[1].to_string());
+        let cursor = page["nextCursor"].as_str().unwrap();
+        let (_, rest) = send("GET", format!("?limit=2&cursor={cursor}"), "testkey", None).await;
+        assert_eq!(rest["wallets"].as_array().unwrap().len(), 1);
+        assert!(rest.get("nextCursor").is_none());
+        let (status, _) = send("GET", "?cursor=first".into(), "testkey", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // Only admins may change a wallet or read its audit trail.
+        let (status, _) = send("POST", format!("/{wallet_id}/freeze"), "testkey", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send("GET", format!("/{wallet_id}/audit"), "testkey", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let reason = serde_json::json!({ "reason": "chargeback review" });
+        let (status, wallet) = send("POST", format!("/{wallet_id}/freeze"), admin, Some(reason)).await;
+        assert_eq!((status, wallet["frozen"].as_bool()), (StatusCode::OK, Some(true)));
+        let (status, body) = send("POST", format!("/{wallet_id}/operations"), "testkey", Some(debit.clone())).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "STATE_ERROR");
+        let (status, _) = send("POST", format!("/{wallet_id}/unfreeze"), admin, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send("POST", format!("/{wallet_id}/operations"), "testkey", Some(debit.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let limits = serde_json::json!({ "dailyLimit": { "amount": 5.0, "currency": "AUD" }, "weeklyLimit": { "amount": 30.0, "currency": "AUD" } });
+        let (status, wallet) = send("PUT", format!("/{wallet_id}/limits"), admin, Some(limits)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(wallet["weeklyLimit"]["amount"], 30.0);
+        let (status, _) = send("POST", format!("/{wallet_id}/operations"), "testkey", Some(debit.clone())).await;
+        assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+        let (status, wallet) = send("POST", format!("/{wallet_id}/reset-daily-spend"), admin, None).await;
+        assert_eq!((status, wallet["dailySpent"]["amount"].as_f64()), (StatusCode::OK, Some(0.0)));
+        let (status, _) = send("POST", format!("/{wallet_id}/operations"), "testkey", Some(debit)).await;
+        assert_eq!(status, StatusCode::OK);
+        let usd = serde_json::json!({ "dailyLimit": { "amount": 5.0, "currency": "USD" } });
+        let (status, _) = send("PUT", format!("/{wallet_id}/limits"), admin, Some(usd)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, audit) = send("GET", format!("/{wallet_id}/audit"), admin, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let records = audit["records"].as_array().unwrap();
+        let actions: Vec<_> = records.iter().map(|r| r["action"]["type"].as_str().unwrap()).collect();
+        assert_eq!(actions, ["freeze", "unfreeze", "setLimits", "resetDailySpend"]);
+        assert_eq!(records[0]["actor"], api_key_id(admin));
+        assert_eq!(records[0]["reason"], "chargeback review");
+        assert_eq!((records[0]["before"]["frozen"].as_bool(), records[0]["after"]["frozen"].as_bool()), (Some(false), Some(true)));
+        assert_eq!(records[3]["before"]["dailySpent"]["amount"], 5.0);
+    }
 }
@@ -1,6 +1,11 @@
 //! Shared API request/response types aligned with openapi.yaml.
 
+use crate::app_state::DomainError;
+use crate::slot_engine::LineWin;
 use crate::state_engine::GameState;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use chrono_tz::Tz;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -26,22 +31,169 @@ impl std::fmt::Display for GameId {
     }
 }
 
-/// Currency per OpenAPI (variant names serialize as "AUD", "USD", "EUR").
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Currency per OpenAPI: an ISO 4217 currency, serialized as its alphabetic code ("AUD").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Currency {
     AUD,
     USD,
     EUR,
+    GBP,
+    NZD,
+    CAD,
+    CHF,
+    JPY,
+    CNY,
+    HKD,
+    SGD,
+    KWD,
 }
 
-/// Money per OpenAPI (amount + currency).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// ISO 4217 entry for a currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyInfo {
+    /// Alphabetic code, e.g. "AUD".
+    pub code: &'static str,
+    /// Numeric code, e.g. 36 for AUD.
+    pub numeric: u16,
+    /// Decimal places of the minor unit: 2 for cents, 0 for JPY, 3 for KWD.
+    pub minor_units: u32,
+    pub name: &'static str,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 12] = [
+        Currency::AUD,
+        Currency::USD,
+        Currency::EUR,
+        Currency::GBP,
+        Currency::NZD,
+        Currency::CAD,
+        Currency::CHF,
+        Currency::JPY,
+        Currency::CNY,
+        Currency::HKD,
+        Currency::SGD,
+        Currency::KWD,
+    ];
+
+    pub fn info(self) -> CurrencyInfo {
+        let (code, numeric, minor_units, name) = match self {
+            Currency::AUD => ("AUD", 36, 2, "Australian dollar"),
+            Currency::USD => ("USD", 840, 2, "US dollar"),
+            Currency::EUR => ("EUR", 978, 2, "Euro"),
+            Currency::GBP => ("GBP", 826, 2, "Pound sterling"),
+            Currency::NZD => ("NZD", 554, 2, "New Zealand dollar"),
+            Currency::CAD => ("CAD", 124, 2, "Canadian dollar"),
+            Currency::CHF => ("CHF", 756, 2, "Swiss franc"),
+            Currency::JPY => ("JPY", 392, 0, "Japanese yen"),
+            Currency::CNY => ("CNY", 156, 2, "Renminbi"),
+            Currency::HKD => ("HKD", 344, 2, "Hong Kong dollar"),
+            Currency::SGD => ("SGD", 702, 2, "Singapore dollar"),
+            Currency::KWD => ("KWD", 414, 3, "Kuwaiti dinar"),
+        };
+        CurrencyInfo { code, numeric, minor_units, name }
+    }
+
+    /// Decimal places of the currency's minor unit.
+    pub fn minor_units(self) -> u32 {
+        self.info().minor_units
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.info().code)
+    }
+}
+
+/// Money per OpenAPI (amount + currency). The amount is a fixed-point decimal held at the
+/// currency's minor-unit precision; on the wire it stays a JSON number. Amounts with more
+/// decimal places than the currency allows are rejected when deserialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "WireMoney")]
 pub struct Money {
-    pub amount: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub amount: Decimal,
     pub currency: Currency,
 }
 
-/// Wallet per OpenAPI.
+#[derive(Deserialize)]
+struct WireMoney {
+    #[serde(with = "rust_decimal::serde::float")]
+    amount: Decimal,
+    currency: Currency,
+}
+
+impl TryFrom<WireMoney> for Money {
+    type Error = String;
+
+    fn try_from(m: WireMoney) -> Result<Self, Self::Error> {
+        let amount = m.amount.normalize();
+        if amount.scale() > m.currency.minor_units() {
+            return Err(format!(
+                "amount {amount} has more than {} decimal places for {}",
+                m.currency.minor_units(),
+                m.currency
+            ));
+        }
+        Ok(Money { amount, currency: m.currency })
+    }
+}
+
+impl Money {
+    /// `amount` rounded to the currency's minor unit, half to even so that rounding over many
+    /// spins does not drift either way.
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        let amount = amount.round_dp_with_strategy(currency.minor_units(), RoundingStrategy::MidpointNearestEven);
+        Money { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money { amount: Decimal::ZERO, currency }
+    }
+
+    /// Converts a floating-point amount (e.g. a slot engine payout), rounding to the minor unit.
+    pub fn from_f64(amount: f64, currency: Currency) -> Result<Self, DomainError> {
+        let amount = Decimal::try_from(amount)
+            .map_err(|_| DomainError::InvalidInput(format!("amount {amount} is not representable")))?;
+        Ok(Money::new(amount, currency))
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount > Decimal::ZERO
+    }
+
+    /// `self + other`; InvalidInput on a currency mismatch or overflow.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, DomainError> {
+        let amount = self.same_currency(other)?.amount.checked_add(other.amount);
+        self.with_amount(amount)
+    }
+
+    /// `self − other`; InvalidInput on a currency mismatch or overflow.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, DomainError> {
+        let amount = self.same_currency(other)?.amount.checked_sub(other.amount);
+        self.with_amount(amount)
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<&Self, DomainError> {
+        if self.currency != other.currency {
+            return Err(DomainError::InvalidInput(format!(
+                "currency {} does not match {}",
+                other.currency, self.currency
+            )));
+        }
+        Ok(self)
+    }
+
+    fn with_amount(&self, amount: Option<Decimal>) -> Result<Money, DomainError> {
+        let amount = amount.ok_or_else(|| DomainError::InvalidInput("amount overflow".into()))?;
+        Ok(Money::new(amount, self.currency))
+    }
+}
+
+/// Wallet per OpenAPI. Debits count towards spend totals over the local day, ISO week
+/// (from Monday) and calendar month in the wallet's timezone; each total resets when its
+/// window ends.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Wallet {
@@ -49,14 +201,231 @@ pub struct Wallet {
     pub balance: Money,
     pub daily_limit: Money,
     pub daily_spent: Money,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_limit: Option<Money>,
+    pub weekly_spent: Money,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_limit: Option<Money>,
+    pub monthly_spent: Money,
+    /// IANA timezone whose midnight bounds the spend windows.
+    #[serde(default = "default_timezone")]
+    pub timezone: Tz,
+    /// Last debit counted in the spend totals; a total is stale once its window has ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spent_at: Option<DateTime<Utc>>,
+    /// Set by an admin: debits are refused, credits (payouts, refunds) still land.
+    #[serde(default)]
+    pub frozen: bool,
 }
 
-/// Session metrics per OpenAPI SessionMetrics.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+fn default_timezone() -> Tz {
+    Tz::UTC
+}
+
+impl Wallet {
+    /// The wallet as of `now`: spend totals whose window has ended since the last debit read
+    /// zero.
+    pub fn at(&self, now: DateTime<Utc>) -> Wallet {
+        let mut wallet = self.clone();
+        let Some(spent_at) = self.spent_at else { return wallet };
+        let (then, now) = (self.windows(spent_at), self.windows(now));
+        let currency = self.balance.currency;
+        if now.day > then.day {
+            wallet.daily_spent = Money::zero(currency);
+        }
+        if now.week > then.week {
+            wallet.weekly_spent = Money::zero(currency);
+        }
+        if now.month > then.month {
+            wallet.monthly_spent = Money::zero(currency);
+        }
+        wallet
+    }
+
+    /// The day, ISO week and month holding `t` in the wallet's timezone.
+    fn windows(&self, t: DateTime<Utc>) -> SpendWindows {
+        let t = t.with_timezone(&self.timezone);
+        SpendWindows {
+            day: t.date_naive(),
+            week: (t.iso_week().year(), t.iso_week().week()),
+            month: (t.year(), t.month()),
+        }
+    }
+
+    /// The wallet after `operation` of `amount` at `now`. A debit must fit the balance and
+    /// what remains of each limit in its current window (WalletLimitExceeded otherwise);
+    /// `amount` must be positive and in the wallet's currency.
+    pub fn apply(&self, operation: &WalletOperationType, amount: &Money, now: DateTime<Utc>) -> Result<Wallet, DomainError> {
+        if !amount.is_positive() {
+            return Err(DomainError::InvalidInput(format!("amount {} must be positive", amount.amount)));
+        }
+        let mut next = self.at(now);
+        match operation {
+            WalletOperationType::Debit if self.frozen => return Err(DomainError::WalletFrozen(self.wallet_id.0)),
+            WalletOperationType::Debit => {
+                next.balance = next.balance.checked_sub(amount)?;
+                next.daily_spent = next.daily_spent.checked_add(amount)?;
+                next.weekly_spent = next.weekly_spent.checked_add(amount)?;
+                next.monthly_spent = next.monthly_spent.checked_add(amount)?;
+                next.spent_at = Some(now);
+                let over = |spent: &Money, limit: Option<&Money>| limit.is_some_and(|l| spent.amount > l.amount);
+                if next.balance.amount < Decimal::ZERO
+                    || over(&next.daily_spent, Some(&next.daily_limit))
+                    || over(&next.weekly_spent, next.weekly_limit.as_ref())
+                    || over(&next.monthly_spent, next.monthly_limit.as_ref())
+                {
+                    return Err(DomainError::WalletLimitExceeded);
+                }
+            }
+            WalletOperationType::Credit => next.balance = next.balance.checked_add(amount)?,
+        }
+        Ok(next)
+    }
+
+    /// The wallet after refunding a debit of `amount` made at `debited_at`: the balance takes
+    /// it back and each spend total whose window, as of `now`, still holds `debited_at` drops
+    /// by it. Lands on a frozen wallet too.
+    pub fn refund(&self, amount: &Money, debited_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<Wallet, DomainError> {
+        if !amount.is_positive() {
+            return Err(DomainError::InvalidInput(format!("amount {} must be positive", amount.amount)));
+        }
+        let mut next = self.at(now);
+        next.balance = next.balance.checked_add(amount)?;
+        let (then, now) = (self.windows(debited_at), self.windows(now));
+        // A total never drops below zero, e.g. after an admin reset it.
+        let less = |spent: &Money| Money { amount: (spent.amount - amount.amount).max(Decimal::ZERO), currency: spent.currency };
+        if now.day == then.day {
+            next.daily_spent = less(&next.daily_spent);
+        }
+        if now.week == then.week {
+            next.weekly_spent = less(&next.weekly_spent);
+        }
+        if now.month == then.month {
+            next.monthly_spent = less(&next.monthly_spent);
+        }
+        Ok(next)
+    }
+
+    /// The wallet after an admin `action`. New limits must be positive and in the wallet's
+    /// currency.
+    pub fn administer(&self, action: &WalletAdminAction) -> Result<Wallet, DomainError> {
+        let mut next = self.clone();
+        match action {
+            WalletAdminAction::SetLimits { daily_limit, weekly_limit, monthly_limit } => {
+                let limits = [("daily", Some(daily_limit)), ("weekly", weekly_limit.as_ref()), ("monthly", monthly_limit.as_ref())];
+                for (window, limit) in limits {
+                    let Some(limit) = limit else { continue };
+                    if limit.currency != self.balance.currency {
+                        return Err(DomainError::InvalidInput(format!(
+                            "{window} limit currency {} does not match wallet currency {}",
+                            limit.currency, self.balance.currency
+                        )));
+                    }
+                    if !limit.is_positive() {
+                        return Err(DomainError::InvalidInput(format!("{window} limit {} must be positive", limit.amount)));
+                    }
+                }
+                next.daily_limit = daily_limit.clone();
+                next.weekly_limit = weekly_limit.clone();
+                next.monthly_limit = monthly_limit.clone();
+            }
+            WalletAdminAction::Freeze => next.frozen = true,
+            WalletAdminAction::Unfreeze => next.frozen = false,
+            WalletAdminAction::ResetDailySpend => next.daily_spent = Money::zero(self.balance.currency),
+        }
+        Ok(next)
+    }
+}
+
+/// Spend windows holding one instant, ordered so a later instant's windows compare greater.
+struct SpendWindows {
+    day: NaiveDate,
+    week: (i32, u32),
+    month: (i32, u32),
+}
+
+/// Change an admin makes to a wallet outside of money movements; each one is audited.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum WalletAdminAction {
+    /// Replaces all three limits; None removes the weekly or monthly one.
+    SetLimits {
+        daily_limit: Money,
+        weekly_limit: Option<Money>,
+        monthly_limit: Option<Money>,
+    },
+    Freeze,
+    Unfreeze,
+    /// Zeroes the current day's spend total.
+    ResetDailySpend,
+}
+
+/// Audit record of one admin change to a wallet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct WalletAuditRecord {
+    pub wallet_id: Uuid,
+    pub action: WalletAdminAction,
+    /// API key id of the admin.
+    pub actor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub before: Wallet,
+    pub after: Wallet,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Session metrics per OpenAPI SessionMetrics: running aggregates over the session's actions,
+/// written together with each state transition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct SessionMetrics {
     pub total_spins: u64,
-    pub total_payout: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub total_staked: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub total_payout: Decimal,
+    /// total_payout − total_staked.
+    #[serde(with = "rust_decimal::serde::float")]
+    pub net_result: Decimal,
+    /// Largest single-spin payout.
+    #[serde(with = "rust_decimal::serde::float")]
+    pub biggest_win: Decimal,
+    /// Highest net_result reached so far (0 before any spin).
+    #[serde(with = "rust_decimal::serde::float")]
+    pub peak_net_result: Decimal,
+    /// peak_net_result − net_result: how far the session is below its best point.
+    #[serde(with = "rust_decimal::serde::float")]
+    pub current_drawdown: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_action_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the idle reaper expired the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expired_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl SessionMetrics {
+    /// Records a non-spin action taken at `at`.
+    pub fn record_action(&mut self, at: chrono::DateTime<chrono::Utc>) {
+        self.last_action_at = Some(at);
+    }
+
+    /// Records a spin of `stake` that paid `payout`, taken at `at`.
+    pub fn record_spin(&mut self, stake: Decimal, payout: Decimal, at: chrono::DateTime<chrono::Utc>) {
+        self.total_spins += 1;
+        self.total_staked += stake;
+        self.total_payout += payout;
+        self.net_result = self.total_payout - self.total_staked;
+        self.biggest_win = self.biggest_win.max(payout);
+        self.peak_net_result = self.peak_net_result.max(self.net_result);
+        self.current_drawdown = self.peak_net_result - self.net_result;
+        self.record_action(at);
+    }
+
+    /// Records the session's expiry at `at`.
+    pub fn record_expiry(&mut self, at: chrono::DateTime<chrono::Utc>) {
+        self.expired_at = Some(at);
+    }
 }
 
 /// Session per OpenAPI Session.
@@ -67,13 +436,43 @@ pub struct Session {
     pub game_id: GameId,
     pub state: GameState,
     pub metrics: SessionMetrics,
+    /// Profile the session was created with.
+    #[serde(default)]
+    pub player_profile: PlayerProfile,
+    /// Wallet that pays the session's stakes and receives its payouts; unbound sessions play
+    /// without money.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<Uuid>,
+    /// Incremented on every state change; exposed as the session's ETag.
+    #[serde(default)]
+    pub version: u64,
+    /// Last state change (or creation); the idle reaper measures inactivity from here.
+    #[serde(default)]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Free-form labels set at creation; session listings can filter on them.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Simulated player behaviour; maps onto `simulator_human_proxy::BehaviourProfile`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorType {
+    #[default]
+    Conservative,
+    Aggressive,
+    #[serde(alias = "mixed")]
+    MixedAdaptive,
 }
 
 /// Player profile per OpenAPI PlayerProfile.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerProfile {
-    pub behavior_type: String,
+    pub behavior_type: BehaviorType,
+    /// Largest stake the session may bet.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_bet: Option<Money>,
 }
@@ -84,6 +483,11 @@ pub struct PlayerProfile {
 pub struct CreateSessionRequest {
     pub game_id: GameId,
     pub player_profile: PlayerProfile,
+    /// Wallet to bind the session to.
+    #[serde(default)]
+    pub wallet_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Create session response per OpenAPI CreateSessionResponse.
@@ -114,13 +518,21 @@ pub struct GameplayAction {
 }
 
 /// Gameplay result returned by the simulator.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameplayResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payout: Option<Money>,
     #[serde(default)]
     pub symbols: Vec<String>,
+    /// Visible reel window, `grid[row][reel]`; `symbols` is this grid flattened row by row.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grid: Vec<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub line_wins: Vec<LineWin>,
+    /// The spin never completed (e.g. the server crashed mid-action) and was voided on recovery.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub voided: bool,
 }
 
 /// Play action request per OpenAPI PlayActionRequest.
@@ -157,6 +569,9 @@ pub enum WalletOperationType {
 pub struct WalletOperationRequest {
     pub operation: WalletOperationType,
     pub amount: Money,
+    /// Cost of the operation borne by the house, booked to the fees account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Money>,
 }
 
 /// Wallet operation response.
@@ -165,6 +580,65 @@ pub struct WalletOperationResponse {
     pub wallet: Wallet,
 }
 
+/// Response for GET /wallets/{id}/transactions: one page of the wallet's ledger.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletTransactionsResponse {
+    pub transactions: Vec<crate::ledger::LedgerEntry>,
+    /// Pass as `cursor` to fetch the next (older) page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Response for GET /wallets/{id}/reconciliation: the stored balance against the one
+/// recomputed from the wallet's ledger.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletReconciliation {
+    pub wallet_id: Uuid,
+    pub balance: Money,
+    pub ledger_balance: Money,
+    pub consistent: bool,
+}
+
+/// Response for GET /wallets: one page of wallets, ordered by id.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletListResponse {
+    pub wallets: Vec<Wallet>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Request for PUT /wallets/{id}/limits (admin).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWalletLimitsRequest {
+    pub daily_limit: Money,
+    #[serde(default)]
+    pub weekly_limit: Option<Money>,
+    #[serde(default)]
+    pub monthly_limit: Option<Money>,
+    /// Recorded in the audit trail.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Optional body of the admin freeze, unfreeze and reset-daily-spend endpoints.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WalletAdminRequest {
+    /// Recorded in the audit trail.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Response for GET /wallets/{id}/audit: admin changes, oldest first.
+#[derive(Debug, Serialize)]
+pub struct WalletAuditResponse {
+    pub records: Vec<WalletAuditRecord>,
+}
+
 /// Request for POST /wallets — create a new wallet.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -173,6 +647,13 @@ pub struct CreateWalletRequest {
     pub wallet_id: Option<SessionId>,
     pub balance: Money,
     pub daily_limit: Money,
+    #[serde(default)]
+    pub weekly_limit: Option<Money>,
+    #[serde(default)]
+    pub monthly_limit: Option<Money>,
+    /// IANA timezone bounding the spend windows; UTC if absent.
+    #[serde(default = "default_timezone")]
+    pub timezone: Tz,
 }
 
 /// Response for GET /sessions/{id}/events.
@@ -193,6 +674,45 @@ pub struct SessionEventRecord {
     pub reward: Option<f64>,
 }
 
+/// One persisted session state change, per OpenAPI SessionTransition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTransition {
+    pub session_id: SessionId,
+    pub from: GameState,
+    pub to: GameState,
+    /// Action or event that triggered the change (e.g. "Spin").
+    pub action: String,
+    /// API key id of the caller; None for system-initiated transitions.
+    pub actor: Option<String>,
+    /// Session version after the change.
+    pub version: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Gameplay event the triggering action writes once its effects have run; crash recovery
+    /// resolves transitions whose event never arrived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<Uuid>,
+    /// Request payload of the triggering action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<serde_json::Value>,
+}
+
+/// Response for GET /sessions: one page of sessions.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionListResponse {
+    pub sessions: Vec<Session>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Response for GET /sessions/{id}/transitions.
+#[derive(Debug, Serialize)]
+pub struct SessionTransitionsResponse {
+    pub transitions: Vec<SessionTransition>,
+}
+
 /// Response for GET /games/{gameId}/fingerprint.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -201,6 +721,61 @@ pub struct GameFingerprintResponse {
     pub rng_signature: String,
     pub symbol_map: serde_json::Value,
     pub statistical_profile: serde_json::Value,
+    /// Randomness battery over the run's outcome streams.
+    pub randomness: serde_json::Value,
+    pub run_id: uuid::Uuid,
+    /// 1-based position among the game's fingerprint runs.
+    pub version: u32,
+    /// Number of Spin outcomes the run observed.
+    pub sample_size: u64,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Response for GET /games/{gameId}/fingerprint/versions (oldest first).
+#[derive(Debug, Serialize)]
+pub struct FingerprintVersionsResponse {
+    pub versions: Vec<GameFingerprintResponse>,
+}
+
+/// Registered game per OpenAPI Game; mirrors a row of the `games` table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Game {
+    pub game_id: GameId,
+    pub name: String,
+    pub rng_signature: serde_json::Value,
+    pub symbol_map: serde_json::Value,
+    pub statistical_profile: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Request for POST /games — register a game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGameRequest {
+    /// Client-supplied game ID; server generates one via Uuid::new_v4() if absent.
+    pub game_id: Option<GameId>,
+    pub name: String,
+    /// JSON documents default to `{}` when absent.
+    pub rng_signature: Option<serde_json::Value>,
+    pub symbol_map: Option<serde_json::Value>,
+    pub statistical_profile: Option<serde_json::Value>,
+}
+
+/// Request for PATCH /games/{gameId}; absent fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGameRequest {
+    pub name: Option<String>,
+    pub rng_signature: Option<serde_json::Value>,
+    pub symbol_map: Option<serde_json::Value>,
+    pub statistical_profile: Option<serde_json::Value>,
+}
+
+/// Response for GET /games.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamesResponse {
+    pub games: Vec<Game>,
 }
 
 /// Health check response; GET /v1/health.
@@ -338,10 +913,161 @@ mod tests {
         assert!(j.contains("\"type\":\"Spin\"") || j.contains("\"type\": \"Spin\""));
     }
 
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
     #[test]
     fn currency_serializes_screaming_snake() {
-        let m = Money { amount: 5.0, currency: Currency::AUD };
+        let m = Money { amount: dec("5"), currency: Currency::AUD };
         let j = serde_json::to_string(&m).unwrap();
         assert!(j.contains("AUD"), "expected AUD in {}", j);
     }
+
+    #[test]
+    fn money_is_a_json_number_at_minor_unit_precision() {
+        let m: Money = serde_json::from_str(r#"{"amount":10.25,"currency":"USD"}"#).unwrap();
+        assert_eq!(m.amount, dec("10.25"));
+        assert_eq!(serde_json::to_string(&m).unwrap(), r#"{"amount":10.25,"currency":"USD"}"#);
+        assert!(serde_json::from_str::<Money>(r#"{"amount":1.005,"currency":"AUD"}"#).is_err());
+        // Trailing zeros are not extra precision.
+        let whole: Money = serde_json::from_str(r#"{"amount":2.500,"currency":"EUR"}"#).unwrap();
+        assert_eq!(whole.amount, dec("2.5"));
+    }
+
+    #[test]
+    fn currencies_carry_iso_4217_metadata() {
+        for c in Currency::ALL {
+            let code = serde_json::to_value(c).unwrap();
+            assert_eq!(code, c.info().code);
+            assert_eq!(c.to_string(), c.info().code);
+        }
+        assert_eq!(Currency::AUD.info().numeric, 36);
+        assert_eq!((Currency::JPY.minor_units(), Currency::KWD.minor_units()), (0, 3));
+        assert!(serde_json::from_str::<Money>(r#"{"amount":100.5,"currency":"JPY"}"#).is_err());
+        let dinar: Money = serde_json::from_str(r#"{"amount":1.125,"currency":"KWD"}"#).unwrap();
+        assert_eq!(dinar.amount, dec("1.125"));
+    }
+
+    #[test]
+    fn money_arithmetic_is_exact_and_checked() {
+        let cent = Money { amount: dec("0.1"), currency: Currency::AUD };
+        let mut total = Money::zero(Currency::AUD);
+        for _ in 0..1_000_000 {
+            total = total.checked_add(&cent).unwrap();
+        }
+        assert_eq!(total.amount, dec("100000"));
+        assert_eq!(total.checked_sub(&cent).unwrap().amount, dec("99999.9"));
+
+        let usd = Money::zero(Currency::USD);
+        assert!(matches!(total.checked_add(&usd), Err(DomainError::InvalidInput(_))));
+        let max = Money { amount: Decimal::MAX, currency: Currency::AUD };
+        let one = Money { amount: Decimal::ONE, currency: Currency::AUD };
+        assert!(matches!(max.checked_add(&one), Err(DomainError::InvalidInput(_))));
+
+        // Half-cent payouts round to even.
+        assert_eq!(Money::from_f64(0.125, Currency::AUD).unwrap().amount, dec("0.12"));
+        assert_eq!(Money::from_f64(0.375, Currency::AUD).unwrap().amount, dec("0.38"));
+        assert_eq!(Money::from_f64(0.1 + 0.2, Currency::AUD).unwrap().amount, dec("0.3"));
+    }
+
+    #[test]
+    fn session_metrics_track_net_result_and_drawdown() {
+        let at = chrono::Utc::now();
+        let mut m = SessionMetrics::default();
+        m.record_spin(dec("1"), dec("5"), at);
+        m.record_spin(dec("1"), dec("0"), at);
+        m.record_spin(dec("2"), dec("0.5"), at);
+        assert_eq!(m.total_spins, 3);
+        assert_eq!((m.total_staked, m.total_payout, m.net_result), (dec("4"), dec("5.5"), dec("1.5")));
+        assert_eq!((m.biggest_win, m.peak_net_result, m.current_drawdown), (dec("5"), dec("4"), dec("2.5")));
+        assert_eq!(m.last_action_at, Some(at));
+        let j = serde_json::to_value(&m).unwrap();
+        assert_eq!(j["netResult"], 1.5);
+
+        // Metrics stored before the aggregates were added still load.
+        let old: SessionMetrics = serde_json::from_str(r#"{"totalSpins":2,"totalPayout":1.0}"#).unwrap();
+        assert_eq!((old.total_spins, old.net_result), (2, Decimal::ZERO));
+    }
+
+    #[test]
+    fn spend_windows_roll_over_at_local_boundaries() {
+        let aud = |amount: i64| Money { amount: Decimal::from(amount), currency: Currency::AUD };
+        let mut wallet = crate::test_support::test_wallet(Uuid::new_v4(), Decimal::from(100));
+        wallet.daily_limit = aud(10);
+        wallet.weekly_limit = Some(aud(20));
+        wallet.monthly_limit = Some(aud(16));
+        wallet.timezone = chrono_tz::Australia::Sydney;
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let debit = |w: &Wallet, amount: i64, now: &str| w.apply(&WalletOperationType::Debit, &aud(amount), at(now));
+        let limited = |r: Result<Wallet, DomainError>| matches!(r, Err(DomainError::WalletLimitExceeded));
+
+        // Thursday 29 Jan, 23:00 in Sydney (UTC+11).
+        let w = debit(&wallet, 10, "2026-01-29T12:00:00Z").unwrap();
+        assert!(limited(debit(&w, 1, "2026-01-29T12:30:00Z")));
+        // 00:30 on Friday in Sydney, still the 29th in UTC: a new day, same week and month.
+        assert_eq!(w.at(at("2026-01-29T13:30:00Z")).daily_spent, aud(0));
+        let w = debit(&w, 6, "2026-01-29T13:30:00Z").unwrap();
+        assert_eq!((w.weekly_spent.clone(), w.monthly_spent.clone()), (aud(16), aud(16)));
+        assert!(limited(debit(&w, 1, "2026-01-29T14:00:00Z")));
+        // Sunday 1 Feb: a new month, same ISO week.
+        let w = debit(&w, 4, "2026-01-31T13:30:00Z").unwrap();
+        assert_eq!(w.monthly_spent, aud(4));
+        assert!(limited(debit(&w, 1, "2026-01-31T14:00:00Z")));
+        // Monday 2 Feb: a new week.
+        let w = debit(&w, 1, "2026-02-01T13:30:00Z").unwrap();
+        assert_eq!((w.daily_spent, w.weekly_spent, w.monthly_spent), (aud(1), aud(1), aud(5)));
+    }
+
+    #[test]
+    fn refunds_give_back_spend_in_the_windows_of_their_debit() {
+        let aud = |amount: i64| Money { amount: Decimal::from(amount), currency: Currency::AUD };
+        let mut wallet = crate::test_support::test_wallet(Uuid::new_v4(), Decimal::from(100));
+        wallet.daily_limit = aud(10);
+        wallet.weekly_limit = Some(aud(20));
+        wallet.timezone = chrono_tz::Australia::Sydney;
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        // Thursday 29 Jan, 23:00 in Sydney.
+        let thursday = at("2026-01-29T12:00:00Z");
+        let w = wallet.apply(&WalletOperationType::Debit, &aud(10), thursday).unwrap();
+        // Refunded the same day: the whole limit is available again.
+        let same_day = w.refund(&aud(10), thursday, at("2026-01-29T12:30:00Z")).unwrap();
+        assert!(same_day.apply(&WalletOperationType::Debit, &aud(10), at("2026-01-29T12:45:00Z")).is_ok());
+        assert_eq!((same_day.balance, same_day.daily_spent, same_day.weekly_spent), (aud(100), aud(0), aud(0)));
+
+        // Refunded on Friday after another debit: that day's total is untouched, the week's
+        // still drops.
+        let friday = at("2026-01-29T13:30:00Z");
+        let w = w.apply(&WalletOperationType::Debit, &aud(4), friday).unwrap();
+        let next_day = w.refund(&aud(10), thursday, friday).unwrap();
+        assert_eq!((next_day.balance, next_day.daily_spent, next_day.weekly_spent), (aud(96), aud(4), aud(4)));
+
+        // Frozen wallets take refunds; totals never go negative.
+        let frozen = w.administer(&WalletAdminAction::Freeze).unwrap().administer(&WalletAdminAction::ResetDailySpend).unwrap();
+        let refunded = frozen.refund(&aud(4), friday, friday).unwrap();
+        assert_eq!((refunded.daily_spent, refunded.weekly_spent), (aud(0), aud(10)));
+    }
+
+    #[test]
+    fn frozen_wallets_refuse_debits_and_admin_limits_are_validated() {
+        let aud = |amount: i64| Money { amount: Decimal::from(amount), currency: Currency::AUD };
+        let now = Utc::now();
+        let wallet = crate::test_support::test_wallet(Uuid::new_v4(), Decimal::from(100));
+        let frozen = wallet.administer(&WalletAdminAction::Freeze).unwrap();
+        assert!(matches!(
+            frozen.apply(&WalletOperationType::Debit, &aud(1), now),
+            Err(DomainError::WalletFrozen(id)) if id == wallet.wallet_id.0
+        ));
+        // Payouts still land.
+        assert_eq!(frozen.apply(&WalletOperationType::Credit, &aud(1), now).unwrap().balance, aud(101));
+        assert!(!frozen.administer(&WalletAdminAction::Unfreeze).unwrap().frozen);
+
+        let limits = |daily: Money| WalletAdminAction::SetLimits { daily_limit: daily, weekly_limit: Some(aud(50)), monthly_limit: None };
+        let limited = wallet.administer(&limits(aud(5))).unwrap();
+        assert_eq!((limited.daily_limit, limited.weekly_limit, limited.monthly_limit), (aud(5), Some(aud(50)), None));
+        assert!(matches!(wallet.administer(&limits(aud(0))), Err(DomainError::InvalidInput(_))));
+        let usd = Money { amount: Decimal::from(5), currency: Currency::USD };
+        assert!(matches!(wallet.administer(&limits(usd)), Err(DomainError::InvalidInput(_))));
+    }
 }
@@ -1,16 +1,25 @@
 //! Application state: dependency injection container for all repositories.
 //! Uses Arc<dyn Trait> so handlers are unit-testable without a database.
 
-use crate::api::{Money, Session, Wallet, WalletOperationType};
+use crate::api::{
+    Currency, Game, Money, Session, SessionTransition, UpdateGameRequest, Wallet, WalletAdminAction,
+    WalletAuditRecord, WalletOperationType, WalletReconciliation,
+};
 use crate::event_store::EventStore;
 use crate::fingerprinter::FingerprintStore;
+use crate::fx::FxRateProvider;
+use crate::ledger::{LedgerCause, LedgerEntry};
 use crate::metrics::SessionMetrics;
 use crate::ratelimit::RateLimiter;
 use crate::rl_feedback_loop::ExperienceStore;
+use crate::slot_engine::{GameCatalog, SlotMachine, SlotRng};
 use crate::state_engine::GameState;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
@@ -25,6 +34,13 @@ pub struct AppConfig {
     pub human_likeness_weight: f64,
     /// Rate-limit cap: maximum requests per minute per token (default 100).
     pub rate_limit_rpm: u32,
+    /// Seed for the slot engine RNG; None seeds from entropy (non-reproducible).
+    pub rng_seed: Option<u64>,
+    /// Inactivity after which a live session expires, unless its game definition sets
+    /// `idleTtlSecs` (default 30 minutes).
+    pub session_idle_ttl: Duration,
+    /// How often the idle reaper scans for expired sessions (default 60 s).
+    pub reaper_interval: Duration,
 }
 
 impl Default for AppConfig {
@@ -33,6 +49,9 @@ impl Default for AppConfig {
             cost_per_spin: 0.01,
             human_likeness_weight: 0.3,
             rate_limit_rpm: 100,
+            rng_seed: None,
+            session_idle_ttl: Duration::from_secs(30 * 60),
+            reaper_interval: Duration::from_secs(60),
         }
     }
 }
@@ -52,6 +71,144 @@ pub enum DomainError {
     Internal(String),
     #[error("rate limit exceeded")]
     RateLimitExceeded,
+    #[error("stale session version: expected {expected}, current {current}")]
+    VersionConflict { expected: u64, current: u64 },
+    #[error("action not allowed in {state:?}: {reason}")]
+    ActionNotAllowed { state: GameState, reason: String },
+    #[error("wallet {0} is frozen")]
+    WalletFrozen(Uuid),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("conflict: {0}")]
+    Conflict(String),
+}
+
+/// What triggered a state change; persisted with the transition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransitionCause {
+    /// Action or event name (e.g. "Spin").
+    pub action: String,
+    /// API key id of the caller; None for system-initiated transitions.
+    pub actor: Option<String>,
+    /// Gameplay event the action will write after the transition (see SessionTransition).
+    pub event_id: Option<Uuid>,
+    /// Request payload of the action.
+    pub request: Option<serde_json::Value>,
+}
+
+/// Timestamp session listings are ordered by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Direction of a listing's order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Position in a session listing: the sort timestamp and id of the last session returned.
+/// Ties on the timestamp are broken by session id, so pages never overlap or skip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionCursor {
+    pub at: DateTime<Utc>,
+    pub session_id: Uuid,
+}
+
+impl SessionCursor {
+    /// Position just after `session` in a listing sorted by `sort`.
+    pub fn after(session: &Session, sort: SessionSort) -> Self {
+        Self {
+            at: sort.key(session),
+            session_id: session.session_id.0,
+        }
+    }
+
+    /// Opaque token handed to clients as `nextCursor`.
+    pub fn encode(&self) -> String {
+        format!(
+            "{:x}.{:x}-{}",
+            self.at.timestamp(),
+            self.at.timestamp_subsec_nanos(),
+            self.session_id.simple()
+        )
+    }
+
+    /// Parses a token from `encode`; InvalidInput if malformed.
+    pub fn decode(token: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::InvalidInput(format!("invalid cursor {token:?}"));
+        let (at, id) = token.split_once('-').ok_or_else(invalid)?;
+        let (secs, nanos) = at.split_once('.').ok_or_else(invalid)?;
+        let secs = i64::from_str_radix(secs, 16).map_err(|_| invalid())?;
+        let nanos = u32::from_str_radix(nanos, 16).map_err(|_| invalid())?;
+        Ok(Self {
+            at: DateTime::from_timestamp(secs, nanos).ok_or_else(invalid)?,
+            session_id: Uuid::try_parse(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl SessionSort {
+    /// The session's value of this sort key; sessions without one sort first.
+    pub fn key(self, session: &Session) -> DateTime<Utc> {
+        let at = match self {
+            SessionSort::CreatedAt => session.created_at,
+            SessionSort::UpdatedAt => session.updated_at,
+        };
+        at.unwrap_or_default()
+    }
+}
+
+/// Filters, order and page position for `SessionRepository::list`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionQuery {
+    pub state: Option<GameState>,
+    pub game_id: Option<Uuid>,
+    /// Inclusive lower bound on creation time.
+    pub created_from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on creation time.
+    pub created_to: Option<DateTime<Utc>>,
+    /// Sessions must carry every one of these tags.
+    pub tags: Vec<String>,
+    pub sort: SessionSort,
+    pub order: SortOrder,
+    /// Only sessions strictly after this position in the listing's order.
+    pub after: Option<SessionCursor>,
+    pub limit: u32,
+}
+
+impl SessionQuery {
+    /// Whether `session` passes the filters and lies after the cursor (in-memory backends).
+    pub fn matches(&self, session: &Session) -> bool {
+        let created = session.created_at.unwrap_or_default();
+        self.state.is_none_or(|s| session.state == s)
+            && self.game_id.is_none_or(|g| session.game_id.0 == g)
+            && self.created_from.is_none_or(|t| created >= t)
+            && self.created_to.is_none_or(|t| created < t)
+            && self.tags.iter().all(|t| session.tags.contains(t))
+            && self.after.is_none_or(|c| {
+                let pos = (self.sort.key(session), session.session_id.0);
+                match self.order {
+                    SortOrder::Asc => pos > (c.at, c.session_id),
+                    SortOrder::Desc => pos < (c.at, c.session_id),
+                }
+            })
+    }
+
+    /// Orders sessions as the listing does (in-memory backends).
+    pub fn sort(&self, sessions: &mut [Session]) {
+        sessions.sort_by_key(|s| (self.sort.key(s), s.session_id.0));
+        if self.order == SortOrder::Desc {
+            sessions.reverse();
+        }
+    }
 }
 
 /// Session repository trait: CRUD on sessions.
@@ -59,28 +216,94 @@ pub enum DomainError {
 pub trait SessionRepository: Send + Sync {
     async fn create(&self, session: Session) -> Result<(), DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Session>, DomainError>;
-    async fn update_state(&self, id: Uuid, state: GameState) -> Result<Session, DomainError>;
+    /// Compare-and-swap: sets `state` and bumps the version only if the session is still at
+    /// `expected_version`; VersionConflict otherwise. The change is recorded as a
+    /// SessionTransition together with the update, so history and state cannot diverge.
+    /// With `metrics`, the session's aggregates are replaced in the same write.
+    async fn update_state(
+        &self,
+        id: Uuid,
+        expected_version: u64,
+        state: GameState,
+        metrics: Option<crate::api::SessionMetrics>,
+        cause: TransitionCause,
+    ) -> Result<Session, DomainError>;
+    /// All sessions created for a game.
+    async fn list_by_game(&self, game_id: Uuid) -> Result<Vec<Session>, DomainError>;
+    /// Non-terminal sessions whose last update is before `cutoff`.
+    async fn list_idle(&self, cutoff: DateTime<Utc>) -> Result<Vec<Session>, DomainError>;
+    /// All non-terminal sessions.
+    async fn list_active(&self) -> Result<Vec<Session>, DomainError>;
+    /// Sessions matching `query`'s filters after its cursor, in its order, at most `limit`.
+    async fn list(&self, query: &SessionQuery) -> Result<Vec<Session>, DomainError>;
+    /// Recorded transitions of a session, oldest first.
+    async fn list_transitions(&self, id: Uuid) -> Result<Vec<SessionTransition>, DomainError>;
 }
 
-/// Wallet repository trait: read and apply operations.
+/// Game repository trait: the registry of games sessions may reference.
+#[async_trait]
+pub trait GameRepository: Send + Sync {
+    /// Inserts a game; InvalidInput if the id is already registered.
+    async fn create(&self, game: Game) -> Result<(), DomainError>;
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Game>, DomainError>;
+    /// Lists games ordered by creation time.
+    async fn list(&self, limit: u32, offset: u32) -> Result<Vec<Game>, DomainError>;
+    /// Applies the fields present in `patch`; NotFound if the game does not exist.
+    async fn update(&self, id: Uuid, patch: UpdateGameRequest) -> Result<Game, DomainError>;
+}
+
+/// Wallet repository trait: read and apply operations. Every balance change is booked in the
+/// wallet's ledger together with the change itself.
 #[async_trait]
 pub trait WalletRepository: Send + Sync {
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Wallet>, DomainError>;
+    /// Applies the operation and books it, with `cause`, as the wallet's next ledger entry.
     async fn apply_operation(
         &self,
         wallet_id: Uuid,
         operation: WalletOperationType,
         amount: Money,
+        cause: LedgerCause,
     ) -> Result<Wallet, DomainError>;
+    /// Refunds the debit booked for gameplay event `event_id` (see `Wallet::refund`) as a Refund
+    /// entry. None, changing nothing, if there is no such debit or it was already refunded, so
+    /// a refund can be retried safely.
+    async fn refund_debit(&self, wallet_id: Uuid, event_id: Uuid) -> Result<Option<Wallet>, DomainError>;
+    /// Creates the wallet with an opening entry; Conflict if a wallet with its id exists.
     async fn create(&self, wallet: Wallet) -> Result<(), DomainError>;
+    /// Ledger entries of a wallet, newest first, with a sequence below `before`, at most `limit`.
+    async fn list_transactions(
+        &self,
+        wallet_id: Uuid,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<LedgerEntry>, DomainError>;
+    /// Ledger entries a wallet booked for a session's actions, oldest first.
+    async fn list_session_entries(&self, wallet_id: Uuid, session_id: Uuid) -> Result<Vec<LedgerEntry>, DomainError>;
+    /// Sum of the wallet account's postings across its ledger: the balance recomputed.
+    async fn ledger_balance(&self, wallet_id: Uuid) -> Result<Decimal, DomainError>;
+    /// Wallets ordered by id, with an id above `after`, at most `limit`.
+    async fn list(&self, after: Option<Uuid>, limit: u32) -> Result<Vec<Wallet>, DomainError>;
+    /// Applies an admin action and records it, with `actor` and `reason`, in the wallet's
+    /// audit trail; returns the wallet after it.
+    async fn administer(
+        &self,
+        wallet_id: Uuid,
+        action: WalletAdminAction,
+        actor: String,
+        reason: Option<String>,
+    ) -> Result<Wallet, DomainError>;
+    /// Admin changes to a wallet, oldest first.
+    async fn list_audit(&self, wallet_id: Uuid) -> Result<Vec<WalletAuditRecord>, DomainError>;
 }
 
 /// Shared application state injected into every handler.
 #[derive(Clone)]
 pub struct AppState {
     pub session_repo: Arc<dyn SessionRepository>,
+    pub game_repo: Arc<dyn GameRepository>,
     pub wallet_repo: Arc<dyn WalletRepository>,
-    /// Sync event store (Arc because EventStore is a sync trait).
+    /// Gameplay event log.
     pub event_store: Arc<dyn EventStore>,
     /// Sync fingerprint store.
     pub fingerprint_store: Arc<dyn FingerprintStore>,
@@ -94,24 +317,35 @@ pub struct AppState {
     pub metrics: Arc<SessionMetrics>,
     /// Runtime configuration (cost_per_spin, likeness weight, rate limit).
     pub config: Arc<AppConfig>,
+    /// Fallback slot engine for sessions whose game has no registered definition.
+    pub slot_machine: Arc<SlotMachine>,
+    /// Game definitions registered at startup; their engines take precedence.
+    pub game_catalog: Arc<GameCatalog>,
+    /// Shared engine RNG; seeded from `config.rng_seed` when set.
+    pub slot_rng: Arc<Mutex<SlotRng>>,
+    /// Exchange rates for wallet operations in another currency; None rejects them.
+    pub fx_rates: Option<Arc<dyn FxRateProvider>>,
 }
 
 impl AppState {
     /// Build AppState from repositories and an optional comma-separated API key env var.
     pub fn new(
         session_repo: Arc<dyn SessionRepository>,
+        game_repo: Arc<dyn GameRepository>,
         wallet_repo: Arc<dyn WalletRepository>,
         event_store: Arc<dyn EventStore>,
         fingerprint_store: Arc<dyn FingerprintStore>,
         rl_store: Arc<dyn ExperienceStore>,
         api_keys_csv: Option<&str>,
     ) -> Self {
-        Self::with_config(session_repo, wallet_repo, event_store, fingerprint_store, rl_store, api_keys_csv, AppConfig::default())
+        Self::with_config(session_repo, game_repo, wallet_repo, event_store, fingerprint_store, rl_store, api_keys_csv, AppConfig::default())
     }
 
     /// Build AppState with explicit AppConfig.
+    #[allow(clippy::too_many_arguments)]
     pub fn with_config(
         session_repo: Arc<dyn SessionRepository>,
+        game_repo: Arc<dyn GameRepository>,
         wallet_repo: Arc<dyn WalletRepository>,
         event_store: Arc<dyn EventStore>,
         fingerprint_store: Arc<dyn FingerprintStore>,
@@ -127,8 +361,10 @@ impl AppState {
             .map(String::from)
             .collect();
         let rpm = config.rate_limit_rpm;
+        let slot_rng = config.rng_seed.map(SlotRng::seeded).unwrap_or_else(SlotRng::from_entropy);
         Self {
             session_repo,
+            game_repo,
             wallet_repo,
             event_store,
             fingerprint_store,
@@ -137,6 +373,80 @@ impl AppState {
             rate_limiter: Arc::new(RateLimiter::new(rpm, Duration::from_secs(60))),
             metrics: Arc::new(SessionMetrics::new()),
             config: Arc::new(config),
+            slot_machine: Arc::new(SlotMachine::classic()),
+            game_catalog: Arc::new(GameCatalog::new()),
+            slot_rng: Arc::new(Mutex::new(slot_rng)),
+            fx_rates: None,
+        }
+    }
+
+    /// Converts wallet operations in other currencies through `provider`.
+    pub fn with_fx_rates(mut self, provider: Arc<dyn FxRateProvider>) -> Self {
+        self.fx_rates = Some(provider);
+        self
+    }
+
+    /// `amount` in `to`, converted through `fx_rates` if needed (see `fx::convert`).
+    pub async fn convert(&self, amount: &Money, to: Currency) -> Result<Money, DomainError> {
+        crate::fx::convert(self.fx_rates.as_deref(), amount, to).await
+    }
+
+    /// Applies `operation` of `amount` to a wallet, converting `amount` (and any fee in
+    /// `cause`) to the wallet's currency first. Returns the updated wallet and the amount
+    /// actually applied.
+    pub async fn wallet_operation(
+        &self,
+        wallet_id: Uuid,
+        operation: WalletOperationType,
+        amount: &Money,
+        mut cause: LedgerCause,
+    ) -> Result<(Wallet, Money), DomainError> {
+        let wallet = self
+            .wallet_repo
+            .get_by_id(wallet_id)
+            .await?
+            .ok_or(DomainError::NotFound(wallet_id))?;
+        let currency = wallet.balance.currency;
+        let applied = self.convert(amount, currency).await?;
+        if let Some(fee) = &cause.fee {
+            cause.fee = Some(self.convert(fee, currency).await?);
         }
+        let wallet = self.wallet_repo.apply_operation(wallet_id, operation, applied.clone(), cause).await?;
+        Ok((wallet, applied))
+    }
+
+    /// A page of a wallet's ledger, newest first, and the cursor of the next page if any.
+    pub async fn wallet_transactions(
+        &self,
+        wallet_id: Uuid,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<(Vec<LedgerEntry>, Option<u64>), DomainError> {
+        self.wallet_repo.get_by_id(wallet_id).await?.ok_or(DomainError::NotFound(wallet_id))?;
+        let mut entries = self.wallet_repo.list_transactions(wallet_id, before, limit.saturating_add(1)).await?;
+        let next = if entries.len() > limit as usize {
+            entries.truncate(limit as usize);
+            entries.last().map(|e| e.sequence)
+        } else {
+            None
+        };
+        Ok((entries, next))
+    }
+
+    /// Recomputes a wallet's balance from its ledger and compares it with the stored one.
+    pub async fn reconcile_wallet(&self, wallet_id: Uuid) -> Result<WalletReconciliation, DomainError> {
+        let wallet = self
+            .wallet_repo
+            .get_by_id(wallet_id)
+            .await?
+            .ok_or(DomainError::NotFound(wallet_id))?;
+        let ledger = self.wallet_repo.ledger_balance(wallet_id).await?;
+        let ledger_balance = Money { amount: ledger, currency: wallet.balance.currency };
+        Ok(WalletReconciliation {
+            wallet_id,
+            consistent: ledger_balance == wallet.balance,
+            balance: wallet.balance,
+            ledger_balance,
+        })
     }
 }
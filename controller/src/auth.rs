@@ -15,16 +15,11 @@ pub enum Role {
 /// Returns None if header is missing, empty, or not Bearer.
 pub fn parse_bearer_token(header_value: Option<&str>) -> Option<String> {
     let v = header_value?.trim();
-    let prefix = "Bearer ";
-    if v.starts_with(prefix) {
-        let token = v[prefix.len()..].trim();
-        if token.is_empty() {
-            return None;
-        }
-        Some(token.to_string())
-    } else {
-        None
+    let token = v.strip_prefix("Bearer ")?.trim();
+    if token.is_empty() {
+        return None;
     }
+    Some(token.to_string())
 }
 
 /// Validate token against the configured API key set and return the caller's Role.
@@ -50,6 +45,20 @@ pub fn validate_token(token: &str, api_keys: &HashSet<String>) -> Result<Role> {
     Ok(Role::User)
 }
 
+/// Authenticated caller, attached to the request by the auth middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caller {
+    /// Stable id of the API key (see `api_key_id`).
+    pub key_id: String,
+    pub role: Role,
+}
+
+/// Identifies an API key in audit records without storing the key: the FNV-1a digest of the
+/// token as 16 hex chars, prefixed "key_".
+pub fn api_key_id(token: &str) -> String {
+    format!("key_{:016x}", crate::fingerprinter::fnv1a_64(token.as_bytes()))
+}
+
 /// Check if role is allowed for an action (e.g. wallet operations require User; admin-only require Admin).
 pub fn role_allowed(required: Role, user_role: Role) -> bool {
     match required {
@@ -117,6 +126,15 @@ mod tests {
         assert_eq!(validate_token("admin:secret", &keys).unwrap(), Role::Admin);
     }
 
+    #[test]
+    fn api_key_id_is_stable_and_hides_the_key() {
+        let id = api_key_id("validkey");
+        assert_eq!(id, api_key_id("validkey"));
+        assert_ne!(id, api_key_id("otherkey"));
+        assert_eq!(id.len(), 20);
+        assert!(!id.contains("validkey"));
+    }
+
     #[test]
     fn role_allowed_user_can_do_user_actions() {
         assert!(role_allowed(Role::User, Role::User));
@@ -1,10 +1,12 @@
 //! Event store: persist and list gameplay events (action + result) per session.
 
 use anyhow::Result;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 /// Persisted gameplay event: one action and its result per session.
@@ -32,11 +34,16 @@ pub fn validate_action_type(action: &JsonValue) -> bool {
 }
 
 /// Event store abstraction: insert and list by session.
+#[async_trait]
 pub trait EventStore: Send + Sync {
-    /// Persist one event; returns error if validation fails or write fails.
-    fn insert(&self, event: GameplayEvent) -> Result<()>;
+    /// Persist one event; returns once it is durable, or an error if validation or the write
+    /// fails.
+    async fn insert(&self, event: GameplayEvent) -> Result<()>;
     /// List events for a session, ordered by timestamp ascending.
-    fn list_by_session(&self, session_id: Uuid) -> Result<Vec<GameplayEvent>>;
+    async fn list_by_session(&self, session_id: Uuid) -> Result<Vec<GameplayEvent>>;
+    /// Events of every session in `session_ids` in one read, grouped by session and ordered by
+    /// timestamp within each.
+    async fn list_by_sessions(&self, session_ids: &[Uuid]) -> Result<Vec<GameplayEvent>>;
 }
 
 /// In-memory event store for tests and minimal scaffolding.
@@ -51,8 +58,9 @@ impl InMemoryEventStore {
     }
 }
 
+#[async_trait]
 impl EventStore for InMemoryEventStore {
-    fn insert(&self, event: GameplayEvent) -> Result<()> {
+    async fn insert(&self, event: GameplayEvent) -> Result<()> {
         if !validate_action_type(&event.action) {
             anyhow::bail!("invalid action type");
         }
@@ -60,18 +68,159 @@ impl EventStore for InMemoryEventStore {
         Ok(())
     }
 
-    fn list_by_session(&self, session_id: Uuid) -> Result<Vec<GameplayEvent>> {
+    async fn list_by_session(&self, session_id: Uuid) -> Result<Vec<GameplayEvent>> {
+        self.list_by_sessions(&[session_id]).await
+    }
+
+    async fn list_by_sessions(&self, session_ids: &[Uuid]) -> Result<Vec<GameplayEvent>> {
         let events = self.events.read().map_err(|e| anyhow::anyhow!("lock: {}", e))?;
-        let mut out: Vec<_> = events.iter().filter(|e| e.session_id == session_id).cloned().collect();
-        out.sort_by(|a, b| {
-            let ta = a.timestamp.map(|t| t.timestamp()).unwrap_or(0);
-            let tb = b.timestamp.map(|t| t.timestamp()).unwrap_or(0);
-            ta.cmp(&tb)
-        });
+        let mut out: Vec<_> = events.iter().filter(|e| session_ids.contains(&e.session_id)).cloned().collect();
+        // Stable, so events with equal (or no) timestamps keep their insertion order.
+        out.sort_by_key(|e| (e.session_id, e.timestamp));
         Ok(out)
     }
 }
 
+/// Most rows written by one INSERT (6 bind parameters each, well under Postgres' 65535).
+pub const EVENT_BATCH_MAX: usize = 500;
+
+/// Events the writer holds before `insert` waits for room.
+pub const EVENT_QUEUE_CAPACITY: usize = 4 * EVENT_BATCH_MAX;
+
+/// A queued event and where to report whether it was written.
+struct PendingEvent {
+    event: GameplayEvent,
+    written: oneshot::Sender<Result<(), String>>,
+}
+
+/// Postgres-backed event store over the `gameplay_events` table.
+///
+/// `insert` validates the event and queues it for a writer task, which drains the queue into
+/// multi-row INSERTs so events arriving while a write is in flight share the next one; the
+/// caller waits until its event's INSERT has committed (or failed). The queue is bounded: when
+/// it is full, callers wait for room. Reads go through the `(session_id, timestamp)` index.
+pub struct PostgresEventStore {
+    pool: sqlx::PgPool,
+    writer: mpsc::Sender<PendingEvent>,
+}
+
+impl PostgresEventStore {
+    /// Spawns the batching writer on the current Tokio runtime.
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        let (writer, rx) = mpsc::channel(EVENT_QUEUE_CAPACITY);
+        tokio::spawn(run_writer(pool.clone(), rx));
+        Self { pool, writer }
+    }
+}
+
+#[async_trait]
+impl EventStore for PostgresEventStore {
+    async fn insert(&self, mut event: GameplayEvent) -> Result<()> {
+        if !validate_action_type(&event.action) {
+            anyhow::bail!("invalid action type");
+        }
+        // Stamp now so queued events keep their arrival order.
+        event.timestamp.get_or_insert_with(Utc::now);
+        let (written, ack) = oneshot::channel();
+        self.writer
+            .send(PendingEvent { event, written })
+            .await
+            .map_err(|_| anyhow::anyhow!("event writer stopped"))?;
+        ack.await
+            .map_err(|_| anyhow::anyhow!("event writer stopped"))?
+            .map_err(|e| anyhow::anyhow!("failed to persist gameplay event: {e}"))
+    }
+
+    async fn list_by_session(&self, session_id: Uuid) -> Result<Vec<GameplayEvent>> {
+        self.list_by_sessions(&[session_id]).await
+    }
+
+    async fn list_by_sessions(&self, session_ids: &[Uuid]) -> Result<Vec<GameplayEvent>> {
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            event_id: Uuid,
+            session_id: Uuid,
+            action: JsonValue,
+            result: JsonValue,
+            timestamp: DateTime<Utc>,
+            reward: Option<f64>,
+        }
+
+        let rows: Vec<Row> = sqlx::query_as(
+            "SELECT event_id, session_id, action, result, timestamp, reward
+             FROM gameplay_events WHERE session_id = ANY($1)
+             ORDER BY session_id, timestamp ASC, event_id ASC",
+        )
+        .bind(session_ids)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| GameplayEvent {
+                event_id: r.event_id,
+                session_id: r.session_id,
+                action: r.action,
+                result: r.result,
+                timestamp: Some(r.timestamp),
+                reward: r.reward,
+            })
+            .collect())
+    }
+}
+
+async fn run_writer(pool: sqlx::PgPool, mut rx: mpsc::Receiver<PendingEvent>) {
+    let mut batch = Vec::new();
+    while let Some(first) = rx.recv().await {
+        batch.push(first);
+        while batch.len() < EVENT_BATCH_MAX {
+            match rx.try_recv() {
+                Ok(pending) => batch.push(pending),
+                Err(_) => break,
+            }
+        }
+        let events: Vec<GameplayEvent> = batch.iter().map(|p| p.event.clone()).collect();
+        let results = write_batch(&pool, &events).await;
+        for (pending, result) in batch.drain(..).zip(results) {
+            // The caller may have given up waiting; the event is written either way.
+            let _ = pending.written.send(result);
+        }
+    }
+}
+
+/// One multi-row INSERT; if it fails, retries row by row so one bad event doesn't fail the
+/// rest. Returns each event's outcome, in order.
+async fn write_batch(pool: &sqlx::PgPool, events: &[GameplayEvent]) -> Vec<Result<(), String>> {
+    match insert_rows(pool, events).await {
+        Ok(()) => events.iter().map(|_| Ok(())).collect(),
+        Err(e) => {
+            tracing::warn!(count = events.len(), error = %e, "batched event insert failed; retrying per event");
+            let mut results = Vec::with_capacity(events.len());
+            for event in events {
+                results.push(insert_rows(pool, std::slice::from_ref(event)).await.map_err(|e| e.to_string()));
+            }
+            results
+        }
+    }
+}
+
+async fn insert_rows(pool: &sqlx::PgPool, events: &[GameplayEvent]) -> Result<(), sqlx::Error> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+        "INSERT INTO gameplay_events (event_id, session_id, action, result, timestamp, reward) ",
+    );
+    query.push_values(events, |mut row, e| {
+        row.push_bind(e.event_id)
+            .push_bind(e.session_id)
+            .push_bind(&e.action)
+            .push_bind(&e.result)
+            .push_bind(e.timestamp.unwrap_or_else(Utc::now))
+            .push_bind(e.reward);
+    });
+    // Re-sent events (e.g. after a failed batch) are idempotent.
+    query.push(" ON CONFLICT (event_id) DO NOTHING");
+    query.build().execute(pool).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,8 +238,8 @@ mod tests {
         assert!(!validate_action_type(&serde_json::json!({ "type": "Invalid" })));
     }
 
-    #[test]
-    fn in_memory_insert_and_list() {
+    #[tokio::test]
+    async fn in_memory_insert_and_list() {
         let store = InMemoryEventStore::new();
         let sid = Uuid::new_v4();
         let e = GameplayEvent {
@@ -101,15 +250,59 @@ mod tests {
             timestamp: None,
             reward: None,
         };
-        store.insert(e.clone()).unwrap();
-        let list = store.list_by_session(sid).unwrap();
+        store.insert(e.clone()).await.unwrap();
+        store.insert(GameplayEvent { event_id: Uuid::new_v4(), session_id: Uuid::new_v4(), ..e.clone() }).await.unwrap();
+        let list = store.list_by_session(sid).await.unwrap();
         assert_eq!(list.len(), 1);
         assert_eq!(list[0].event_id, e.event_id);
+        let other = list_ids(&store, &[sid, Uuid::new_v4()]).await;
+        assert_eq!(other, vec![e.event_id]);
     }
 
-    #[test]
-    fn insert_invalid_action_fails() {
+    #[tokio::test]
+    async fn in_memory_list_orders_within_the_second() {
         let store = InMemoryEventStore::new();
+        let sid = Uuid::new_v4();
+        let t = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let event = |offset_ms: i64| GameplayEvent {
+            event_id: Uuid::new_v4(),
+            session_id: sid,
+            action: serde_json::json!({ "type": "Spin" }),
+            result: serde_json::json!({}),
+            timestamp: Some(t + chrono::Duration::milliseconds(offset_ms)),
+            reward: None,
+        };
+        let (late, early, tied) = (event(900), event(100), event(900));
+        for e in [&late, &early, &tied] {
+            store.insert(e.clone()).await.unwrap();
+        }
+        assert_eq!(list_ids(&store, &[sid]).await, vec![early.event_id, late.event_id, tied.event_id]);
+    }
+
+    async fn list_ids(store: &dyn EventStore, sessions: &[Uuid]) -> Vec<Uuid> {
+        store.list_by_sessions(sessions).await.unwrap().iter().map(|e| e.event_id).collect()
+    }
+
+    #[tokio::test]
+    async fn insert_invalid_action_fails() {
+        let store = InMemoryEventStore::new();
+        let e = GameplayEvent {
+            event_id: Uuid::new_v4(),
+            session_id: Uuid::new_v4(),
+            action: serde_json::json!({ "type": "Bad" }),
+            result: serde_json::json!({}),
+            timestamp: None,
+            reward: None,
+        };
+        assert!(store.insert(e).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn postgres_insert_validates_before_queueing() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let store = PostgresEventStore::new(pool);
         let e = GameplayEvent {
             event_id: Uuid::new_v4(),
             session_id: Uuid::new_v4(),
@@ -118,6 +311,24 @@ mod tests {
             timestamp: None,
             reward: None,
         };
-        assert!(store.insert(e).is_err());
+        assert!(store.insert(e).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn postgres_insert_reports_write_failures() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(200))
+            .connect_lazy("postgres://localhost:1/unreachable")
+            .unwrap();
+        let store = PostgresEventStore::new(pool);
+        let e = GameplayEvent {
+            event_id: Uuid::new_v4(),
+            session_id: Uuid::new_v4(),
+            action: serde_json::json!({ "type": "Spin" }),
+            result: serde_json::json!({}),
+            timestamp: None,
+            reward: None,
+        };
+        assert!(store.insert(e).await.is_err());
     }
 }
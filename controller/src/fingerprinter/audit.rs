@@ -0,0 +1,333 @@
+//! Fairness audit: goodness-of-fit of observed spins against a game's declared spec.
+//!
+//! Two families of targets are tested:
+//! - each visible reel position (row, reel): observed symbols vs the strip composition;
+//! - each payline: observed line outcome (no win, or the paytable entry hit) vs its exact probability.
+//!
+//! Every target gets a chi-square test (sparse bins pooled until expected ≥ `min_expected`) and a
+//! Kolmogorov–Smirnov test over the ordered categories. Targets pass at `significance / tests`
+//! (Bonferroni), so the overall verdict holds the family-wise false-alarm rate at `significance`.
+
+use super::stats::{chi_square_sf, ks_p_value};
+use super::SpinSample;
+use crate::slot_engine::{line_pay_probabilities, SlotMachine};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// Settings for `fairness_audit`.
+#[derive(Debug, Clone, Copy)]
+pub struct AuditConfig {
+    /// Family-wise significance level (default 0.01).
+    pub significance: f64,
+    /// Chi-square bins are pooled until each expects at least this many counts (default 5).
+    pub min_expected: f64,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            significance: 0.01,
+            min_expected: 5.0,
+        }
+    }
+}
+
+/// What a pair of tests was run against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AuditTarget {
+    /// Symbol shown at one visible cell.
+    ReelPosition { row: usize, reel: usize },
+    /// Outcome category of one payline.
+    Payline { index: usize },
+}
+
+/// One hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResult {
+    pub statistic: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degrees_of_freedom: Option<usize>,
+    pub p_value: f64,
+    /// `p_value` ≥ the report's adjusted significance.
+    pub passed: bool,
+}
+
+/// Chi-square and KS results for one target.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetAudit {
+    pub target: AuditTarget,
+    pub observations: u64,
+    pub chi_square: TestResult,
+    pub kolmogorov_smirnov: TestResult,
+}
+
+/// Fairness verdict for a game's observed spins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairnessReport {
+    pub game_id: Uuid,
+    /// Spins whose grid matched the machine's shape and were audited.
+    pub sample_size: u64,
+    /// Spins skipped because their symbols did not form a rows × reels grid.
+    pub skipped: u64,
+    pub significance: f64,
+    /// Per-test threshold: `significance` divided by the number of tests.
+    pub adjusted_significance: f64,
+    pub targets: Vec<TargetAudit>,
+    /// Every test passed.
+    pub passed: bool,
+}
+
+/// Tests observed spins against the probabilities implied by `machine`.
+pub fn fairness_audit(
+    game_id: Uuid,
+    machine: &SlotMachine,
+    samples: &[SpinSample],
+    config: &AuditConfig,
+) -> FairnessReport {
+    let rows = machine.rows();
+    let reels = machine.reels().len();
+    let grids: Vec<Vec<Vec<String>>> = samples
+        .iter()
+        .filter(|s| s.symbols.len() == rows * reels)
+        .map(|s| s.symbols.chunks(reels).map(<[String]>::to_vec).collect())
+        .collect();
+
+    let mut targets = Vec::new();
+    for row in 0..rows {
+        for reel in 0..reels {
+            let observed: Vec<&str> = grids.iter().map(|g| g[row][reel].as_str()).collect();
+            let (chi_square, kolmogorov_smirnov) =
+                symbol_tests(&machine.reels()[reel], &observed, config.min_expected);
+            targets.push(TargetAudit {
+                target: AuditTarget::ReelPosition { row, reel },
+                observations: observed.len() as u64,
+                chi_square,
+                kolmogorov_smirnov,
+            });
+        }
+    }
+
+    let wins: Vec<_> = grids.iter().map(|g| machine.evaluate_lines(g)).collect();
+    for index in 0..machine.paylines().len() {
+        // Category 0 is "no win"; the rest are paying entries by ascending multiplier.
+        let mut pays = line_pay_probabilities(machine, index);
+        pays.sort_by(|a, b| a.0.multiplier.total_cmp(&b.0.multiplier));
+        let mut expected = vec![(1.0 - pays.iter().map(|(_, p)| p).sum::<f64>()).max(0.0)];
+        expected.extend(pays.iter().map(|(_, p)| *p));
+        let mut observed = vec![0u64; expected.len()];
+        for spin in &wins {
+            let category = spin
+                .iter()
+                .find(|w| w.payline == index)
+                .and_then(|w| {
+                    pays.iter()
+                        .position(|(e, _)| e.symbol == w.symbol && e.count == w.count)
+                })
+                .map_or(0, |i| i + 1);
+            observed[category] += 1;
+        }
+        let (chi_square, kolmogorov_smirnov) =
+            categorical_tests(&observed, &expected, config.min_expected);
+        targets.push(TargetAudit {
+            target: AuditTarget::Payline { index },
+            observations: grids.len() as u64,
+            chi_square,
+            kolmogorov_smirnov,
+        });
+    }
+
+    let adjusted_significance = config.significance / (2 * targets.len()).max(1) as f64;
+    for t in &mut targets {
+        t.chi_square.passed = t.chi_square.p_value >= adjusted_significance;
+        t.kolmogorov_smirnov.passed = t.kolmogorov_smirnov.p_value >= adjusted_significance;
+    }
+    let passed = targets
+        .iter()
+        .all(|t| t.chi_square.passed && t.kolmogorov_smirnov.passed);
+
+    FairnessReport {
+        game_id,
+        sample_size: grids.len() as u64,
+        skipped: (samples.len() - grids.len()) as u64,
+        significance: config.significance,
+        adjusted_significance,
+        targets,
+        passed,
+    }
+}
+
+/// Symbols at one cell vs the strip composition. Categories are symbol names in sorted order
+/// (including observed symbols missing from the strip, which have probability 0).
+fn symbol_tests(strip: &[String], observed: &[&str], min_expected: f64) -> (TestResult, TestResult) {
+    let mut strip_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for s in strip {
+        *strip_counts.entry(s.as_str()).or_default() += 1;
+    }
+    let names: BTreeSet<&str> = strip_counts.keys().copied().chain(observed.iter().copied()).collect();
+    let expected: Vec<f64> = names
+        .iter()
+        .map(|n| strip_counts.get(n).copied().unwrap_or(0) as f64 / strip.len() as f64)
+        .collect();
+    let counts: Vec<u64> = names
+        .iter()
+        .map(|n| observed.iter().filter(|o| *o == n).count() as u64)
+        .collect();
+    categorical_tests(&counts, &expected, min_expected)
+}
+
+/// Chi-square (with pooling) and KS tests of category counts against probabilities.
+/// `passed` is left false; the caller sets it once the adjusted threshold is known.
+fn categorical_tests(observed: &[u64], expected: &[f64], min_expected: f64) -> (TestResult, TestResult) {
+    let n: u64 = observed.iter().sum();
+    let (mut cdf_obs, mut cdf_exp, mut d) = (0.0, 0.0, 0.0f64);
+    for (o, p) in observed.iter().zip(expected) {
+        cdf_obs += *o as f64 / n.max(1) as f64;
+        cdf_exp += p;
+        d = d.max((cdf_obs - cdf_exp).abs());
+    }
+    let ks = TestResult {
+        statistic: if n == 0 { 0.0 } else { d },
+        degrees_of_freedom: None,
+        p_value: ks_p_value(d, n),
+        passed: false,
+    };
+    (chi_square_test(observed, expected, n, min_expected), ks)
+}
+
+/// Pearson chi-square of category counts against probabilities, pooling sparse bins.
+pub(super) fn chi_square_test(
+    observed: &[u64],
+    expected: &[f64],
+    n: u64,
+    min_expected: f64,
+) -> TestResult {
+    // A symbol or outcome the spec makes impossible is conclusive on its own.
+    if observed.iter().zip(expected).any(|(o, p)| *o > 0 && *p <= 0.0) {
+        return TestResult {
+            statistic: f64::INFINITY,
+            degrees_of_freedom: None,
+            p_value: 0.0,
+            passed: false,
+        };
+    }
+    // Pool the sparsest categories together until every bin expects enough counts.
+    let mut cells: Vec<(f64, f64)> = observed
+        .iter()
+        .zip(expected)
+        .filter(|(_, p)| **p > 0.0)
+        .map(|(o, p)| (*o as f64, *p * n as f64))
+        .collect();
+    cells.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let mut bins: Vec<(f64, f64)> = Vec::new();
+    let mut acc = (0.0, 0.0);
+    for (o, e) in cells {
+        acc = (acc.0 + o, acc.1 + e);
+        if acc.1 >= min_expected {
+            bins.push(acc);
+            acc = (0.0, 0.0);
+        }
+    }
+    if acc.1 > 0.0 {
+        match bins.last_mut() {
+            Some(last) => *last = (last.0 + acc.0, last.1 + acc.1),
+            None => bins.push(acc),
+        }
+    }
+    let df = bins.len().saturating_sub(1);
+    let statistic: f64 = bins.iter().map(|(o, e)| (o - e).powi(2) / e).sum();
+    TestResult {
+        statistic: if df == 0 { 0.0 } else { statistic },
+        degrees_of_freedom: Some(df),
+        p_value: chi_square_sf(statistic, df),
+        passed: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slot_engine::{Payline, PaytableEntry, SlotRng};
+
+    fn samples(machine: &SlotMachine, spins: usize, seed: u64) -> Vec<SpinSample> {
+        let mut rng = SlotRng::seeded(seed);
+        (0..spins)
+            .map(|_| {
+                let out = machine.spin(&mut rng, 1.0);
+                SpinSample { symbols: out.grid.concat(), stake: 1.0, payout: out.payout }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn engine_output_passes_its_own_spec() {
+        let m = SlotMachine::classic();
+        let report = fairness_audit(Uuid::nil(), &m, &samples(&m, 20_000, 17), &AuditConfig::default());
+        assert_eq!(report.sample_size, 20_000);
+        assert_eq!(report.targets.len(), 9 + 5);
+        assert!(report.passed, "{report:#?}");
+    }
+
+    #[test]
+    fn skewed_strip_fails_audit() {
+        let spec = SlotMachine::classic();
+        // Same layout, but one Cherry swapped for a Seven on every reel.
+        let mut strip = spec.reels()[0].clone();
+        let cherry = strip.iter().position(|s| s == "Cherry").unwrap();
+        strip[cherry] = "Seven".into();
+        let vendor = SlotMachine::new(
+            vec![strip.clone(), strip.clone(), strip],
+            spec.rows(),
+            spec.paylines().to_vec(),
+            spec.paytable().to_vec(),
+        )
+        .unwrap();
+        let report = fairness_audit(Uuid::nil(), &spec, &samples(&vendor, 20_000, 17), &AuditConfig::default());
+        assert!(!report.passed);
+        let reel_failures = report
+            .targets
+            .iter()
+            .filter(|t| matches!(t.target, AuditTarget::ReelPosition { .. }) && !t.chi_square.passed)
+            .count();
+        assert_eq!(reel_failures, 9);
+    }
+
+    #[test]
+    fn impossible_symbol_is_conclusive_and_bad_grids_are_skipped() {
+        let m = SlotMachine::new(
+            vec![vec!["A".into(), "B".into()]],
+            1,
+            vec![Payline(vec![0])],
+            vec![PaytableEntry { symbol: "A".into(), count: 1, multiplier: 1.0 }],
+        )
+        .unwrap();
+        let spin = |s: &[&str]| SpinSample {
+            symbols: s.iter().map(|x| x.to_string()).collect(),
+            stake: 1.0,
+            payout: 0.0,
+        };
+        let obs = vec![spin(&["A"]), spin(&["Z"]), spin(&["A", "B"])];
+        let report = fairness_audit(Uuid::nil(), &m, &obs, &AuditConfig::default());
+        assert_eq!(report.sample_size, 2);
+        assert_eq!(report.skipped, 1);
+        let cell = &report.targets[0];
+        assert_eq!(cell.chi_square.p_value, 0.0);
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn chi_square_pools_sparse_bins() {
+        // Expected counts 1, 1, 98: the two sparse bins pool into one (still < 5) then merge.
+        let r = chi_square_test(&[1, 1, 98], &[0.01, 0.01, 0.98], 100, 5.0);
+        assert_eq!(r.degrees_of_freedom, Some(0));
+        assert_eq!(r.p_value, 1.0);
+        let r = chi_square_test(&[30, 70], &[0.5, 0.5], 100, 5.0);
+        assert_eq!(r.degrees_of_freedom, Some(1));
+        assert!((r.statistic - 16.0).abs() < 1e-12);
+        assert!(r.p_value < 1e-3);
+    }
+}
@@ -0,0 +1,174 @@
+//! Differences between two fingerprint versions of a game.
+//!
+//! Besides raw deltas of the symbol map and payout profile, the diff tests whether the two runs
+//! could come from the same game build: a two-sample z-test on RTP (each side's standard error
+//! from its profile) and a two-proportion z-test on hit frequency.
+
+use super::stats::normal_two_sided_p;
+use super::{GameFingerprint, StatisticalProfile, TestResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// One value in both versions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValueChange {
+    pub from: f64,
+    pub to: f64,
+    pub delta: f64,
+}
+
+impl ValueChange {
+    fn new(from: f64, to: f64) -> Self {
+        Self { from, to, delta: to - from }
+    }
+}
+
+/// Comparison of fingerprint versions `from_version` → `to_version`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintDiff {
+    pub game_id: Uuid,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub from_sample_size: u64,
+    pub to_sample_size: u64,
+    pub rng_signature_changed: bool,
+    /// Frequency change per symbol seen in either version (absent counts as 0).
+    pub symbol_map: BTreeMap<String, ValueChange>,
+    /// Change per payout profile metric.
+    pub profile: BTreeMap<String, ValueChange>,
+    /// Two-sample z-test of equal RTP.
+    pub rtp_shift: TestResult,
+    /// Two-proportion z-test of equal hit frequency.
+    pub hit_frequency_shift: TestResult,
+    pub significance: f64,
+    /// Neither shift test rejected at `significance`.
+    pub consistent: bool,
+}
+
+/// Compares two fingerprints of the same game; tests reject below `significance`.
+pub fn diff_fingerprints(from: &GameFingerprint, to: &GameFingerprint, significance: f64) -> FingerprintDiff {
+    let symbols = |fp: &GameFingerprint| -> BTreeMap<String, f64> {
+        serde_json::from_value(fp.symbol_map.clone()).unwrap_or_default()
+    };
+    let (a, b) = (symbols(from), symbols(to));
+    let names: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    let symbol_map = names
+        .into_iter()
+        .map(|n| {
+            let change = ValueChange::new(a.get(n).copied().unwrap_or(0.0), b.get(n).copied().unwrap_or(0.0));
+            (n.clone(), change)
+        })
+        .collect();
+
+    let profile = |fp: &GameFingerprint| -> StatisticalProfile {
+        serde_json::from_value(fp.statistical_profile.clone()).unwrap_or_default()
+    };
+    let (p, q) = (profile(from), profile(to));
+    let metrics = BTreeMap::from([
+        ("rtp_ratio".to_string(), ValueChange::new(p.rtp_ratio, q.rtp_ratio)),
+        ("volatility".to_string(), ValueChange::new(p.volatility, q.volatility)),
+        ("hit_frequency".to_string(), ValueChange::new(p.hit_frequency, q.hit_frequency)),
+        ("max_win_multiple".to_string(), ValueChange::new(p.max_win_multiple, q.max_win_multiple)),
+    ]);
+
+    let rtp_se = (p.rtp_std_error.powi(2) + q.rtp_std_error.powi(2)).sqrt();
+    let mut rtp_shift = z_test(q.rtp_ratio - p.rtp_ratio, rtp_se, p.sample_count, q.sample_count);
+    let (n1, n2) = (p.sample_count as f64, q.sample_count as f64);
+    let pooled = (p.hit_frequency * n1 + q.hit_frequency * n2) / (n1 + n2).max(1.0);
+    let hit_se = (pooled * (1.0 - pooled) * (1.0 / n1.max(1.0) + 1.0 / n2.max(1.0))).sqrt();
+    let mut hit_frequency_shift =
+        z_test(q.hit_frequency - p.hit_frequency, hit_se, p.sample_count, q.sample_count);
+    rtp_shift.passed = rtp_shift.p_value >= significance;
+    hit_frequency_shift.passed = hit_frequency_shift.p_value >= significance;
+
+    FingerprintDiff {
+        game_id: to.game_id,
+        from_version: from.version,
+        to_version: to.version,
+        from_sample_size: from.sample_size,
+        to_sample_size: to.sample_size,
+        rng_signature_changed: from.rng_signature != to.rng_signature,
+        symbol_map,
+        profile: metrics,
+        rtp_shift,
+        hit_frequency_shift,
+        significance,
+        consistent: rtp_shift.passed && hit_frequency_shift.passed,
+    }
+}
+
+/// z-test of `delta` against its standard error; a side with no staked spins is untestable.
+fn z_test(delta: f64, se: f64, n1: u64, n2: u64) -> TestResult {
+    let (statistic, p_value) = if n1 == 0 || n2 == 0 {
+        (0.0, 1.0)
+    } else if se > 0.0 {
+        let z = delta / se;
+        (z, normal_two_sided_p(z))
+    } else if delta == 0.0 {
+        (0.0, 1.0)
+    } else {
+        // Both sides are exact (no spread) yet differ.
+        (delta.signum() * f64::INFINITY, 0.0)
+    };
+    TestResult {
+        statistic,
+        degrees_of_freedom: None,
+        p_value,
+        passed: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprinter::{build_statistical_profile, ProfileConfig, SpinSample};
+    use serde_json::json;
+
+    fn fingerprint(version: u32, hits_per_ten: usize, spins: usize) -> GameFingerprint {
+        let samples: Vec<SpinSample> = (0..spins)
+            .map(|i| SpinSample {
+                symbols: vec![],
+                stake: 1.0,
+                payout: if i % 10 < hits_per_ten { 2.0 } else { 0.0 },
+            })
+            .collect();
+        let profile = build_statistical_profile(&samples, &ProfileConfig::default());
+        GameFingerprint {
+            game_id: Uuid::nil(),
+            rng_signature: format!("sig{hits_per_ten}"),
+            symbol_map: json!({"A": 0.5, "B": 0.5}),
+            statistical_profile: serde_json::to_value(profile).unwrap(),
+            randomness: serde_json::Value::Null,
+            run_id: Uuid::new_v4(),
+            version,
+            sample_size: spins as u64,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn same_build_is_consistent() {
+        let d = diff_fingerprints(&fingerprint(1, 4, 1_000), &fingerprint(2, 4, 2_000), 0.01);
+        assert_eq!((d.from_version, d.to_version), (1, 2));
+        assert!(d.consistent, "{d:#?}");
+        assert!(!d.rng_signature_changed);
+        assert_eq!(d.profile["rtp_ratio"].delta, 0.0);
+    }
+
+    #[test]
+    fn changed_payouts_are_flagged() {
+        let mut to = fingerprint(2, 5, 1_000);
+        to.symbol_map = json!({"A": 0.25, "C": 0.75});
+        let d = diff_fingerprints(&fingerprint(1, 4, 1_000), &to, 0.01);
+        assert!(!d.consistent);
+        assert!(!d.rtp_shift.passed && d.rtp_shift.statistic > 0.0);
+        assert!(!d.hit_frequency_shift.passed);
+        assert!(d.rng_signature_changed);
+        assert_eq!(d.symbol_map["A"].delta, -0.25);
+        assert_eq!(d.symbol_map["B"], ValueChange { from: 0.5, to: 0.0, delta: -0.5 });
+        assert_eq!(d.symbol_map["C"].from, 0.0);
+        assert!((d.profile["hit_frequency"].delta - 0.1).abs() < 1e-12);
+    }
+}
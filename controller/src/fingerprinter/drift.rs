@@ -0,0 +1,228 @@
+//! Drift detection: two-sided tabular CUSUM of live outcomes against a baseline fingerprint.
+//!
+//! Live staked spins are grouped into batches of `batch_size`; each monitored metric's batch
+//! mean is compared with the baseline mean μ and standard error σ = sd/√batch_size, where μ and
+//! sd come from the baseline's statistical profile. Batching keeps a single jackpot from tripping
+//! the detector on heavy-tailed returns.
+//!
+//! S⁺ = max(0, S⁺ + x − μ − kσ), S⁻ = max(0, S⁻ + μ − x − kσ); the monitor alarms the first time
+//! either exceeds hσ. The defaults k = 0.5, h = 5 detect a 1σ shift in about ten batches, with an
+//! in-control run length of several hundred batches.
+
+use super::{GameFingerprint, SpinSample, StatisticalProfile};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Settings for `detect_drift`.
+#[derive(Debug, Clone, Copy)]
+pub struct DriftConfig {
+    /// Spins per CUSUM observation (default 100).
+    pub batch_size: usize,
+    /// Allowance, in batch standard errors (default 0.5).
+    pub k: f64,
+    /// Decision threshold, in batch standard errors (default 5).
+    pub h: f64,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            k: 0.5,
+            h: 5.0,
+        }
+    }
+}
+
+/// Per-spin quantity a monitor tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftMetric {
+    /// payout / stake; baseline mean is the RTP and sd the volatility.
+    Return,
+    /// 1 if the spin paid anything; baseline mean is the hit frequency.
+    Hit,
+}
+
+/// Direction of a detected shift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftDirection {
+    Up,
+    Down,
+}
+
+/// CUSUM state for one metric after all live batches.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CusumMonitor {
+    pub metric: DriftMetric,
+    pub baseline_mean: f64,
+    /// Per-spin standard deviation.
+    pub baseline_sd: f64,
+    /// Alarm threshold hσ for the batch-mean CUSUM.
+    pub threshold: f64,
+    pub upper: f64,
+    pub lower: f64,
+    /// Live spins consumed when the first alarm fired.
+    pub alarm_at: Option<u64>,
+    pub direction: Option<DriftDirection>,
+}
+
+/// Drift verdict of live outcomes against one baseline version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub game_id: Uuid,
+    pub baseline_version: u32,
+    pub baseline_run_id: Uuid,
+    /// Staked live spins monitored (a trailing partial batch is not).
+    pub observations: u64,
+    pub batch_size: usize,
+    pub monitors: Vec<CusumMonitor>,
+    /// Some monitor alarmed.
+    pub drifted: bool,
+}
+
+/// Runs the CUSUM monitors over `live` spins, in the order played.
+pub fn detect_drift(baseline: &GameFingerprint, live: &[SpinSample], config: &DriftConfig) -> DriftReport {
+    let profile: StatisticalProfile =
+        serde_json::from_value(baseline.statistical_profile.clone()).unwrap_or_default();
+    let batch_size = config.batch_size.max(1);
+    let staked: Vec<&SpinSample> = live.iter().filter(|s| s.stake > 0.0).collect();
+    let batches: Vec<&[&SpinSample]> = staked.chunks_exact(batch_size).collect();
+
+    let hit_sd = (profile.hit_frequency * (1.0 - profile.hit_frequency)).sqrt();
+    let monitors = vec![
+        cusum(DriftMetric::Return, profile.rtp_ratio, profile.volatility, &batches, config, |s| {
+            s.payout / s.stake
+        }),
+        cusum(DriftMetric::Hit, profile.hit_frequency, hit_sd, &batches, config, |s| {
+            f64::from(u8::from(s.payout > 0.0))
+        }),
+    ];
+
+    DriftReport {
+        game_id: baseline.game_id,
+        baseline_version: baseline.version,
+        baseline_run_id: baseline.run_id,
+        observations: (batches.len() * batch_size) as u64,
+        batch_size,
+        drifted: monitors.iter().any(|m| m.alarm_at.is_some()),
+        monitors,
+    }
+}
+
+fn cusum(
+    metric: DriftMetric,
+    mean: f64,
+    sd: f64,
+    batches: &[&[&SpinSample]],
+    config: &DriftConfig,
+    value: impl Fn(&SpinSample) -> f64,
+) -> CusumMonitor {
+    let batch_size = config.batch_size.max(1);
+    let se = sd / (batch_size as f64).sqrt();
+    let (allowance, threshold) = (config.k * se, config.h * se);
+    let mut monitor = CusumMonitor {
+        metric,
+        baseline_mean: mean,
+        baseline_sd: sd,
+        threshold,
+        upper: 0.0,
+        lower: 0.0,
+        alarm_at: None,
+        direction: None,
+    };
+    for (i, batch) in batches.iter().enumerate() {
+        let x = batch.iter().map(|s| value(s)).sum::<f64>() / batch_size as f64;
+        monitor.upper = (monitor.upper + x - mean - allowance).max(0.0);
+        monitor.lower = (monitor.lower + mean - x - allowance).max(0.0);
+        if monitor.alarm_at.is_none() {
+            let direction = if monitor.upper > threshold {
+                Some(DriftDirection::Up)
+            } else if monitor.lower > threshold {
+                Some(DriftDirection::Down)
+            } else {
+                None
+            };
+            if direction.is_some() {
+                monitor.alarm_at = Some(((i + 1) * batch_size) as u64);
+                monitor.direction = direction;
+            }
+        }
+    }
+    monitor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprinter::{build_statistical_profile, ProfileConfig};
+    use crate::slot_engine::{SlotMachine, SlotRng};
+
+    fn spins(machine: &SlotMachine, n: usize, seed: u64) -> Vec<SpinSample> {
+        let mut rng = SlotRng::seeded(seed);
+        (0..n)
+            .map(|_| {
+                let out = machine.spin(&mut rng, 1.0);
+                SpinSample { symbols: out.grid.concat(), stake: 1.0, payout: out.payout }
+            })
+            .collect()
+    }
+
+    fn baseline(samples: &[SpinSample]) -> GameFingerprint {
+        let profile = build_statistical_profile(samples, &ProfileConfig::default());
+        GameFingerprint {
+            game_id: Uuid::nil(),
+            rng_signature: String::new(),
+            symbol_map: serde_json::json!({}),
+            statistical_profile: serde_json::to_value(profile).unwrap(),
+            randomness: serde_json::Value::Null,
+            run_id: Uuid::new_v4(),
+            version: 1,
+            sample_size: samples.len() as u64,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn same_build_does_not_drift() {
+        let m = SlotMachine::classic();
+        let base = baseline(&spins(&m, 50_000, 1));
+        let report = detect_drift(&base, &spins(&m, 20_000, 2), &DriftConfig::default());
+        assert_eq!(report.observations, 20_000);
+        assert_eq!(report.baseline_version, 1);
+        assert!(!report.drifted, "{report:#?}");
+    }
+
+    #[test]
+    fn lower_hit_rate_build_drifts_down() {
+        let m = SlotMachine::classic();
+        let base = baseline(&spins(&m, 50_000, 1));
+        // A build that voids every third win.
+        let live: Vec<SpinSample> = spins(&m, 20_000, 3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut s)| {
+                if i % 3 == 0 {
+                    s.payout = 0.0;
+                }
+                s
+            })
+            .collect();
+        let report = detect_drift(&base, &live, &DriftConfig::default());
+        assert!(report.drifted);
+        let hit = report.monitors.iter().find(|m| m.metric == DriftMetric::Hit).unwrap();
+        assert_eq!(hit.direction, Some(DriftDirection::Down));
+        assert!(hit.alarm_at.is_some_and(|at| at < 20_000));
+    }
+
+    #[test]
+    fn partial_batch_is_not_monitored() {
+        let m = SlotMachine::classic();
+        let base = baseline(&spins(&m, 1_000, 1));
+        let report = detect_drift(&base, &spins(&m, 150, 2), &DriftConfig::default());
+        assert_eq!(report.observations, 100);
+    }
+}
@@ -1,5 +1,8 @@
 //! Pure extraction logic: symbol frequencies, RNG signature, statistical profile.
 
+use super::stats::{mean_std, normal_quantile, sorted_quantile};
+use crate::slot_engine::SlotRng;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Symbol frequencies: symbol -> count.
@@ -40,7 +43,7 @@ pub fn rng_signature_digest(spin_symbols: &[Vec<String>], max_symbols: usize) ->
 }
 
 /// FNV-1a 64-bit hash: stable across builds, platforms, and Rust versions.
-fn fnv1a_64(data: &[u8]) -> u64 {
+pub(crate) fn fnv1a_64(data: &[u8]) -> u64 {
     const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
     const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
     let mut hash = OFFSET_BASIS;
@@ -51,36 +54,131 @@ fn fnv1a_64(data: &[u8]) -> u64 {
     hash
 }
 
-/// Statistical profile: RTP-like and volatility.
-#[derive(Debug, Clone, Default)]
+/// One observed Spin: visible symbols, the stake it was played for and its payout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpinSample {
+    pub symbols: Vec<String>,
+    pub stake: f64,
+    pub payout: f64,
+}
+
+/// Two-sided confidence interval for the observed RTP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    /// Nominal coverage, e.g. 0.95.
+    pub level: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Settings for `build_statistical_profile`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfileConfig {
+    /// Confidence level for both RTP intervals (default 0.95).
+    pub confidence: f64,
+    /// Bootstrap resamples for the percentile interval (default 1000).
+    pub bootstrap_resamples: u32,
+    /// Seed for bootstrap resampling, so a profile is reproducible from its sample.
+    pub bootstrap_seed: u64,
+}
+
+impl Default for ProfileConfig {
+    fn default() -> Self {
+        Self {
+            confidence: 0.95,
+            bootstrap_resamples: 1_000,
+            bootstrap_seed: 0x5eed_f00d,
+        }
+    }
+}
+
+/// Payout statistics for a fingerprint run. Returns are per unit stake; spins with no
+/// stake carry no return information and are excluded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct StatisticalProfile {
+    /// Staked spins the profile is computed from.
+    pub sample_count: u64,
+    /// Observed return-to-player: total payout / total stake.
     pub rtp_ratio: f64,
+    /// Standard deviation of the per-spin return (payout / stake).
     pub volatility: f64,
+    /// Standard error of `rtp_ratio` (delta method for a ratio of sums).
+    pub rtp_std_error: f64,
+    /// `rtp_ratio` ± z·standard error.
+    pub rtp_ci_normal: ConfidenceInterval,
+    /// Percentile interval over bootstrap resamples of the spins.
+    pub rtp_ci_bootstrap: ConfidenceInterval,
+    /// Share of staked spins that paid anything.
+    pub hit_frequency: f64,
+    /// Largest single-spin payout / stake.
+    pub max_win_multiple: f64,
 }
 
-/// Build profile from total stake and payout.
-pub fn build_statistical_profile(
-    frequencies: &HashMap<String, f64>,
-    total_stake: f64,
-    total_payout: f64,
-) -> StatisticalProfile {
-    let rtp_ratio = if total_stake > 0.0 {
-        (total_payout / total_stake).clamp(0.0, 10.0)
+/// Build the payout profile from observed spins.
+pub fn build_statistical_profile(spins: &[SpinSample], config: &ProfileConfig) -> StatisticalProfile {
+    let staked: Vec<&SpinSample> = spins.iter().filter(|s| s.stake > 0.0).collect();
+    if staked.is_empty() {
+        return StatisticalProfile::default();
+    }
+    let n = staked.len() as f64;
+    let total_stake: f64 = staked.iter().map(|s| s.stake).sum();
+    let total_payout: f64 = staked.iter().map(|s| s.payout).sum();
+    let rtp_ratio = total_payout / total_stake;
+
+    let returns: Vec<f64> = staked.iter().map(|s| s.payout / s.stake).collect();
+    let (_, volatility) = mean_std(&returns);
+
+    // Ratio estimator: Var(R) ≈ Σ(p_i − R·s_i)² / (n(n−1)) / mean(s)².
+    let rtp_std_error = if staked.len() > 1 {
+        let ss: f64 = staked
+            .iter()
+            .map(|s| (s.payout - rtp_ratio * s.stake).powi(2))
+            .sum();
+        (ss / (n * (n - 1.0))).sqrt() / (total_stake / n)
     } else {
         0.0
     };
-    let volatility = if frequencies.is_empty() {
-        0.0
+    let alpha = 1.0 - config.confidence;
+    let z = normal_quantile(1.0 - alpha / 2.0);
+    let rtp_ci_normal = ConfidenceInterval {
+        level: config.confidence,
+        lower: (rtp_ratio - z * rtp_std_error).max(0.0),
+        upper: rtp_ratio + z * rtp_std_error,
+    };
+
+    let mut rng = SlotRng::seeded(config.bootstrap_seed);
+    let mut resampled: Vec<f64> = (0..config.bootstrap_resamples)
+        .map(|_| {
+            let (mut stake, mut payout) = (0.0, 0.0);
+            for _ in 0..staked.len() {
+                let s = staked[rng.next_below(staked.len() as u64) as usize];
+                stake += s.stake;
+                payout += s.payout;
+            }
+            payout / stake
+        })
+        .collect();
+    resampled.sort_by(f64::total_cmp);
+    let rtp_ci_bootstrap = if resampled.is_empty() {
+        ConfidenceInterval { level: config.confidence, lower: rtp_ratio, upper: rtp_ratio }
     } else {
-        let mean = 1.0 / frequencies.len() as f64;
-        let variance: f64 = frequencies
-            .values()
-            .map(|p| (p - mean).powi(2))
-            .sum::<f64>()
-            / frequencies.len() as f64;
-        (variance.sqrt() * 10.0).min(1.0)
+        ConfidenceInterval {
+            level: config.confidence,
+            lower: sorted_quantile(&resampled, alpha / 2.0),
+            upper: sorted_quantile(&resampled, 1.0 - alpha / 2.0),
+        }
     };
-    StatisticalProfile { rtp_ratio, volatility }
+
+    StatisticalProfile {
+        sample_count: staked.len() as u64,
+        rtp_ratio,
+        volatility,
+        rtp_std_error,
+        rtp_ci_normal,
+        rtp_ci_bootstrap,
+        hit_frequency: staked.iter().filter(|s| s.payout > 0.0).count() as f64 / n,
+        max_win_multiple: returns.iter().copied().fold(0.0, f64::max),
+    }
 }
 
 #[cfg(test)]
@@ -98,12 +196,50 @@ mod tests {
         assert!((sum - 1.0).abs() < 1e-9);
     }
 
+    fn spin(stake: f64, payout: f64) -> SpinSample {
+        SpinSample { symbols: vec![], stake, payout }
+    }
+
     #[test]
-    fn statistical_profile_rtp_bounds() {
-        let f = HashMap::new();
-        let p = build_statistical_profile(&f, 100.0, 95.0);
-        assert!(p.rtp_ratio >= 0.0 && p.rtp_ratio <= 10.0);
+    fn statistical_profile_rtp_and_payout_figures() {
+        let spins = vec![spin(50.0, 0.0), spin(50.0, 95.0)];
+        let p = build_statistical_profile(&spins, &ProfileConfig::default());
+        assert_eq!(p.sample_count, 2);
         assert_eq!(p.rtp_ratio, 0.95);
+        assert_eq!(p.hit_frequency, 0.5);
+        assert_eq!(p.max_win_multiple, 1.9);
+        // Returns 0.0 and 1.9: sample sd = 1.9 / sqrt(2).
+        assert!((p.volatility - 1.9 / 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn statistical_profile_ignores_unstaked_spins_and_handles_empty() {
+        let p = build_statistical_profile(&[spin(0.0, 3.0)], &ProfileConfig::default());
+        assert_eq!(p, StatisticalProfile::default());
+    }
+
+    #[test]
+    fn confidence_intervals_cover_true_rtp_and_shrink_with_samples() {
+        // Pays 2x with probability 0.45: true RTP 0.9.
+        let mut rng = SlotRng::seeded(3);
+        let draw = |rng: &mut SlotRng, n: usize| -> Vec<SpinSample> {
+            (0..n)
+                .map(|_| spin(1.0, if rng.next_f64() < 0.45 { 2.0 } else { 0.0 }))
+                .collect()
+        };
+        let cfg = ProfileConfig::default();
+        let small = build_statistical_profile(&draw(&mut rng, 400), &cfg);
+        let large = build_statistical_profile(&draw(&mut rng, 20_000), &cfg);
+        for p in [&small, &large] {
+            for ci in [p.rtp_ci_normal, p.rtp_ci_bootstrap] {
+                assert!(ci.lower <= p.rtp_ratio && p.rtp_ratio <= ci.upper, "{ci:?}");
+                assert!(ci.lower < 0.9 && 0.9 < ci.upper, "{ci:?} should cover 0.9");
+            }
+        }
+        let width = |ci: ConfidenceInterval| ci.upper - ci.lower;
+        assert!(width(large.rtp_ci_normal) < width(small.rtp_ci_normal) / 5.0);
+        // Bootstrap and normal intervals agree closely for large samples.
+        assert!((width(large.rtp_ci_bootstrap) - width(large.rtp_ci_normal)).abs() < 0.005);
     }
 
     #[test]
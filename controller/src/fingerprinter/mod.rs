@@ -1,10 +1,30 @@
-//! Game fingerprint extraction: RNG signature, symbol map, statistical profile.
+//! Game fingerprint extraction: RNG signature, symbol map, statistical profile, randomness
+//! battery, and a fairness audit of observed spins against the declared game spec. Runs are
+//! kept as versions that can be diffed, and live outcomes are monitored for drift.
 
+mod audit;
+mod diff;
+mod drift;
 mod extraction;
+mod pipeline;
+mod randomness;
+pub(crate) mod stats;
 mod store;
 
+pub use audit::{
+    fairness_audit, AuditConfig, AuditTarget, FairnessReport, TargetAudit, TestResult,
+};
+pub use diff::{diff_fingerprints, FingerprintDiff, ValueChange};
+pub use drift::{detect_drift, CusumMonitor, DriftConfig, DriftDirection, DriftMetric, DriftReport};
 pub use extraction::{
     build_statistical_profile, extract_symbol_frequencies, rng_signature_digest,
-    symbol_counts, StatisticalProfile,
+    symbol_counts, ConfidenceInterval, ProfileConfig, SpinSample, StatisticalProfile,
+};
+pub(crate) use extraction::fnv1a_64;
+pub use randomness::{
+    randomness_battery, RandomnessConfig, RandomnessReport, StreamRandomness,
+};
+pub use pipeline::{
+    fingerprint_from_samples, spin_samples, timed_spin_samples, SIGNATURE_MAX_SYMBOLS,
 };
-pub use store::{FingerprintStore, GameFingerprint, InMemoryFingerprintStore};
+pub use store::{FingerprintStore, GameFingerprint, InMemoryFingerprintStore, PostgresFingerprintStore};
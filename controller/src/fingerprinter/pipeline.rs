@@ -0,0 +1,124 @@
+//! Fingerprint run: turn recorded gameplay events into a stored `GameFingerprint`.
+
+use super::{
+    build_statistical_profile, extract_symbol_frequencies, randomness_battery, rng_signature_digest,
+    GameFingerprint, ProfileConfig, RandomnessConfig, SpinSample,
+};
+use crate::api::{GameplayAction, GameplayActionType, GameplayResult};
+use crate::event_store::GameplayEvent;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Outcome symbols hashed into the RNG signature (prefix of the observed stream).
+pub const SIGNATURE_MAX_SYMBOLS: usize = 10_000;
+
+/// Extracts Spin samples from one session's events (in timestamp order).
+/// A Spin without an amount is staked at the session's most recent PlaceBet amount,
+/// matching how the server resolves it.
+pub fn spin_samples(events: &[GameplayEvent]) -> Vec<SpinSample> {
+    timed_spin_samples(events).into_iter().map(|(_, s)| s).collect()
+}
+
+/// `spin_samples` paired with each Spin event's timestamp, for merging sessions in time order.
+pub fn timed_spin_samples(events: &[GameplayEvent]) -> Vec<(Option<DateTime<Utc>>, SpinSample)> {
+    let mut last_bet = Decimal::ZERO;
+    let mut out = Vec::new();
+    for e in events {
+        let Ok(action) = serde_json::from_value::<GameplayAction>(e.action.clone()) else {
+            continue;
+        };
+        let amount = action.amount.as_ref().map(|m| m.amount);
+        match action.action_type {
+            GameplayActionType::PlaceBet => last_bet = amount.unwrap_or(last_bet),
+            GameplayActionType::Spin => {
+                let result: GameplayResult =
+                    serde_json::from_value(e.result.clone()).unwrap_or_default();
+                if result.voided {
+                    continue;
+                }
+                out.push((
+                    e.timestamp,
+                    SpinSample {
+                        symbols: result.symbols,
+                        stake: amount.unwrap_or(last_bet).to_f64().unwrap_or_default(),
+                        payout: result.payout.and_then(|m| m.amount.to_f64()).unwrap_or(0.0),
+                    },
+                ));
+            }
+            GameplayActionType::CashOut => {}
+        }
+    }
+    out
+}
+
+/// Builds a fingerprint for `game_id` from Spin samples; each call is a new run
+/// (its version is assigned when the store saves it).
+pub fn fingerprint_from_samples(game_id: Uuid, samples: &[SpinSample]) -> GameFingerprint {
+    let spins: Vec<Vec<String>> = samples.iter().map(|s| s.symbols.clone()).collect();
+    let frequencies = extract_symbol_frequencies(&spins);
+    let profile = build_statistical_profile(samples, &ProfileConfig::default());
+    let randomness = randomness_battery(samples, &RandomnessConfig::default());
+    GameFingerprint {
+        game_id,
+        rng_signature: rng_signature_digest(&spins, SIGNATURE_MAX_SYMBOLS),
+        symbol_map: serde_json::to_value(&frequencies).unwrap_or_default(),
+        statistical_profile: serde_json::to_value(&profile).unwrap_or_default(),
+        randomness: serde_json::to_value(&randomness).unwrap_or_default(),
+        run_id: Uuid::new_v4(),
+        version: 0,
+        sample_size: samples.len() as u64,
+        created_at: Some(chrono::Utc::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(action: serde_json::Value, result: serde_json::Value) -> GameplayEvent {
+        GameplayEvent {
+            event_id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            action,
+            result,
+            timestamp: None,
+            reward: None,
+        }
+    }
+
+    #[test]
+    fn spin_inherits_last_bet_amount() {
+        let events = vec![
+            event(json!({"type": "PlaceBet", "amount": {"amount": 2.0, "currency": "AUD"}}), json!({"symbols": []})),
+            event(
+                json!({"type": "Spin"}),
+                json!({"symbols": ["A", "B"], "payout": {"amount": 5.0, "currency": "AUD"}}),
+            ),
+            event(json!({"type": "CashOut"}), json!({"symbols": []})),
+        ];
+        let samples = spin_samples(&events);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].stake, 2.0);
+        assert_eq!(samples[0].payout, 5.0);
+        assert_eq!(samples[0].symbols, vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn fingerprint_records_sample_size_and_rtp() {
+        let samples = vec![
+            SpinSample { symbols: vec!["A".into(), "A".into()], stake: 1.0, payout: 0.0 },
+            SpinSample { symbols: vec!["A".into(), "B".into()], stake: 1.0, payout: 1.5 },
+        ];
+        let game_id = Uuid::new_v4();
+        let fp = fingerprint_from_samples(game_id, &samples);
+        assert_eq!(fp.game_id, game_id);
+        assert_eq!(fp.sample_size, 2);
+        assert_eq!(fp.symbol_map["A"], 0.75);
+        assert_eq!(fp.statistical_profile["rtp_ratio"], 0.75);
+        assert_eq!(fp.randomness["streams"].as_array().map(Vec::len), Some(2));
+        assert_ne!(fp.run_id, fingerprint_from_samples(game_id, &samples).run_id);
+    }
+}
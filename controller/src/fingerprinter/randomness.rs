@@ -0,0 +1,410 @@
+//! Randomness battery over recorded outcome streams.
+//!
+//! Every visible cell (an index into a spin's flattened symbols) yields one categorical stream:
+//! the symbol it showed on successive spins. A sound RNG draws each spin independently, so every
+//! stream is i.i.d. whatever the strip layout, and the battery needs no game definition:
+//! - runs: number of maximal same-symbol runs (Wald–Wolfowitz, generalised to k symbols);
+//! - serial correlation: lag-1 correlation of the centred one-hot symbol vectors;
+//! - gap: gaps between occurrences of the most frequent symbol vs the geometric law;
+//! - poker: distinct symbols per hand of `poker_hand` outcomes vs the multinomial law;
+//! - entropy: G-test of lag-1 mutual information (order-0 vs order-1 Markov chain).
+//!
+//! Symbol probabilities are estimated from each stream. Tests pass at `significance / tests`
+//! (Bonferroni), as in the fairness audit.
+
+use super::audit::chi_square_test;
+use super::stats::{chi_square_sf, normal_two_sided_p};
+use super::{SpinSample, TestResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tests run per stream.
+const TESTS_PER_STREAM: usize = 5;
+
+/// Settings for `randomness_battery`.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomnessConfig {
+    /// Family-wise significance level (default 0.01).
+    pub significance: f64,
+    /// Chi-square bins are pooled until each expects at least this many counts (default 5).
+    pub min_expected: f64,
+    /// Outcomes per poker-test hand (default 5).
+    pub poker_hand: usize,
+}
+
+impl Default for RandomnessConfig {
+    fn default() -> Self {
+        Self {
+            significance: 0.01,
+            min_expected: 5.0,
+            poker_hand: 5,
+        }
+    }
+}
+
+/// Battery results for one cell's stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamRandomness {
+    /// Index into each spin's flattened symbols.
+    pub position: usize,
+    pub length: u64,
+    /// Distinct symbols observed.
+    pub categories: usize,
+    /// Shannon entropy of the observed symbol frequencies, in bits.
+    pub entropy_bits: f64,
+    /// Entropy of a symbol given its predecessor, in bits; ≈ `entropy_bits` for an i.i.d. stream.
+    pub conditional_entropy_bits: f64,
+    /// Statistic is the z-score of the run count.
+    pub runs: TestResult,
+    /// Statistic is the lag-1 correlation coefficient.
+    pub serial_correlation: TestResult,
+    pub gap: TestResult,
+    pub poker: TestResult,
+    /// Statistic is the G (log-likelihood ratio) of lag-1 independence.
+    pub entropy: TestResult,
+}
+
+/// Randomness verdict for a game's observed outcome streams.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomnessReport {
+    /// Spins with the most common symbol count; their cells form the streams.
+    pub sample_size: u64,
+    /// Spins skipped because their symbol count differed.
+    pub skipped: u64,
+    pub significance: f64,
+    /// Per-test threshold: `significance` divided by the number of tests.
+    pub adjusted_significance: f64,
+    pub streams: Vec<StreamRandomness>,
+    /// Every test passed.
+    pub passed: bool,
+}
+
+/// Runs the battery on each cell's symbol stream, in spin order.
+pub fn randomness_battery(samples: &[SpinSample], config: &RandomnessConfig) -> RandomnessReport {
+    let mut lengths: BTreeMap<usize, u64> = BTreeMap::new();
+    for s in samples {
+        *lengths.entry(s.symbols.len()).or_default() += 1;
+    }
+    let (cells, sample_size) = lengths
+        .into_iter()
+        .max_by_key(|&(len, count)| (count, len))
+        .unwrap_or((0, 0));
+    let spins: Vec<&SpinSample> = samples.iter().filter(|s| s.symbols.len() == cells).collect();
+
+    let mut streams: Vec<StreamRandomness> = (0..cells)
+        .map(|position| {
+            let stream: Vec<&str> = spins.iter().map(|s| s.symbols[position].as_str()).collect();
+            stream_battery(position, &stream, config)
+        })
+        .collect();
+
+    let adjusted_significance =
+        config.significance / (TESTS_PER_STREAM * streams.len()).max(1) as f64;
+    for s in &mut streams {
+        for t in [
+            &mut s.runs,
+            &mut s.serial_correlation,
+            &mut s.gap,
+            &mut s.poker,
+            &mut s.entropy,
+        ] {
+            t.passed = t.p_value >= adjusted_significance;
+        }
+    }
+    let passed = streams.iter().all(|s| {
+        [s.runs, s.serial_correlation, s.gap, s.poker, s.entropy]
+            .iter()
+            .all(|t| t.passed)
+    });
+
+    RandomnessReport {
+        sample_size,
+        skipped: samples.len() as u64 - sample_size,
+        significance: config.significance,
+        adjusted_significance,
+        streams,
+        passed,
+    }
+}
+
+fn stream_battery(position: usize, stream: &[&str], config: &RandomnessConfig) -> StreamRandomness {
+    let names: BTreeSet<&str> = stream.iter().copied().collect();
+    let index: BTreeMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let xs: Vec<usize> = stream.iter().map(|s| index[s]).collect();
+    let mut counts = vec![0u64; names.len()];
+    for &x in &xs {
+        counts[x] += 1;
+    }
+    let (entropy, conditional_entropy_bits) = entropy_test(&xs, counts.len());
+    StreamRandomness {
+        position,
+        length: xs.len() as u64,
+        categories: counts.len(),
+        entropy_bits: entropy_bits(&counts),
+        conditional_entropy_bits,
+        runs: runs_test(&xs, &counts),
+        serial_correlation: serial_correlation_test(&xs, &counts),
+        gap: gap_test(&xs, &counts, config.min_expected),
+        poker: poker_test(&xs, &counts, config.poker_hand, config.min_expected),
+        entropy,
+    }
+}
+
+/// A constant or too-short stream gives no evidence either way.
+fn untestable() -> TestResult {
+    TestResult {
+        statistic: 0.0,
+        degrees_of_freedom: None,
+        p_value: 1.0,
+        passed: false,
+    }
+}
+
+fn entropy_bits(counts: &[u64]) -> f64 {
+    let n: u64 = counts.iter().sum();
+    counts
+        .iter()
+        .filter(|c| **c > 0)
+        .map(|&c| {
+            let p = c as f64 / n as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn runs_test(xs: &[usize], counts: &[u64]) -> TestResult {
+    if xs.len() < 3 || counts.len() < 2 {
+        return untestable();
+    }
+    let n = xs.len() as f64;
+    let runs = (1 + xs.windows(2).filter(|w| w[0] != w[1]).count()) as f64;
+    let s2: f64 = counts.iter().map(|&c| (c as f64).powi(2)).sum();
+    let s3: f64 = counts.iter().map(|&c| (c as f64).powi(3)).sum();
+    let mean = 1.0 + (n * n - s2) / n;
+    let var = (s2 * (s2 + n * (n + 1.0)) - 2.0 * n * s3 - n.powi(3)) / (n * n * (n - 1.0));
+    if var <= 0.0 {
+        return untestable();
+    }
+    let z = (runs - mean) / var.sqrt();
+    TestResult {
+        statistic: z,
+        degrees_of_freedom: None,
+        p_value: normal_two_sided_p(z),
+        passed: false,
+    }
+}
+
+/// With u_t = onehot(x_t) − p, S = Σ ⟨u_t, u_{t+1}⟩ has mean 0 and variance (n−1)·tr(Σ²) under
+/// independence, where Σ = diag(p) − ppᵀ.
+fn serial_correlation_test(xs: &[usize], counts: &[u64]) -> TestResult {
+    if xs.len() < 3 || counts.len() < 2 {
+        return untestable();
+    }
+    let n = xs.len() as f64;
+    let p: Vec<f64> = counts.iter().map(|&c| c as f64 / n).collect();
+    let s2: f64 = p.iter().map(|q| q * q).sum();
+    let s3: f64 = p.iter().map(|q| q.powi(3)).sum();
+    let lagged: f64 = xs
+        .windows(2)
+        .map(|w| f64::from(u8::from(w[0] == w[1])) - p[w[0]] - p[w[1]] + s2)
+        .sum();
+    let r = lagged / (n * (1.0 - s2));
+    let z = lagged / ((n - 1.0) * (s2 - 2.0 * s3 + s2 * s2)).sqrt();
+    TestResult {
+        statistic: r,
+        degrees_of_freedom: None,
+        p_value: normal_two_sided_p(z),
+        passed: false,
+    }
+}
+
+/// Gap lengths j between occurrences of the most frequent symbol (probability p) should
+/// follow P(j) = p(1−p)^j; bins run until the tail expects fewer than `min_expected`.
+fn gap_test(xs: &[usize], counts: &[u64], min_expected: f64) -> TestResult {
+    let Some((target, &hits)) = counts.iter().enumerate().max_by_key(|&(i, c)| (*c, usize::MAX - i))
+    else {
+        return untestable();
+    };
+    if counts.len() < 2 || hits < 3 {
+        return untestable();
+    }
+    let p = hits as f64 / xs.len() as f64;
+    let positions: Vec<usize> = (0..xs.len()).filter(|&i| xs[i] == target).collect();
+    let gaps: Vec<usize> = positions.windows(2).map(|w| w[1] - w[0] - 1).collect();
+    let n = gaps.len() as u64;
+
+    let mut expected = Vec::new();
+    let mut tail = 1.0;
+    while tail * n as f64 >= min_expected && expected.len() < 64 {
+        expected.push(tail * p);
+        tail *= 1.0 - p;
+    }
+    expected.push(tail);
+    let last = expected.len() - 1;
+    let mut observed = vec![0u64; expected.len()];
+    for g in gaps {
+        observed[g.min(last)] += 1;
+    }
+    chi_square_test(&observed, &expected, n, min_expected)
+}
+
+/// Distinct symbols per non-overlapping hand vs their exact probability under the observed
+/// symbol frequencies.
+fn poker_test(xs: &[usize], counts: &[u64], hand: usize, min_expected: f64) -> TestResult {
+    let hands = xs.len() / hand.max(1);
+    if hand < 2 || hands == 0 || counts.len() < 2 {
+        return untestable();
+    }
+    let p: Vec<f64> = counts.iter().map(|&c| c as f64 / xs.len() as f64).collect();
+    let expected = distinct_count_probabilities(&p, hand);
+    let mut observed = vec![0u64; hand];
+    for h in xs.chunks_exact(hand) {
+        let distinct: BTreeSet<usize> = h.iter().copied().collect();
+        observed[distinct.len() - 1] += 1;
+    }
+    chi_square_test(&observed, &expected, hands as u64, min_expected)
+}
+
+/// P(exactly d distinct symbols in m draws), d = 1..=m, for symbol probabilities `p`.
+/// Dynamic programme over symbols of Σ Π p_i^{c_i} / c_i! with Σ c_i = m, then × m!.
+fn distinct_count_probabilities(p: &[f64], m: usize) -> Vec<f64> {
+    // dp[j][d]: j draws placed so far, touching d symbols.
+    let mut dp = vec![vec![0.0; m + 1]; m + 1];
+    dp[0][0] = 1.0;
+    for &q in p {
+        let mut next = dp.clone();
+        for j in 0..m {
+            for d in 0..m {
+                let v = dp[j][d];
+                if v == 0.0 {
+                    continue;
+                }
+                let mut term = 1.0;
+                for c in 1..=m - j {
+                    term *= q / c as f64;
+                    next[j + c][d + 1] += v * term;
+                }
+            }
+        }
+        dp = next;
+    }
+    let factorial: f64 = (1..=m).map(|i| i as f64).product();
+    (1..=m).map(|d| factorial * dp[m][d]).collect()
+}
+
+/// G-test of independence between consecutive symbols over overlapping transitions; returns
+/// the test and the conditional entropy H(X_{t+1} | X_t) in bits.
+fn entropy_test(xs: &[usize], k: usize) -> (TestResult, f64) {
+    if xs.len() < 3 || k < 2 {
+        return (untestable(), 0.0);
+    }
+    let mut pairs = vec![vec![0u64; k]; k];
+    for w in xs.windows(2) {
+        pairs[w[0]][w[1]] += 1;
+    }
+    let total = (xs.len() - 1) as f64;
+    let rows: Vec<u64> = pairs.iter().map(|r| r.iter().sum()).collect();
+    let cols: Vec<u64> = (0..k).map(|b| pairs.iter().map(|r| r[b]).sum()).collect();
+    let mut g = 0.0;
+    let mut conditional = 0.0;
+    for (a, row) in pairs.iter().enumerate() {
+        for (b, &n_ab) in row.iter().enumerate() {
+            if n_ab == 0 {
+                continue;
+            }
+            let n_ab = n_ab as f64;
+            g += n_ab * (n_ab * total / (rows[a] as f64 * cols[b] as f64)).ln();
+            conditional -= n_ab / total * (n_ab / rows[a] as f64).log2();
+        }
+    }
+    g *= 2.0;
+    let live = |v: &[u64]| v.iter().filter(|c| **c > 0).count();
+    let df = live(&rows).saturating_sub(1) * live(&cols).saturating_sub(1);
+    let test = TestResult {
+        statistic: g,
+        degrees_of_freedom: Some(df),
+        p_value: chi_square_sf(g, df),
+        passed: false,
+    };
+    (test, conditional)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slot_engine::{SlotMachine, SlotRng};
+
+    fn stream_samples(stream: &[&str]) -> Vec<SpinSample> {
+        stream
+            .iter()
+            .map(|s| SpinSample { symbols: vec![s.to_string()], stake: 1.0, payout: 0.0 })
+            .collect()
+    }
+
+    #[test]
+    fn engine_output_passes_battery() {
+        let m = SlotMachine::classic();
+        let mut rng = SlotRng::seeded(23);
+        let samples: Vec<SpinSample> = (0..5_000)
+            .map(|_| SpinSample { symbols: m.spin(&mut rng, 1.0).grid.concat(), stake: 1.0, payout: 0.0 })
+            .collect();
+        let report = randomness_battery(&samples, &RandomnessConfig::default());
+        assert_eq!(report.sample_size, 5_000);
+        assert_eq!(report.streams.len(), 9);
+        assert!(report.passed, "{report:#?}");
+        let s = &report.streams[0];
+        assert!((s.entropy_bits - s.conditional_entropy_bits).abs() < 0.05);
+    }
+
+    #[test]
+    fn alternating_stream_fails_runs_and_serial_correlation() {
+        let stream: Vec<&str> = (0..1_000).map(|i| if i % 2 == 0 { "A" } else { "B" }).collect();
+        let report = randomness_battery(&stream_samples(&stream), &RandomnessConfig::default());
+        let s = &report.streams[0];
+        assert!(!report.passed);
+        assert!(!s.runs.passed && s.runs.statistic > 0.0);
+        assert!(!s.serial_correlation.passed && s.serial_correlation.statistic < -0.9);
+        assert!(!s.entropy.passed);
+        assert!(s.conditional_entropy_bits < 1e-9);
+    }
+
+    #[test]
+    fn sticky_rng_fails_runs_gap_and_poker() {
+        // Each draw repeated three times: right frequencies, wrong sequence.
+        let mut rng = SlotRng::seeded(5);
+        let symbols = ["A", "B", "C", "D"];
+        let stream: Vec<&str> = (0..1_000)
+            .flat_map(|_| [symbols[rng.next_below(4) as usize]; 3])
+            .collect();
+        let report = randomness_battery(&stream_samples(&stream), &RandomnessConfig::default());
+        let s = &report.streams[0];
+        assert!(!s.runs.passed && s.runs.statistic < 0.0);
+        assert!(!s.gap.passed);
+        assert!(!s.poker.passed);
+        assert!((s.entropy_bits - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn distinct_count_probabilities_sum_to_one() {
+        let uniform = distinct_count_probabilities(&[0.5, 0.5], 2);
+        assert!((uniform[0] - 0.5).abs() < 1e-12 && (uniform[1] - 0.5).abs() < 1e-12);
+        let skewed = distinct_count_probabilities(&[0.7, 0.2, 0.1], 5);
+        assert!((skewed.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((skewed[0] - (0.7f64.powi(5) + 0.2f64.powi(5) + 0.1f64.powi(5))).abs() < 1e-12);
+        assert_eq!(skewed[3], 0.0);
+    }
+
+    #[test]
+    fn odd_sized_spins_are_skipped_and_empty_input_passes() {
+        let mut samples = stream_samples(&["A", "B", "A"]);
+        samples.push(SpinSample { symbols: vec!["A".into(), "B".into()], stake: 1.0, payout: 0.0 });
+        let report = randomness_battery(&samples, &RandomnessConfig::default());
+        assert_eq!((report.sample_size, report.skipped), (3, 1));
+        assert_eq!(report.streams.len(), 1);
+
+        let empty = randomness_battery(&[], &RandomnessConfig::default());
+        assert!(empty.passed && empty.streams.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
-//! Fingerprint storage and lookup.
+//! Fingerprint storage and lookup: in memory, or in the `game_fingerprints` table.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::sync::{Arc, RwLock};
@@ -13,18 +14,38 @@ pub struct GameFingerprint {
     pub rng_signature: String,
     pub symbol_map: JsonValue,
     pub statistical_profile: JsonValue,
+    /// `RandomnessReport` of the run's outcome streams.
+    #[serde(default)]
+    pub randomness: JsonValue,
+    /// Fingerprint run that produced this record.
+    #[serde(default)]
+    pub run_id: Uuid,
+    /// 1-based position among the game's runs; assigned by the store on save.
+    #[serde(default)]
+    pub version: u32,
+    /// Number of Spin outcomes the run observed.
+    #[serde(default)]
+    pub sample_size: u64,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-/// Store and retrieve fingerprints by game_id.
+/// Store and retrieve fingerprints by game_id. Every run is kept as a new version.
+#[async_trait::async_trait]
 pub trait FingerprintStore: Send + Sync {
-    fn get(&self, game_id: Uuid) -> Result<Option<GameFingerprint>>;
-    fn save(&self, fp: GameFingerprint) -> Result<()>;
+    /// Latest version for the game.
+    async fn get(&self, game_id: Uuid) -> Result<Option<GameFingerprint>>;
+    /// Appends `fp` as the game's next version and returns the stored record.
+    async fn save(&self, fp: GameFingerprint) -> Result<GameFingerprint>;
+    async fn get_version(&self, game_id: Uuid, version: u32) -> Result<Option<GameFingerprint>>;
+    /// All versions, oldest first.
+    async fn list_versions(&self, game_id: Uuid) -> Result<Vec<GameFingerprint>>;
 }
 
 /// In-memory fingerprint store for tests and minimal scaffolding.
 #[derive(Default)]
 pub struct InMemoryFingerprintStore {
-    store: Arc<RwLock<std::collections::HashMap<Uuid, GameFingerprint>>>,
+    store: Arc<RwLock<std::collections::HashMap<Uuid, Vec<GameFingerprint>>>>,
 }
 
 impl InMemoryFingerprintStore {
@@ -33,37 +54,211 @@ impl InMemoryFingerprintStore {
     }
 }
 
+#[async_trait::async_trait]
 impl FingerprintStore for InMemoryFingerprintStore {
-    fn get(&self, game_id: Uuid) -> Result<Option<GameFingerprint>> {
+    async fn get(&self, game_id: Uuid) -> Result<Option<GameFingerprint>> {
         let g = self.store.read().map_err(|e| anyhow::anyhow!("lock: {}", e))?;
-        Ok(g.get(&game_id).cloned())
+        Ok(g.get(&game_id).and_then(|v| v.last()).cloned())
     }
 
-    fn save(&self, fp: GameFingerprint) -> Result<()> {
-        self.store
-            .write()
-            .map_err(|e| anyhow::anyhow!("lock: {}", e))?
-            .insert(fp.game_id, fp);
-        Ok(())
+    async fn save(&self, mut fp: GameFingerprint) -> Result<GameFingerprint> {
+        let mut g = self.store.write().map_err(|e| anyhow::anyhow!("lock: {}", e))?;
+        let versions = g.entry(fp.game_id).or_default();
+        fp.version = versions.len() as u32 + 1;
+        versions.push(fp.clone());
+        Ok(fp)
+    }
+
+    async fn get_version(&self, game_id: Uuid, version: u32) -> Result<Option<GameFingerprint>> {
+        let g = self.store.read().map_err(|e| anyhow::anyhow!("lock: {}", e))?;
+        Ok(g.get(&game_id)
+            .and_then(|v| v.iter().find(|fp| fp.version == version))
+            .cloned())
+    }
+
+    async fn list_versions(&self, game_id: Uuid) -> Result<Vec<GameFingerprint>> {
+        let g = self.store.read().map_err(|e| anyhow::anyhow!("lock: {}", e))?;
+        Ok(g.get(&game_id).cloned().unwrap_or_default())
+    }
+}
+
+/// Postgres-backed fingerprint store over the `game_fingerprints` table. Fingerprinted games
+/// must be registered in `games`.
+pub struct PostgresFingerprintStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresFingerprintStore {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct FingerprintRow {
+    game_id: Uuid,
+    version: i32,
+    run_id: Uuid,
+    rng_signature: String,
+    symbol_map: JsonValue,
+    statistical_profile: JsonValue,
+    randomness: JsonValue,
+    sample_size: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl From<FingerprintRow> for GameFingerprint {
+    fn from(r: FingerprintRow) -> Self {
+        GameFingerprint {
+            game_id: r.game_id,
+            rng_signature: r.rng_signature,
+            symbol_map: r.symbol_map,
+            statistical_profile: r.statistical_profile,
+            randomness: r.randomness,
+            run_id: r.run_id,
+            version: r.version as u32,
+            sample_size: r.sample_size as u64,
+            created_at: Some(r.created_at),
+        }
+    }
+}
+
+const FINGERPRINT_COLUMNS: &str = "game_id, version, run_id, rng_signature, symbol_map, \
+     statistical_profile, randomness, sample_size, created_at";
+
+#[async_trait::async_trait]
+impl FingerprintStore for PostgresFingerprintStore {
+    async fn get(&self, game_id: Uuid) -> Result<Option<GameFingerprint>> {
+        let row: Option<FingerprintRow> = sqlx::query_as(&format!(
+            "SELECT {FINGERPRINT_COLUMNS} FROM game_fingerprints
+             WHERE game_id = $1 ORDER BY version DESC LIMIT 1"
+        ))
+        .bind(game_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(GameFingerprint::from))
+    }
+
+    /// Locks the game's row while numbering the run, so concurrent runs get consecutive versions.
+    async fn save(&self, fp: GameFingerprint) -> Result<GameFingerprint> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT game_id FROM games WHERE game_id = $1 FOR UPDATE")
+            .bind(fp.game_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("game {} is not registered", fp.game_id))?;
+        let row: FingerprintRow = sqlx::query_as(&format!(
+            "INSERT INTO game_fingerprints (game_id, version, run_id, rng_signature, symbol_map,
+                 statistical_profile, randomness, sample_size, created_at)
+             SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, $4, $5, $6, $7, COALESCE($8, NOW())
+             FROM game_fingerprints WHERE game_id = $1
+             RETURNING {FINGERPRINT_COLUMNS}"
+        ))
+        .bind(fp.game_id)
+        .bind(fp.run_id)
+        .bind(&fp.rng_signature)
+        .bind(&fp.symbol_map)
+        .bind(&fp.statistical_profile)
+        .bind(&fp.randomness)
+        .bind(i64::try_from(fp.sample_size)?)
+        .bind(fp.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row.into())
+    }
+
+    async fn get_version(&self, game_id: Uuid, version: u32) -> Result<Option<GameFingerprint>> {
+        let Ok(version) = i32::try_from(version) else { return Ok(None) };
+        let row: Option<FingerprintRow> = sqlx::query_as(&format!(
+            "SELECT {FINGERPRINT_COLUMNS} FROM game_fingerprints WHERE game_id = $1 AND version = $2"
+        ))
+        .bind(game_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(GameFingerprint::from))
+    }
+
+    async fn list_versions(&self, game_id: Uuid) -> Result<Vec<GameFingerprint>> {
+        let rows: Vec<FingerprintRow> = sqlx::query_as(&format!(
+            "SELECT {FINGERPRINT_COLUMNS} FROM game_fingerprints WHERE game_id = $1 ORDER BY version ASC"
+        ))
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(GameFingerprint::from).collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app_state::GameRepository;
+    use crate::persistence_metrics::PostgresGameStore;
+    use crate::test_support::{test_game, test_pool};
 
-    #[test]
-    fn save_and_get_fingerprint() {
-        let s = InMemoryFingerprintStore::new();
-        let id = Uuid::new_v4();
-        let fp = GameFingerprint {
-            game_id: id,
-            rng_signature: "abc".into(),
+    fn fingerprint(game_id: Uuid, signature: &str) -> GameFingerprint {
+        GameFingerprint {
+            game_id,
+            rng_signature: signature.into(),
             symbol_map: serde_json::json!({"A": 0.5}),
             statistical_profile: serde_json::json!({"rtp_ratio": 0.96}),
-        };
-        s.save(fp.clone()).unwrap();
-        let got = s.get(id).unwrap().unwrap();
+            randomness: serde_json::Value::Null,
+            run_id: Uuid::new_v4(),
+            version: 0,
+            sample_size: 100,
+            created_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn save_and_get_fingerprint() {
+        let s = InMemoryFingerprintStore::new();
+        let id = Uuid::new_v4();
+        s.save(fingerprint(id, "abc")).await.unwrap();
+        let got = s.get(id).await.unwrap().unwrap();
         assert_eq!(got.rng_signature, "abc");
     }
+
+    #[tokio::test]
+    async fn save_appends_versions() {
+        let s = InMemoryFingerprintStore::new();
+        let id = Uuid::new_v4();
+        assert_eq!(s.save(fingerprint(id, "v1")).await.unwrap().version, 1);
+        assert_eq!(s.save(fingerprint(id, "v2")).await.unwrap().version, 2);
+        assert_eq!(s.get(id).await.unwrap().unwrap().rng_signature, "v2");
+        assert_eq!(s.get_version(id, 1).await.unwrap().unwrap().rng_signature, "v1");
+        assert!(s.get_version(id, 3).await.unwrap().is_none());
+        let versions: Vec<u32> = s.list_versions(id).await.unwrap().iter().map(|fp| fp.version).collect();
+        assert_eq!(versions, vec![1, 2]);
+        assert!(s.list_versions(Uuid::new_v4()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn postgres_store_numbers_concurrent_runs_consecutively() {
+        let Some(pool) = test_pool().await else { return };
+        let id = Uuid::new_v4();
+        PostgresGameStore::new(pool.clone()).create(test_game(id, "Classic")).await.unwrap();
+        let s = std::sync::Arc::new(PostgresFingerprintStore::new(pool));
+        assert!(s.get(id).await.unwrap().is_none());
+
+        let mut runs = tokio::task::JoinSet::new();
+        for i in 0..4 {
+            let s = s.clone();
+            runs.spawn(async move { s.save(fingerprint(id, &format!("run{i}"))).await });
+        }
+        while let Some(saved) = runs.join_next().await {
+            saved.unwrap().unwrap();
+        }
+        let versions: Vec<u32> = s.list_versions(id).await.unwrap().iter().map(|fp| fp.version).collect();
+        assert_eq!(versions, vec![1, 2, 3, 4]);
+        let latest = s.get(id).await.unwrap().unwrap();
+        assert_eq!(latest.version, 4);
+        assert!(latest.created_at.is_some());
+        let first = s.get_version(id, 1).await.unwrap().unwrap();
+        assert_eq!((first.sample_size, &first.symbol_map), (100, &serde_json::json!({"A": 0.5})));
+        assert!(s.get_version(id, 5).await.unwrap().is_none());
+        assert!(s.save(fingerprint(Uuid::new_v4(), "unregistered")).await.is_err());
+    }
 }
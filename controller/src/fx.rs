@@ -0,0 +1,177 @@
+//! Foreign-exchange rates for wallet operations in a currency other than the wallet's.
+//!
+//! A `FxRateProvider` quotes how many units of one currency buy one unit of another;
+//! `convert` applies the quote and rounds to the target currency's minor unit. Without a
+//! provider, or without a quote for the pair, mismatched currencies are rejected.
+
+use crate::api::{Currency, Money};
+use crate::app_state::DomainError;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Source of exchange rates.
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    /// Units of `to` per one unit of `from`; None if the pair is not quoted.
+    async fn rate(&self, from: Currency, to: Currency) -> Result<Option<Decimal>, DomainError>;
+}
+
+/// `amount` expressed in `to`: unchanged when already in `to`, otherwise converted at the
+/// provider's rate. InvalidInput when there is no provider or no rate for the pair.
+pub async fn convert(
+    provider: Option<&dyn FxRateProvider>,
+    amount: &Money,
+    to: Currency,
+) -> Result<Money, DomainError> {
+    if amount.currency == to {
+        return Ok(amount.clone());
+    }
+    let unquoted = || DomainError::InvalidInput(format!("no exchange rate from {} to {to}", amount.currency));
+    let Some(provider) = provider else {
+        return Err(unquoted());
+    };
+    let rate = provider.rate(amount.currency, to).await?.ok_or_else(unquoted)?;
+    let converted = amount
+        .amount
+        .checked_mul(rate)
+        .ok_or_else(|| DomainError::InvalidInput("amount overflow".into()))?;
+    Ok(Money::new(converted, to))
+}
+
+#[derive(Debug, Error)]
+pub enum FxRatesError {
+    #[error("io: {0}")]
+    Io(String),
+    #[error("parse: {0}")]
+    Parse(String),
+    #[error("unsupported rates format: {0} (expected .json or .toml)")]
+    UnsupportedFormat(String),
+    #[error("rate for {0} must be positive")]
+    NonPositiveRate(Currency),
+}
+
+/// Fixed rates read from a file, quoted against one base currency:
+///
+/// ```toml
+/// base = "AUD"
+///
+/// [rates]   # units of AUD per one unit of each currency
+/// USD = 1.52
+/// EUR = 1.65
+/// ```
+///
+/// Cross rates go through the base (USD→EUR = 1.52 / 1.65).
+#[derive(Debug, Clone, Deserialize)]
+pub struct StaticFxRates {
+    base: Currency,
+    rates: HashMap<Currency, Decimal>,
+}
+
+impl StaticFxRates {
+    pub fn new(base: Currency, rates: HashMap<Currency, Decimal>) -> Result<Self, FxRatesError> {
+        let fx = Self { base, rates };
+        fx.validate()?;
+        Ok(fx)
+    }
+
+    /// Loads a `.json` or `.toml` rates file, choosing the parser by extension.
+    pub fn load_file(path: &Path) -> Result<Self, FxRatesError> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| FxRatesError::Io(format!("{}: {e}", path.display())))?;
+        let fx: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&raw).map_err(|e| FxRatesError::Parse(e.to_string()))?,
+            Some("toml") => toml::from_str(&raw).map_err(|e| FxRatesError::Parse(e.to_string()))?,
+            _ => return Err(FxRatesError::UnsupportedFormat(path.display().to_string())),
+        };
+        fx.validate()?;
+        Ok(fx)
+    }
+
+    fn validate(&self) -> Result<(), FxRatesError> {
+        match self.rates.iter().find(|(_, r)| **r <= Decimal::ZERO) {
+            Some((currency, _)) => Err(FxRatesError::NonPositiveRate(*currency)),
+            None => Ok(()),
+        }
+    }
+
+    /// Units of the base currency per one unit of `currency`.
+    fn quote(&self, currency: Currency) -> Option<Decimal> {
+        if currency == self.base {
+            return Some(Decimal::ONE);
+        }
+        self.rates.get(&currency).copied()
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for StaticFxRates {
+    async fn rate(&self, from: Currency, to: Currency) -> Result<Option<Decimal>, DomainError> {
+        if from == to {
+            return Ok(Some(Decimal::ONE));
+        }
+        Ok(self
+            .quote(from)
+            .zip(self.quote(to))
+            .and_then(|(from, to)| from.checked_div(to)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn rates() -> StaticFxRates {
+        let raw = r#"
+            base = "AUD"
+
+            [rates]
+            USD = 1.5
+            EUR = 1.6
+            JPY = 0.01
+        "#;
+        toml::from_str(raw).unwrap()
+    }
+
+    fn money(amount: &str, currency: Currency) -> Money {
+        Money { amount: dec(amount), currency }
+    }
+
+    #[tokio::test]
+    async fn converts_through_the_base_and_rounds_to_the_minor_unit() {
+        let fx = rates();
+        let p: Option<&dyn FxRateProvider> = Some(&fx);
+        assert_eq!(convert(p, &money("10", Currency::USD), Currency::AUD).await.unwrap(), money("15", Currency::AUD));
+        assert_eq!(convert(p, &money("15", Currency::AUD), Currency::USD).await.unwrap(), money("10", Currency::USD));
+        // 10 EUR = 16 AUD = 10.666… USD.
+        assert_eq!(convert(p, &money("10", Currency::EUR), Currency::USD).await.unwrap(), money("10.67", Currency::USD));
+        // JPY has no minor unit.
+        assert_eq!(convert(p, &money("1.234", Currency::AUD), Currency::JPY).await.unwrap(), money("123", Currency::JPY));
+    }
+
+    #[tokio::test]
+    async fn unquoted_pairs_and_missing_providers_are_rejected() {
+        let fx = rates();
+        let gbp = money("1", Currency::GBP);
+        assert!(matches!(convert(Some(&fx), &gbp, Currency::AUD).await, Err(DomainError::InvalidInput(_))));
+        assert!(matches!(convert(None, &gbp, Currency::AUD).await, Err(DomainError::InvalidInput(_))));
+        // Same currency needs no provider.
+        assert_eq!(convert(None, &gbp, Currency::GBP).await.unwrap(), gbp);
+    }
+
+    #[test]
+    fn rejects_non_positive_rates() {
+        let rates = HashMap::from([(Currency::USD, Decimal::ZERO)]);
+        assert!(matches!(
+            StaticFxRates::new(Currency::AUD, rates),
+            Err(FxRatesError::NonPositiveRate(Currency::USD))
+        ));
+    }
+}
@@ -1,36 +1,111 @@
 //! Session lifecycle: create, get, and state transitions.
-//! Uses the SessionRepository trait; works with any backend.
+//! Uses the SessionRepository and GameRepository traits; works with any backend.
+//! Typed events go through `apply_event`, which persists the guarded transition and then hands
+//! its effects to an `EffectExecutor`. The `reaper` expires sessions left idle; `recovery`
+//! resolves actions a crash interrupted.
+
+mod reaper;
+mod recovery;
+
+pub use reaper::{reap_idle_sessions, spawn_reaper};
+pub use recovery::{recover_sessions, RecoveredAction, Resolution};
 
 use crate::api::{
     CreateSessionRequest, CreateSessionResponse, Session, SessionId, SessionMetrics,
 };
-use crate::app_state::{DomainError, SessionRepository};
-use crate::state_engine::{transition, GameState, StateError};
+use crate::app_state::{
+    DomainError, GameRepository, SessionCursor, SessionQuery, SessionRepository, TransitionCause,
+};
+use crate::state_engine::{
+    apply, transition, Effect, GameEvent, GameState, Guard, StateError, TransitionContext,
+};
+use async_trait::async_trait;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 use uuid::Uuid;
 
+/// Carries out the effects of an applied event (stake debit, spin resolution, payout, metrics).
+#[async_trait]
+pub trait EffectExecutor: Send {
+    /// Works out one effect's outcome before the transition is persisted, folding it into the
+    /// session aggregates `metrics`, which are written with the new state. An error here aborts
+    /// the event before any state change. Anything taken here (a spin's stake) must be handed
+    /// back by `revert`: if the write loses its compare-and-swap the outcome is discarded.
+    async fn resolve(
+        &mut self,
+        _session: &Session,
+        _effect: &Effect,
+        _metrics: &mut SessionMetrics,
+    ) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    /// Undoes what `resolve` took, once the event is known not to be persisted.
+    async fn revert(&mut self, _session: &Session) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    /// Runs one effect for `session`, which is already in the post-transition state.
+    async fn execute(&mut self, session: &Session, effect: &Effect) -> Result<(), DomainError>;
+}
+
+fn domain_error(session_id: SessionId, e: StateError) -> DomainError {
+    match e {
+        StateError::InvalidTransition { from, .. } => DomainError::InvalidTransition { from },
+        StateError::NotFound => DomainError::NotFound(session_id.0),
+        // Balance guards are wallet limits: the wallet cannot cover the stake.
+        StateError::GuardFailed { guard: Guard::InsufficientBalance, .. } => DomainError::WalletLimitExceeded,
+        StateError::GuardFailed { state, guard } => DomainError::ActionNotAllowed {
+            state,
+            reason: guard.to_string(),
+        },
+    }
+}
+
 /// Manages sessions and state transitions via the SessionRepository trait.
 pub struct GameSessionManager {
     repo: Arc<dyn SessionRepository>,
+    games: Arc<dyn GameRepository>,
 }
 
 impl GameSessionManager {
-    pub fn new(repo: Arc<dyn SessionRepository>) -> Self {
-        Self { repo }
+    pub fn new(repo: Arc<dyn SessionRepository>, games: Arc<dyn GameRepository>) -> Self {
+        Self { repo, games }
     }
 
     /// Creates a session in Initialized state and persists it.
+    /// Returns NotFound if `req.game_id` is not a registered game, InvalidInput if the profile's
+    /// max bet is not positive or a tag is empty or contains a comma (tags are filtered as a
+    /// comma-separated list).
     pub async fn create_session(
         &self,
         req: CreateSessionRequest,
     ) -> Result<CreateSessionResponse, DomainError> {
+        if let Some(max) = req.player_profile.max_bet.as_ref().filter(|m| !m.is_positive()) {
+            return Err(DomainError::InvalidInput(format!("maxBet must be positive, got {}", max.amount)));
+        }
+        if let Some(tag) = req.tags.iter().find(|t| t.trim().is_empty() || t.contains(',')) {
+            return Err(DomainError::InvalidInput(format!("invalid tag {tag:?}")));
+        }
+        if self.games.get_by_id(req.game_id.0).await?.is_none() {
+            return Err(DomainError::NotFound(req.game_id.0));
+        }
+        let mut tags = req.tags;
+        tags.sort();
+        tags.dedup();
         let session_id = SessionId(Uuid::new_v4());
+        let now = chrono::Utc::now();
         let session = Session {
             session_id,
             game_id: req.game_id,
             state: GameState::Initialized,
             metrics: SessionMetrics::default(),
+            player_profile: req.player_profile,
+            wallet_id: req.wallet_id,
+            version: 1,
+            updated_at: Some(now),
+            created_at: Some(now),
+            tags,
         };
         self.repo.create(session).await?;
         info!(session_id = %session_id.0, "session created");
@@ -45,24 +120,52 @@ impl GameSessionManager {
         self.repo.get_by_id(session_id.0).await
     }
 
-    /// Transitions session to `to_state` if valid; persists and logs.
+    /// One page of sessions matching `query`, with the cursor of the next page when more match.
+    pub async fn list_sessions(
+        &self,
+        mut query: SessionQuery,
+    ) -> Result<(Vec<Session>, Option<SessionCursor>), DomainError> {
+        let limit = query.limit as usize;
+        query.limit = query.limit.saturating_add(1);
+        let mut sessions = self.repo.list(&query).await?;
+        let next = if sessions.len() > limit {
+            sessions.truncate(limit);
+            sessions.last().map(|s| SessionCursor::after(s, query.sort))
+        } else {
+            None
+        };
+        Ok((sessions, next))
+    }
+
+    /// Transitions session to `to_state` if valid; persists it with `cause` in the session's
+    /// transition history and logs.
+    /// With `expected_version`, fails with VersionConflict unless the session is still at that
+    /// version; either way the write is a compare-and-swap against the version read here.
     pub async fn transition_session(
         &self,
         session_id: SessionId,
         to_state: GameState,
+        expected_version: Option<u64>,
+        cause: TransitionCause,
     ) -> Result<Session, DomainError> {
         let current = self
             .repo
             .get_by_id(session_id.0)
             .await?
             .ok_or(DomainError::NotFound(session_id.0))?;
+        if let Some(expected) = expected_version.filter(|v| *v != current.version) {
+            return Err(DomainError::VersionConflict {
+                expected,
+                current: current.version,
+            });
+        }
 
-        let new_state = transition(current.state, to_state).map_err(|e| match e {
-            StateError::InvalidTransition { from, .. } => DomainError::InvalidTransition { from },
-            StateError::NotFound => DomainError::NotFound(session_id.0),
-        })?;
+        let new_state = transition(current.state, to_state).map_err(|e| domain_error(session_id, e))?;
 
-        let updated = self.repo.update_state(session_id.0, new_state).await?;
+        let updated = self
+            .repo
+            .update_state(session_id.0, current.version, new_state, None, cause)
+            .await?;
         info!(
             session_id = %session_id.0,
             from = ?current.state,
@@ -71,28 +174,104 @@ impl GameSessionManager {
         );
         Ok(updated)
     }
+
+    /// State a Suspended session was suspended from, per its transition history.
+    pub async fn suspended_from(&self, session_id: SessionId) -> Result<Option<GameState>, DomainError> {
+        let history = self.repo.list_transitions(session_id.0).await?;
+        Ok(history
+            .iter()
+            .rev()
+            .find(|t| t.to == GameState::Suspended)
+            .map(|t| t.from))
+    }
+
+    /// Applies `event` to the session under the guards of `ctx`, resolves the transition's effects
+    /// into the session metrics, persists new state and metrics with the same compare-and-swap as
+    /// `transition_session` (recorded with `cause`, whose action is set to the event name), then
+    /// runs the effects in order.
+    /// Effects only run once the state write has won, so a rejected or stale event has none.
+    pub async fn apply_event(
+        &self,
+        session_id: SessionId,
+        event: &GameEvent,
+        ctx: &TransitionContext,
+        expected_version: Option<u64>,
+        cause: TransitionCause,
+        executor: &mut dyn EffectExecutor,
+    ) -> Result<Session, DomainError> {
+        let current = self
+            .repo
+            .get_by_id(session_id.0)
+            .await?
+            .ok_or(DomainError::NotFound(session_id.0))?;
+        if let Some(expected) = expected_version.filter(|v| *v != current.version) {
+            return Err(DomainError::VersionConflict {
+                expected,
+                current: current.version,
+            });
+        }
+
+        let next = apply(current.state, event, ctx).map_err(|e| domain_error(session_id, e))?;
+        let mut metrics = current.metrics.clone();
+        let mut resolved = Ok(());
+        for effect in &next.effects {
+            resolved = executor.resolve(&current, effect, &mut metrics).await;
+            if resolved.is_err() {
+                break;
+            }
+        }
+        let written = match resolved {
+            Ok(()) => {
+                let metrics = (metrics != current.metrics).then_some(metrics);
+                let cause = TransitionCause { action: event.name().to_string(), ..cause };
+                self.repo
+                    .update_state(session_id.0, current.version, next.state, metrics, cause)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let updated = match written {
+            Ok(updated) => updated,
+            Err(e) => {
+                if let Err(revert) = executor.revert(&current).await {
+                    warn!(session_id = %session_id.0, error = %revert, "failed to revert resolved effects");
+                }
+                return Err(e);
+            }
+        };
+        info!(
+            session_id = %session_id.0,
+            from = ?current.state,
+            to = ?next.state,
+            ?event,
+            "state transition"
+        );
+        for effect in &next.effects {
+            executor.execute(&updated, effect).await?;
+        }
+        Ok(updated)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::{GameId, PlayerProfile};
-    use crate::persistence_metrics::InMemorySessionStore;
+    use crate::persistence_metrics::{InMemoryGameStore, InMemorySessionStore};
+    use crate::test_support::{session_request, test_game};
+    use rust_decimal::Decimal;
+
+    const GAME: Uuid = Uuid::from_u128(0x0b6f_4c8e_1d2a_4f3b_9c5e_7a8d_9e0f_1a01);
 
     fn make_manager() -> GameSessionManager {
-        GameSessionManager::new(Arc::new(InMemorySessionStore::new()))
+        let games = InMemoryGameStore::new();
+        games.seed(test_game(GAME, "Classic Fruit"));
+        GameSessionManager::new(Arc::new(InMemorySessionStore::new()), Arc::new(games))
     }
 
     #[tokio::test]
     async fn create_session_returns_initialized() {
         let mgr = make_manager();
-        let req = CreateSessionRequest {
-            game_id: GameId(Uuid::new_v4()),
-            player_profile: PlayerProfile {
-                behavior_type: "conservative".to_string(),
-                max_bet: None,
-            },
-        };
+        let req = session_request(GAME, None);
         let res = mgr.create_session(req).await.unwrap();
         assert_eq!(res.state, GameState::Initialized);
         let session = mgr.get_session(res.session_id).await.unwrap().unwrap();
@@ -102,33 +281,179 @@ mod tests {
     #[tokio::test]
     async fn transition_session_valid() {
         let mgr = make_manager();
-        let req = CreateSessionRequest {
-            game_id: GameId(Uuid::new_v4()),
-            player_profile: PlayerProfile {
-                behavior_type: "aggressive".to_string(),
-                max_bet: None,
-            },
-        };
+        let req = session_request(GAME, None);
         let res = mgr.create_session(req).await.unwrap();
         let updated = mgr
-            .transition_session(res.session_id, GameState::Playing)
+            .transition_session(res.session_id, GameState::Playing, None, TransitionCause::default())
             .await
             .unwrap();
         assert_eq!(updated.state, GameState::Playing);
+        assert_eq!(updated.version, 2);
     }
 
     #[tokio::test]
     async fn transition_session_invalid_returns_error() {
         let mgr = make_manager();
-        let req = CreateSessionRequest {
-            game_id: GameId(Uuid::new_v4()),
-            player_profile: PlayerProfile {
-                behavior_type: "mixed".to_string(),
-                max_bet: None,
-            },
-        };
+        let req = session_request(GAME, None);
         let res = mgr.create_session(req).await.unwrap();
-        let r = mgr.transition_session(res.session_id, GameState::Completed).await;
+        let r = mgr.transition_session(res.session_id, GameState::Completed, None, TransitionCause::default()).await;
         assert!(r.is_err());
     }
+
+    #[tokio::test]
+    async fn create_session_unknown_game_returns_not_found() {
+        let mgr = make_manager();
+        let unknown = Uuid::new_v4();
+        let req = session_request(unknown, None);
+        let r = mgr.create_session(req).await;
+        assert!(matches!(r, Err(DomainError::NotFound(id)) if id == unknown));
+    }
+
+    #[tokio::test]
+    async fn transition_session_with_stale_version_conflicts() {
+        let mgr = make_manager();
+        let req = session_request(GAME, None);
+        let res = mgr.create_session(req).await.unwrap();
+        mgr.transition_session(res.session_id, GameState::Playing, Some(1), TransitionCause::default())
+            .await
+            .unwrap();
+        // A second writer still holding version 1 loses.
+        let r = mgr
+            .transition_session(res.session_id, GameState::Evaluating, Some(1), TransitionCause::default())
+            .await;
+        assert!(matches!(r, Err(DomainError::VersionConflict { expected: 1, current: 2 })));
+    }
+
+    #[tokio::test]
+    async fn list_sessions_returns_next_cursor_until_exhausted() {
+        let mgr = make_manager();
+        for tags in [vec!["vip".to_string(), "vip".to_string()], vec![], vec!["vip".to_string()]] {
+            let req = CreateSessionRequest { tags, ..session_request(GAME, None) };
+            mgr.create_session(req).await.unwrap();
+        }
+        let query = SessionQuery { tags: vec!["vip".into()], limit: 1, ..Default::default() };
+        let (first, next) = mgr.list_sessions(query.clone()).await.unwrap();
+        assert_eq!(first[0].tags, vec!["vip".to_string()]);
+        let (second, next) = mgr.list_sessions(SessionQuery { after: next, ..query }).await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert_ne!(first[0].session_id, second[0].session_id);
+        assert!(next.is_none());
+
+        let bad = CreateSessionRequest { tags: vec!["a,b".into()], ..session_request(GAME, None) };
+        assert!(matches!(mgr.create_session(bad).await, Err(DomainError::InvalidInput(_))));
+    }
+
+    fn actor(key_id: &str) -> TransitionCause {
+        TransitionCause { actor: Some(key_id.into()), ..Default::default() }
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Effect>);
+
+    #[async_trait]
+    impl EffectExecutor for Recorder {
+        async fn resolve(&mut self, _session: &Session, effect: &Effect, metrics: &mut SessionMetrics) -> Result<(), DomainError> {
+            if let Effect::ResolveSpin { stake } = *effect {
+                metrics.record_spin(stake, Decimal::TWO * stake, chrono::Utc::now());
+            }
+            Ok(())
+        }
+
+        async fn execute(&mut self, _session: &Session, effect: &Effect) -> Result<(), DomainError> {
+            self.0.push(*effect);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_event_runs_effects_only_after_guards_pass() {
+        let mgr = make_manager();
+        let req = session_request(GAME, None);
+        let id = mgr.create_session(req).await.unwrap().session_id;
+        let mut effects = Recorder::default();
+        let no_bet = TransitionContext::default();
+
+        let s = mgr
+            .apply_event(id, &GameEvent::PlaceBet { amount: Decimal::new(15, 1) }, &no_bet, None, TransitionCause::default(), &mut effects)
+            .await
+            .unwrap();
+        assert_eq!((s.state, s.version), (GameState::Playing, 2));
+
+        // Spin with no bet known to the context is rejected and leaves the session untouched.
+        let r = mgr
+            .apply_event(id, &GameEvent::Spin { stake: None }, &no_bet, None, TransitionCause::default(), &mut effects)
+            .await;
+        assert!(matches!(r, Err(DomainError::ActionNotAllowed { state: GameState::Playing, .. })));
+        assert_eq!(mgr.get_session(id).await.unwrap().unwrap().version, 2);
+
+        let ctx = TransitionContext { current_bet: Some(Decimal::new(15, 1)), balance: None };
+        let s = mgr
+            .apply_event(id, &GameEvent::Spin { stake: None }, &ctx, Some(2), actor("key-1"), &mut effects)
+            .await
+            .unwrap();
+        assert_eq!(s.state, GameState::Evaluating);
+        assert_eq!(
+            effects.0,
+            vec![Effect::RecordBet { amount: Decimal::new(15, 1) }, Effect::ResolveSpin { stake: Decimal::new(15, 1) }]
+        );
+        let history = mgr.repo.list_transitions(id.0).await.unwrap();
+        let actions: Vec<_> = history.iter().map(|t| (t.action.as_str(), t.actor.as_deref())).collect();
+        assert_eq!(actions, vec![("PlaceBet", None), ("Spin", Some("key-1"))]);
+        // The spin's outcome was written with the transition.
+        assert_eq!((s.metrics.total_spins, s.metrics.total_payout), (1, Decimal::from(3)));
+
+        // A stale spin resolves nothing into the stored metrics.
+        let r = mgr
+            .apply_event(id, &GameEvent::Spin { stake: None }, &ctx, Some(2), actor("key-1"), &mut effects)
+            .await;
+        assert!(matches!(r, Err(DomainError::VersionConflict { .. })));
+        assert_eq!(mgr.get_session(id).await.unwrap().unwrap().metrics, s.metrics);
+    }
+
+    /// Loses the race: another writer commits between its resolve and the manager's write.
+    struct Racer {
+        repo: Arc<dyn SessionRepository>,
+        reverted: bool,
+    }
+
+    #[async_trait]
+    impl EffectExecutor for Racer {
+        async fn resolve(&mut self, session: &Session, _effect: &Effect, _metrics: &mut SessionMetrics) -> Result<(), DomainError> {
+            self.repo
+                .update_state(session.session_id.0, session.version, session.state, None, TransitionCause::default())
+                .await?;
+            Ok(())
+        }
+
+        async fn revert(&mut self, _session: &Session) -> Result<(), DomainError> {
+            self.reverted = true;
+            Ok(())
+        }
+
+        async fn execute(&mut self, _session: &Session, _effect: &Effect) -> Result<(), DomainError> {
+            panic!("effects must not run for a lost write");
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_event_reverts_resolved_effects_when_the_write_fails() {
+        let mgr = make_manager();
+        let req = session_request(GAME, None);
+        let id = mgr.create_session(req).await.unwrap().session_id;
+
+        // A stake above the wallet balance is refused by the guard, before anything resolves.
+        let mut racer = Racer { repo: mgr.repo.clone(), reverted: false };
+        let poor = TransitionContext { current_bet: None, balance: Some(Decimal::ONE) };
+        let r = mgr
+            .apply_event(id, &GameEvent::PlaceBet { amount: Decimal::TWO }, &poor, None, TransitionCause::default(), &mut racer)
+            .await;
+        assert!(matches!(r, Err(DomainError::WalletLimitExceeded)));
+        assert!(!racer.reverted);
+
+        let r = mgr
+            .apply_event(id, &GameEvent::PlaceBet { amount: Decimal::ONE }, &poor, None, TransitionCause::default(), &mut racer)
+            .await;
+        assert!(matches!(r, Err(DomainError::VersionConflict { expected: 1, current: 2 })));
+        assert!(racer.reverted);
+    }
 }
@@ -0,0 +1,138 @@
+//! Idle-session reaper: expires live sessions that have not changed state within their game's
+//! idle TTL (definition `idleTtlSecs`, else `AppConfig::session_idle_ttl`).
+
+use super::{EffectExecutor, GameSessionManager};
+use crate::api::{Session, SessionId, SessionMetrics};
+use crate::app_state::{AppState, DomainError, TransitionCause};
+use crate::state_engine::{Effect, GameEvent, TransitionContext};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Idle TTL for sessions of `game_id`.
+fn idle_ttl(state: &AppState, game_id: Uuid) -> Duration {
+    state
+        .game_catalog
+        .get(game_id)
+        .and_then(|g| g.definition.idle_ttl_secs)
+        .map_or(state.config.session_idle_ttl, Duration::from_secs)
+}
+
+/// Settles an expiring session. Stakes are debited per spin and payouts credited at once, so
+/// no money is left open on the session to settle; the expiry itself is recorded in the
+/// session's metrics, written with the Expired state.
+struct ExpiryEffects<'a> {
+    state: &'a AppState,
+    now: DateTime<Utc>,
+}
+
+#[async_trait]
+impl EffectExecutor for ExpiryEffects<'_> {
+    async fn resolve(&mut self, _session: &Session, effect: &Effect, metrics: &mut SessionMetrics) -> Result<(), DomainError> {
+        if let Effect::Settle = effect {
+            metrics.record_expiry(self.now);
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, _session: &Session, effect: &Effect) -> Result<(), DomainError> {
+        if let Effect::Settle = effect {
+            self.state.metrics.record_session_expired();
+        }
+        Ok(())
+    }
+}
+
+/// Expires every live session idle past its TTL as of `now`; returns the expired ids.
+/// A session that changes state during the scan loses nothing: its version moved on, so the
+/// expiry compare-and-swap fails and the session is left alone.
+pub async fn reap_idle_sessions(state: &AppState, now: DateTime<Utc>) -> Result<Vec<SessionId>, DomainError> {
+    let shortest = state
+        .game_catalog
+        .list()
+        .iter()
+        .filter_map(|g| g.definition.idle_ttl_secs.map(Duration::from_secs))
+        .fold(state.config.session_idle_ttl, Duration::min);
+    let cutoff = now - chrono::Duration::from_std(shortest).unwrap_or(chrono::Duration::MAX);
+    let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+
+    let mut expired = Vec::new();
+    for session in state.session_repo.list_idle(cutoff).await? {
+        let ttl = chrono::Duration::from_std(idle_ttl(state, session.game_id.0)).unwrap_or(chrono::Duration::MAX);
+        if session.updated_at.is_some_and(|t| now - t < ttl) {
+            continue;
+        }
+        let mut effects = ExpiryEffects { state, now };
+        match mgr
+            .apply_event(
+                session.session_id,
+                &GameEvent::Expire,
+                &TransitionContext::default(),
+                Some(session.version),
+                TransitionCause::default(),
+                &mut effects,
+            )
+            .await
+        {
+            Ok(_) => {
+                info!(session_id = %session.session_id, from = ?session.state, "session expired");
+                expired.push(session.session_id);
+            }
+            Err(DomainError::VersionConflict { .. }) => {}
+            Err(e) => warn!(session_id = %session.session_id, error = %e, "failed to expire session"),
+        }
+    }
+    Ok(expired)
+}
+
+/// Runs `reap_idle_sessions` every `config.reaper_interval` until the task is aborted.
+pub fn spawn_reaper(state: AppState) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(state.config.reaper_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = reap_idle_sessions(&state, Utc::now()).await {
+                warn!(error = %e, "idle session scan failed");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_engine::GameState;
+    use crate::test_support::{session_request, test_state};
+
+    const GAME: Uuid = Uuid::from_u128(0x0b6f_4c8e_1d2a_4f3b_9c5e_7a8d_9e0f_1a01);
+
+    async fn session(state: &AppState) -> SessionId {
+        let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+        mgr.create_session(session_request(GAME, None)).await.unwrap().session_id
+    }
+
+    #[tokio::test]
+    async fn expires_sessions_idle_past_ttl_once() {
+        let state = test_state(GAME);
+        let id = session(&state).await;
+
+        let soon = Utc::now() + chrono::Duration::minutes(10);
+        assert!(reap_idle_sessions(&state, soon).await.unwrap().is_empty());
+
+        let later = Utc::now() + chrono::Duration::minutes(31);
+        assert_eq!(reap_idle_sessions(&state, later).await.unwrap(), vec![id]);
+        let expired = state.session_repo.get_by_id(id.0).await.unwrap().unwrap();
+        assert_eq!(expired.state, GameState::Expired);
+        assert_eq!(expired.metrics.expired_at, Some(later));
+        assert_eq!(state.metrics.get_sessions_expired(), 1);
+        let history = state.session_repo.list_transitions(id.0).await.unwrap();
+        assert_eq!((history[0].action.as_str(), history[0].actor.as_ref()), ("Expire", None));
+
+        assert!(reap_idle_sessions(&state, later).await.unwrap().is_empty());
+        assert_eq!(state.metrics.get_sessions_expired(), 1);
+    }
+}
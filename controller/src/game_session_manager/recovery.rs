@@ -0,0 +1,247 @@
+//! Startup recovery of actions interrupted by a crash.
+//!
+//! A Spin resolves its effects first, debiting its stake from the session's wallet against the
+//! id of the gameplay event it will write; then it persists its transition, which carries that
+//! event id; then it credits its payout against the same id; then it appends the event. Other
+//! actions move no money. Recovery reconciles each live session's transitions with its event
+//! log and its wallet's ledger:
+//!
+//! - A debit whose event no transition names was taken by a spin that never moved the session;
+//!   it is refunded.
+//! - A latest transition whose event is missing marks an action that died after the transition
+//!   was written (an interrupted action is the last thing the crashed process did to its
+//!   session, so earlier transitions are not checked). Outcome-free actions are replayed into
+//!   the event log. A Spin whose payout credit is in the ledger stands, and its event is written
+//!   with that payout; otherwise the spin never settled, so it is voided and its stake refunded.
+//!
+//! Refunds are booked against the event and skipped once one is, so a recovery that is itself
+//! interrupted can run again without refunding twice.
+
+use crate::api::{GameplayResult, Money, Session, SessionId, SessionTransition};
+use crate::app_state::{AppState, DomainError};
+use crate::event_store::GameplayEvent;
+use crate::ledger::{LedgerEntry, LedgerEntryKind};
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How an interrupted action was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Resolution {
+    /// The missing gameplay event was written as the action would have.
+    Replayed,
+    /// The spin had paid out; its event was written with the payout from the ledger.
+    Settled,
+    /// Recorded as a voided spin with no payout.
+    Voided,
+    /// The stake of a spin that never moved the session was refunded.
+    Refunded,
+}
+
+/// One interrupted action found on startup.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredAction {
+    pub session_id: SessionId,
+    pub event_id: Uuid,
+    pub action: String,
+    pub resolution: Resolution,
+}
+
+impl RecoveredAction {
+    fn new(session_id: SessionId, event_id: Uuid, action: &str, resolution: Resolution) -> Self {
+        warn!(%session_id, %event_id, action, ?resolution, "recovered interrupted action");
+        Self { session_id, event_id, action: action.to_string(), resolution }
+    }
+}
+
+/// Scans live sessions and resolves any interrupted action; returns what was resolved.
+pub async fn recover_sessions(state: &AppState) -> Result<Vec<RecoveredAction>, DomainError> {
+    let mut recovered = Vec::new();
+    for session in state.session_repo.list_active().await? {
+        let id = session.session_id;
+        let history = state.session_repo.list_transitions(id.0).await?;
+        let entries = match session.wallet_id {
+            Some(wallet) => state.wallet_repo.list_session_entries(wallet, id.0).await?,
+            None => Vec::new(),
+        };
+        for debit in entries.iter().filter(|e| e.kind == LedgerEntryKind::Debit) {
+            let Some(event_id) = debit.event_id else { continue };
+            if history.iter().any(|t| t.event_id == Some(event_id)) {
+                continue;
+            }
+            if state.wallet_repo.refund_debit(debit.wallet_id, event_id).await?.is_some() {
+                recovered.push(RecoveredAction::new(id, event_id, "Spin", Resolution::Refunded));
+            }
+        }
+
+        let Some(last) = history.last() else { continue };
+        let Some(event_id) = last.event_id else { continue };
+        let logged = state
+            .event_store
+            .list_by_session(id.0)
+            .await
+            .map_err(|e| DomainError::Internal(e.to_string()))?;
+        if logged.iter().any(|e| e.event_id == event_id) {
+            continue;
+        }
+        let resolution = resolve(state, &session, last, event_id, &entries).await?;
+        recovered.push(RecoveredAction::new(id, event_id, &last.action, resolution));
+    }
+    info!(count = recovered.len(), "session recovery complete");
+    Ok(recovered)
+}
+
+/// Settles the action of `transition`, whose event `event_id` is missing; `entries` are the
+/// ledger entries booked for its session.
+async fn resolve(
+    state: &AppState,
+    session: &Session,
+    transition: &SessionTransition,
+    event_id: Uuid,
+    entries: &[LedgerEntry],
+) -> Result<Resolution, DomainError> {
+    let paid = entries
+        .iter()
+        .find(|e| e.event_id == Some(event_id) && e.kind == LedgerEntryKind::Credit)
+        .map(|e| Money { amount: e.wallet_delta(), currency: e.balance_after.currency });
+    let (resolution, result, reward) = match (transition.action.as_str(), paid) {
+        // The payout landed in the wallet (in its currency), so the spin stands; the grid it
+        // showed is lost.
+        ("Spin", Some(payout)) => {
+            let settled = GameplayResult { payout: Some(payout), ..GameplayResult::default() };
+            (Resolution::Settled, settled, None)
+        }
+        ("Spin", None) => {
+            if let Some(wallet) = session.wallet_id {
+                state.wallet_repo.refund_debit(wallet, event_id).await?;
+            }
+            let voided = GameplayResult { voided: true, ..GameplayResult::default() };
+            (Resolution::Voided, voided, Some(0.0))
+        }
+        _ => (Resolution::Replayed, GameplayResult::default(), None),
+    };
+    let event = GameplayEvent {
+        event_id,
+        session_id: transition.session_id.0,
+        action: transition.request.clone().unwrap_or_default(),
+        result: serde_json::to_value(&result).unwrap_or_default(),
+        timestamp: Some(transition.timestamp),
+        reward,
+    };
+    state
+        .event_store
+        .insert(event)
+        .await
+        .map_err(|e| DomainError::Internal(e.to_string()))?;
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::TransitionCause;
+    use crate::api::{Currency, Money, WalletOperationType};
+    use crate::game_session_manager::GameSessionManager;
+    use crate::ledger::{LedgerCause, LedgerEntryKind};
+    use crate::state_engine::GameState;
+    use crate::test_support::{session_request, test_state, test_wallet};
+    use rust_decimal::Decimal;
+
+    const GAME: Uuid = Uuid::from_u128(0x0b6f_4c8e_1d2a_4f3b_9c5e_7a8d_9e0f_1a01);
+
+    /// Creates a session and moves it to `to` as `action` did, without writing its event.
+    async fn interrupted(state: &AppState, to: GameState, action: &str, wallet_id: Option<Uuid>) -> (SessionId, Uuid) {
+        let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+        let id = mgr.create_session(session_request(GAME, wallet_id)).await.unwrap().session_id;
+        let event_id = Uuid::new_v4();
+        let cause = TransitionCause {
+            action: action.into(),
+            actor: None,
+            event_id: Some(event_id),
+            request: Some(serde_json::json!({ "type": action, "amount": { "amount": 2.0, "currency": "AUD" } })),
+        };
+        state.session_repo.update_state(id.0, 1, to, None, cause).await.unwrap();
+        (id, event_id)
+    }
+
+    /// Books `operation` of `amount` on `wallet` for `event` of `session`, as a spin does.
+    async fn book(state: &AppState, wallet: Uuid, session: SessionId, event: Uuid, operation: WalletOperationType, amount: i64) {
+        let amount = Money { amount: Decimal::from(amount), currency: Currency::AUD };
+        let cause = LedgerCause { session_id: Some(session.0), game_id: Some(GAME), event_id: Some(event), fee: None };
+        state.wallet_repo.apply_operation(wallet, operation, amount, cause).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn voids_interrupted_spins_and_replays_other_actions() {
+        let state = test_state(GAME);
+        let wallet = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet, Decimal::from(10))).await.unwrap();
+        let (bet, bet_event) = interrupted(&state, GameState::Playing, "PlaceBet", None).await;
+        let (spin, spin_event) = interrupted(&state, GameState::Evaluating, "Spin", Some(wallet)).await;
+        // The interrupted spin already debited its 2.0 stake.
+        book(&state, wallet, spin, spin_event, WalletOperationType::Debit, 2).await;
+
+        let mut recovered = recover_sessions(&state).await.unwrap();
+        recovered.sort_by_key(|r| r.action.clone());
+        assert_eq!(
+            recovered,
+            vec![
+                RecoveredAction { session_id: bet, event_id: bet_event, action: "PlaceBet".into(), resolution: Resolution::Replayed },
+                RecoveredAction { session_id: spin, event_id: spin_event, action: "Spin".into(), resolution: Resolution::Voided },
+            ]
+        );
+        let events = state.event_store.list_by_session(spin.0).await.unwrap();
+        assert_eq!(events[0].event_id, spin_event);
+        let result: GameplayResult = serde_json::from_value(events[0].result.clone()).unwrap();
+        assert!(result.voided && result.payout.is_none());
+        let refunded = state.wallet_repo.get_by_id(wallet).await.unwrap().unwrap();
+        assert_eq!((refunded.balance.amount, refunded.daily_spent.amount), (Decimal::from(10), Decimal::ZERO));
+        let refund = &state.wallet_repo.list_transactions(wallet, None, 1).await.unwrap()[0];
+        assert_eq!(refund.kind, LedgerEntryKind::Refund);
+        assert_eq!((refund.session_id, refund.event_id), (Some(spin.0), Some(spin_event)));
+        assert!(state.wallet_repo.refund_debit(wallet, spin_event).await.unwrap().is_none(), "refunded once");
+
+        // Resolved actions now have their events; a second pass finds nothing.
+        assert!(recover_sessions(&state).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn interrupted_spins_that_paid_out_stand() {
+        let state = test_state(GAME);
+        let wallet = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet, Decimal::from(10))).await.unwrap();
+        let (spin, spin_event) = interrupted(&state, GameState::Evaluating, "Spin", Some(wallet)).await;
+        book(&state, wallet, spin, spin_event, WalletOperationType::Debit, 2).await;
+        book(&state, wallet, spin, spin_event, WalletOperationType::Credit, 5).await;
+
+        let recovered = recover_sessions(&state).await.unwrap();
+        assert_eq!(recovered.iter().map(|r| r.resolution).collect::<Vec<_>>(), vec![Resolution::Settled]);
+        let events = state.event_store.list_by_session(spin.0).await.unwrap();
+        let result: GameplayResult = serde_json::from_value(events[0].result.clone()).unwrap();
+        assert!(!result.voided);
+        assert_eq!(result.payout, Some(Money { amount: Decimal::from(5), currency: Currency::AUD }));
+        // Neither the stake is refunded nor the payout taken back.
+        let wallet = state.wallet_repo.get_by_id(wallet).await.unwrap().unwrap();
+        assert_eq!(wallet.balance.amount, Decimal::from(13));
+        assert!(recover_sessions(&state).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn refunds_debits_of_spins_that_never_moved_their_session() {
+        let state = test_state(GAME);
+        let wallet = Uuid::new_v4();
+        state.wallet_repo.create(test_wallet(wallet, Decimal::from(10))).await.unwrap();
+        let mgr = GameSessionManager::new(state.session_repo.clone(), state.game_repo.clone());
+        let session = mgr.create_session(session_request(GAME, Some(wallet))).await.unwrap().session_id;
+        // The stake was debited, then the process died before the transition was written.
+        let orphan = Uuid::new_v4();
+        book(&state, wallet, session, orphan, WalletOperationType::Debit, 3).await;
+
+        let recovered = recover_sessions(&state).await.unwrap();
+        assert_eq!(recovered, vec![RecoveredAction { session_id: session, event_id: orphan, action: "Spin".into(), resolution: Resolution::Refunded }]);
+        let refunded = state.wallet_repo.get_by_id(wallet).await.unwrap().unwrap();
+        assert_eq!((refunded.balance.amount, refunded.daily_spent.amount), (Decimal::from(10), Decimal::ZERO));
+        assert!(recover_sessions(&state).await.unwrap().is_empty(), "refunded once");
+    }
+}
@@ -0,0 +1,295 @@
+//! Double-entry wallet ledger.
+//!
+//! Every change to a wallet's balance is booked as an immutable `LedgerEntry` whose postings
+//! move money between accounts and sum to zero: the wallet, the house (per game, or the
+//! operator's own for operations outside a game) and fees. The wallet account's postings add
+//! up to its balance, which is what the reconciliation check recomputes.
+
+use crate::api::{Currency, Money, Wallet, WalletOperationType};
+use crate::app_state::DomainError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Account a posting moves money into or out of. Serialized as `wallet:<id>`,
+/// `house:<game id>`, `house` (the operator's account, for operations outside a game) or `fees`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum LedgerAccount {
+    Wallet(Uuid),
+    House(Option<Uuid>),
+    Fees,
+}
+
+impl fmt::Display for LedgerAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerAccount::Wallet(id) => write!(f, "wallet:{id}"),
+            LedgerAccount::House(Some(game)) => write!(f, "house:{game}"),
+            LedgerAccount::House(None) => f.write_str("house"),
+            LedgerAccount::Fees => f.write_str("fees"),
+        }
+    }
+}
+
+impl FromStr for LedgerAccount {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::InvalidInput(format!("invalid ledger account {s:?}"));
+        let id = |id: &str| Uuid::try_parse(id).map_err(|_| invalid());
+        match s.split_once(':') {
+            Some(("wallet", wallet)) => Ok(LedgerAccount::Wallet(id(wallet)?)),
+            Some(("house", game)) => Ok(LedgerAccount::House(Some(id(game)?))),
+            None if s == "house" => Ok(LedgerAccount::House(None)),
+            None if s == "fees" => Ok(LedgerAccount::Fees),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl From<LedgerAccount> for String {
+    fn from(account: LedgerAccount) -> String {
+        account.to_string()
+    }
+}
+
+impl TryFrom<String> for LedgerAccount {
+    type Error = DomainError;
+
+    fn try_from(s: String) -> Result<Self, DomainError> {
+        s.parse()
+    }
+}
+
+/// One leg of an entry: `amount` into `account` (negative: out of it).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Posting {
+    pub account: LedgerAccount,
+    pub amount: Money,
+}
+
+/// Why an entry was booked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerEntryKind {
+    /// Funds a wallet when it is created.
+    Opening,
+    Debit,
+    Credit,
+    /// Reverses a debit that bought nothing, e.g. a voided spin's stake; it references the
+    /// same gameplay event as the debit.
+    Refund,
+}
+
+/// What a wallet operation is for; recorded on its ledger entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LedgerCause {
+    /// Session whose action moved the money.
+    pub session_id: Option<Uuid>,
+    /// Game whose house account takes the other side; None books it to the operator's.
+    pub game_id: Option<Uuid>,
+    /// Gameplay event of the action.
+    pub event_id: Option<Uuid>,
+    /// Cost of the operation borne by the house and booked to the fees account; the wallet
+    /// moves the same amount either way. In the wallet's currency.
+    pub fee: Option<Money>,
+}
+
+/// Immutable record of one change to a wallet's balance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    pub entry_id: Uuid,
+    pub wallet_id: Uuid,
+    /// Position in the wallet's ledger, from 1.
+    pub sequence: u64,
+    pub kind: LedgerEntryKind,
+    /// Balanced: they sum to zero.
+    pub postings: Vec<Posting>,
+    /// Wallet balance once the entry is applied.
+    pub balance_after: Money,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerEntry {
+    /// Books `operation` of `amount`, which left the wallet at `after`: the wallet and the
+    /// house take opposite sides, and any fee in `cause` moves from the house to fees.
+    pub fn operation(
+        after: &Wallet,
+        sequence: u64,
+        operation: &WalletOperationType,
+        amount: &Money,
+        cause: &LedgerCause,
+    ) -> Result<Self, DomainError> {
+        let wallet = match operation {
+            WalletOperationType::Debit => Money { amount: -amount.amount, currency: amount.currency },
+            WalletOperationType::Credit => amount.clone(),
+        };
+        let fee = cause.fee.clone().unwrap_or_else(|| Money::zero(amount.currency));
+        if fee.amount < Decimal::ZERO {
+            return Err(DomainError::InvalidInput(format!("fee {} must not be negative", fee.amount)));
+        }
+        let house = Money::zero(amount.currency).checked_sub(&wallet)?.checked_sub(&fee)?;
+        let mut postings = vec![
+            Posting { account: LedgerAccount::Wallet(after.wallet_id.0), amount: wallet },
+            Posting { account: LedgerAccount::House(cause.game_id), amount: house },
+        ];
+        if fee.is_positive() {
+            postings.push(Posting { account: LedgerAccount::Fees, amount: fee });
+        }
+        let kind = match operation {
+            WalletOperationType::Debit => LedgerEntryKind::Debit,
+            WalletOperationType::Credit => LedgerEntryKind::Credit,
+        };
+        Ok(Self::new(after, sequence, kind, postings, cause))
+    }
+
+    /// Books a wallet's creation as its opening `balance` moving from the operator's house
+    /// account into the wallet.
+    pub fn opening(after: &Wallet, sequence: u64, balance: &Money) -> Self {
+        let house = Money { amount: -balance.amount, currency: balance.currency };
+        let postings = vec![
+            Posting { account: LedgerAccount::Wallet(after.wallet_id.0), amount: balance.clone() },
+            Posting { account: LedgerAccount::House(None), amount: house },
+        ];
+        Self::new(after, sequence, LedgerEntryKind::Opening, postings, &LedgerCause::default())
+    }
+
+    /// Books the reversal of `debit`, which left the wallet at `after`: every posting of the
+    /// debit, fees included, moves back.
+    pub fn refund(after: &Wallet, sequence: u64, debit: &LedgerEntry) -> Result<Self, DomainError> {
+        if debit.kind != LedgerEntryKind::Debit {
+            return Err(DomainError::Internal(format!("ledger entry {} is not a debit", debit.entry_id)));
+        }
+        let postings = debit
+            .postings
+            .iter()
+            .map(|p| Posting { account: p.account, amount: Money { amount: -p.amount.amount, currency: p.amount.currency } })
+            .collect();
+        let cause = LedgerCause { session_id: debit.session_id, game_id: debit.game_id, event_id: debit.event_id, fee: None };
+        Ok(Self::new(after, sequence, LedgerEntryKind::Refund, postings, &cause))
+    }
+
+    fn new(after: &Wallet, sequence: u64, kind: LedgerEntryKind, postings: Vec<Posting>, cause: &LedgerCause) -> Self {
+        Self {
+            entry_id: Uuid::new_v4(),
+            wallet_id: after.wallet_id.0,
+            sequence,
+            kind,
+            postings,
+            balance_after: after.balance.clone(),
+            session_id: cause.session_id,
+            game_id: cause.game_id,
+            event_id: cause.event_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether the postings sum to zero in every currency.
+    pub fn is_balanced(&self) -> bool {
+        let mut sums: Vec<(Currency, Decimal)> = Vec::new();
+        for p in &self.postings {
+            match sums.iter_mut().find(|(c, _)| *c == p.amount.currency) {
+                Some((_, sum)) => *sum += p.amount.amount,
+                None => sums.push((p.amount.currency, p.amount.amount)),
+            }
+        }
+        sums.iter().all(|(_, sum)| sum.is_zero())
+    }
+
+    /// The debit among `entries` booked for gameplay event `event_id`, unless it has already
+    /// been refunded.
+    pub fn unrefunded_debit(entries: &[LedgerEntry], event_id: Uuid) -> Option<&LedgerEntry> {
+        let mut for_event = entries.iter().filter(|e| e.event_id == Some(event_id));
+        if for_event.clone().any(|e| e.kind == LedgerEntryKind::Refund) {
+            return None;
+        }
+        for_event.find(|e| e.kind == LedgerEntryKind::Debit)
+    }
+
+    /// Net change this entry makes to the wallet's balance.
+    pub fn wallet_delta(&self) -> Decimal {
+        self.postings
+            .iter()
+            .filter(|p| p.account == LedgerAccount::Wallet(self.wallet_id))
+            .map(|p| p.amount.amount)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_wallet;
+
+    fn aud(amount: i64) -> Money {
+        Money { amount: Decimal::from(amount), currency: Currency::AUD }
+    }
+
+    #[test]
+    fn accounts_round_trip_through_their_names() {
+        let game = Uuid::new_v4();
+        for account in [LedgerAccount::Wallet(Uuid::new_v4()), LedgerAccount::House(Some(game)), LedgerAccount::House(None), LedgerAccount::Fees] {
+            assert_eq!(account.to_string().parse::<LedgerAccount>().unwrap(), account);
+        }
+        assert_eq!(serde_json::to_value(LedgerAccount::House(Some(game))).unwrap(), format!("house:{game}"));
+        assert!("bank:1".parse::<LedgerAccount>().is_err());
+    }
+
+    #[test]
+    fn operations_post_balanced_legs_to_wallet_house_and_fees() {
+        let wallet = test_wallet(Uuid::new_v4(), Decimal::from(90));
+        let game = Uuid::new_v4();
+        let cause = LedgerCause { game_id: Some(game), fee: Some(aud(1)), ..Default::default() };
+        let debit = LedgerEntry::operation(&wallet, 2, &WalletOperationType::Debit, &aud(10), &cause).unwrap();
+        assert!(debit.is_balanced());
+        assert_eq!(debit.wallet_delta(), Decimal::from(-10));
+        assert_eq!(
+            debit.postings,
+            vec![
+                Posting { account: LedgerAccount::Wallet(wallet.wallet_id.0), amount: aud(-10) },
+                Posting { account: LedgerAccount::House(Some(game)), amount: aud(9) },
+                Posting { account: LedgerAccount::Fees, amount: aud(1) },
+            ]
+        );
+        assert_eq!(debit.balance_after, aud(90));
+
+        let credit = LedgerEntry::operation(&wallet, 3, &WalletOperationType::Credit, &aud(5), &LedgerCause::default()).unwrap();
+        assert!(credit.is_balanced());
+        assert_eq!(credit.postings.len(), 2);
+        assert_eq!(credit.postings[1], Posting { account: LedgerAccount::House(None), amount: aud(-5) });
+
+        let negative = LedgerCause { fee: Some(aud(-1)), ..Default::default() };
+        assert!(LedgerEntry::operation(&wallet, 4, &WalletOperationType::Credit, &aud(5), &negative).is_err());
+    }
+
+    #[test]
+    fn refunds_reverse_every_leg_of_their_debit_once() {
+        let wallet = test_wallet(Uuid::new_v4(), Decimal::from(90));
+        let event = Uuid::new_v4();
+        let cause = LedgerCause { game_id: Some(Uuid::new_v4()), event_id: Some(event), fee: Some(aud(1)), ..Default::default() };
+        let debit = LedgerEntry::operation(&wallet, 1, &WalletOperationType::Debit, &aud(10), &cause).unwrap();
+        let payout = LedgerEntry::operation(&wallet, 2, &WalletOperationType::Credit, &aud(3), &cause).unwrap();
+        let mut entries = vec![debit.clone(), payout.clone()];
+        assert_eq!(LedgerEntry::unrefunded_debit(&entries, event), Some(&debit));
+        assert_eq!(LedgerEntry::unrefunded_debit(&entries, Uuid::new_v4()), None);
+        assert!(LedgerEntry::refund(&wallet, 3, &payout).is_err());
+
+        let refund = LedgerEntry::refund(&wallet, 3, &debit).unwrap();
+        assert!(refund.is_balanced());
+        assert_eq!((refund.kind, refund.event_id, refund.wallet_delta()), (LedgerEntryKind::Refund, Some(event), Decimal::from(10)));
+        assert_eq!(refund.postings[2], Posting { account: LedgerAccount::Fees, amount: aud(-1) });
+        entries.push(refund);
+        assert_eq!(LedgerEntry::unrefunded_debit(&entries, event), None);
+    }
+}
@@ -10,6 +10,8 @@ pub struct SessionMetrics {
     pub sessions_created: AtomicU64,
     pub sessions_completed: AtomicU64,
     pub sessions_playing: AtomicU64,
+    pub sessions_expired: AtomicU64,
+    pub sessions_aborted: AtomicU64,
 }
 
 impl SessionMetrics {
@@ -29,6 +31,14 @@ impl SessionMetrics {
         self.sessions_playing.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn record_session_expired(&self) {
+        self.sessions_expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_session_aborted(&self) {
+        self.sessions_aborted.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn get_sessions_created(&self) -> u64 {
         self.sessions_created.load(Ordering::Relaxed)
     }
@@ -36,6 +46,14 @@ impl SessionMetrics {
     pub fn get_sessions_completed(&self) -> u64 {
         self.sessions_completed.load(Ordering::Relaxed)
     }
+
+    pub fn get_sessions_expired(&self) -> u64 {
+        self.sessions_expired.load(Ordering::Relaxed)
+    }
+
+    pub fn get_sessions_aborted(&self) -> u64 {
+        self.sessions_aborted.load(Ordering::Relaxed)
+    }
 }
 
 /// Record request latency in milliseconds (for histogram/summary). No-op stub;
@@ -57,5 +75,7 @@ mod tests {
         assert_eq!(m.get_sessions_created(), 2);
         m.record_session_completed();
         assert_eq!(m.get_sessions_completed(), 1);
+        m.record_session_expired();
+        assert_eq!(m.get_sessions_expired(), 1);
     }
 }
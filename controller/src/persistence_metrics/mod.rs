@@ -1,9 +1,23 @@
-//! Persistence layer: in-memory implementations of SessionRepository and WalletRepository.
+//! Persistence layer: in-memory implementations of SessionRepository, GameRepository and
+//! WalletRepository, plus Postgres-backed stores in `postgres`.
 
-use crate::api::{Currency, Money, Session, SessionId, Wallet, WalletOperationType};
-use crate::app_state::{DomainError, SessionRepository, WalletRepository};
+mod postgres;
+
+pub use postgres::{PostgresGameStore, PostgresSessionStore, PostgresWalletStore};
+
+use crate::api::{
+    Game, Money, Session, SessionMetrics, SessionTransition, UpdateGameRequest, Wallet,
+    WalletAdminAction, WalletAuditRecord, WalletOperationType,
+};
+use crate::app_state::{
+    DomainError, GameRepository, SessionQuery, SessionRepository, TransitionCause,
+    WalletRepository,
+};
+use crate::ledger::{LedgerCause, LedgerEntry};
 use crate::state_engine::GameState;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
@@ -12,6 +26,7 @@ use uuid::Uuid;
 #[derive(Default)]
 pub struct InMemorySessionStore {
     inner: Mutex<HashMap<Uuid, Session>>,
+    transitions: Mutex<HashMap<Uuid, Vec<SessionTransition>>>,
 }
 
 impl InMemorySessionStore {
@@ -47,18 +62,150 @@ impl SessionRepository for InMemorySessionStore {
         Ok(self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?.get(&id).cloned())
     }
 
-    async fn update_state(&self, id: Uuid, state: GameState) -> Result<Session, DomainError> {
+    async fn update_state(
+        &self,
+        id: Uuid,
+        expected_version: u64,
+        state: GameState,
+        metrics: Option<SessionMetrics>,
+        cause: TransitionCause,
+    ) -> Result<Session, DomainError> {
         let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
         let session = guard.get_mut(&id).ok_or(DomainError::NotFound(id))?;
+        if session.version != expected_version {
+            return Err(DomainError::VersionConflict {
+                expected: expected_version,
+                current: session.version,
+            });
+        }
+        let transition = SessionTransition {
+            session_id: session.session_id,
+            from: session.state,
+            to: state,
+            action: cause.action,
+            actor: cause.actor,
+            version: session.version + 1,
+            timestamp: chrono::Utc::now(),
+            event_id: cause.event_id,
+            request: cause.request,
+        };
+        // Taken while the session lock is held, so concurrent writers append in version order.
+        self.transitions
+            .lock()
+            .map_err(|e| DomainError::Internal(e.to_string()))?
+            .entry(id)
+            .or_default()
+            .push(transition);
         session.state = state;
+        if let Some(metrics) = metrics {
+            session.metrics = metrics;
+        }
+        session.version += 1;
+        session.updated_at = Some(chrono::Utc::now());
         Ok(session.clone())
     }
+
+    async fn list_by_game(&self, game_id: Uuid) -> Result<Vec<Session>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(guard.values().filter(|s| s.game_id.0 == game_id).cloned().collect())
+    }
+
+    async fn list_idle(&self, cutoff: DateTime<Utc>) -> Result<Vec<Session>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(guard
+            .values()
+            .filter(|s| !s.state.is_terminal() && s.updated_at.is_some_and(|t| t < cutoff))
+            .cloned()
+            .collect())
+    }
+
+    async fn list_active(&self) -> Result<Vec<Session>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(guard.values().filter(|s| !s.state.is_terminal()).cloned().collect())
+    }
+
+    async fn list(&self, query: &SessionQuery) -> Result<Vec<Session>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut sessions: Vec<Session> = guard.values().filter(|s| query.matches(s)).cloned().collect();
+        query.sort(&mut sessions);
+        sessions.truncate(query.limit as usize);
+        Ok(sessions)
+    }
+
+    async fn list_transitions(&self, id: Uuid) -> Result<Vec<SessionTransition>, DomainError> {
+        let guard = self.transitions.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(guard.get(&id).cloned().unwrap_or_default())
+    }
+}
+
+/// In-memory game registry (thread-safe).
+#[derive(Default)]
+pub struct InMemoryGameStore {
+    inner: Mutex<HashMap<Uuid, Game>>,
+}
+
+impl InMemoryGameStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed a game for tests.
+    pub fn seed(&self, game: Game) {
+        self.inner.lock().unwrap().insert(game.game_id.0, game);
+    }
+}
+
+#[async_trait]
+impl GameRepository for InMemoryGameStore {
+    async fn create(&self, game: Game) -> Result<(), DomainError> {
+        let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        if guard.contains_key(&game.game_id.0) {
+            return Err(DomainError::Conflict(format!("game {} already exists", game.game_id)));
+        }
+        guard.insert(game.game_id.0, game);
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Game>, DomainError> {
+        Ok(self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?.get(&id).cloned())
+    }
+
+    async fn list(&self, limit: u32, offset: u32) -> Result<Vec<Game>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut games: Vec<Game> = guard.values().cloned().collect();
+        games.sort_by_key(|g| (g.created_at, g.game_id.0));
+        Ok(games
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn update(&self, id: Uuid, patch: UpdateGameRequest) -> Result<Game, DomainError> {
+        let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let game = guard.get_mut(&id).ok_or(DomainError::NotFound(id))?;
+        if let Some(name) = patch.name {
+            game.name = name;
+        }
+        if let Some(v) = patch.rng_signature {
+            game.rng_signature = v;
+        }
+        if let Some(v) = patch.symbol_map {
+            game.symbol_map = v;
+        }
+        if let Some(v) = patch.statistical_profile {
+            game.statistical_profile = v;
+        }
+        Ok(game.clone())
+    }
 }
 
-/// In-memory wallet store (thread-safe).
+/// In-memory wallet store (thread-safe). Locks `inner` before `ledger` or `audit`.
 #[derive(Default)]
 pub struct InMemoryWalletStore {
     inner: Mutex<HashMap<Uuid, Wallet>>,
+    ledger: Mutex<HashMap<Uuid, Vec<LedgerEntry>>>,
+    audit: Mutex<HashMap<Uuid, Vec<WalletAuditRecord>>>,
 }
 
 impl InMemoryWalletStore {
@@ -68,14 +215,26 @@ impl InMemoryWalletStore {
 
     /// Seed a wallet for tests.
     pub fn seed(&self, wallet: Wallet) {
-        self.inner.lock().unwrap().insert(wallet.wallet_id.0, wallet);
+        self.insert(wallet).unwrap();
+    }
+
+    fn insert(&self, wallet: Wallet) -> Result<(), DomainError> {
+        let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        if guard.contains_key(&wallet.wallet_id.0) {
+            return Err(DomainError::Conflict(format!("wallet {} already exists", wallet.wallet_id)));
+        }
+        ledger.insert(wallet.wallet_id.0, vec![LedgerEntry::opening(&wallet, 1, &wallet.balance)]);
+        guard.insert(wallet.wallet_id.0, wallet);
+        Ok(())
     }
 }
 
 #[async_trait]
 impl WalletRepository for InMemoryWalletStore {
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Wallet>, DomainError> {
-        Ok(self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?.get(&id).cloned())
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(guard.get(&id).map(|w| w.at(Utc::now())))
     }
 
     async fn apply_operation(
@@ -83,54 +242,126 @@ impl WalletRepository for InMemoryWalletStore {
         wallet_id: Uuid,
         operation: WalletOperationType,
         amount: Money,
+        cause: LedgerCause,
     ) -> Result<Wallet, DomainError> {
         let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
         let wallet = guard.get_mut(&wallet_id).ok_or(DomainError::NotFound(wallet_id))?;
-
-        match operation {
-            WalletOperationType::Debit => {
-                // Check balance
-                if wallet.balance.amount < amount.amount {
-                    return Err(DomainError::WalletLimitExceeded);
-                }
-                // Check daily limit
-                if wallet.daily_spent.amount + amount.amount > wallet.daily_limit.amount {
-                    return Err(DomainError::WalletLimitExceeded);
-                }
-                wallet.balance.amount -= amount.amount;
-                wallet.daily_spent.amount += amount.amount;
-            }
-            WalletOperationType::Credit => {
-                wallet.balance.amount += amount.amount;
-            }
-        }
+        let next = wallet.apply(&operation, &amount, Utc::now())?;
+        let entries = ledger.entry(wallet_id).or_default();
+        let entry = LedgerEntry::operation(&next, entries.len() as u64 + 1, &operation, &amount, &cause)?;
+        entries.push(entry);
+        *wallet = next;
         Ok(wallet.clone())
     }
 
+    async fn refund_debit(&self, wallet_id: Uuid, event_id: Uuid) -> Result<Option<Wallet>, DomainError> {
+        let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let wallet = guard.get_mut(&wallet_id).ok_or(DomainError::NotFound(wallet_id))?;
+        let entries = ledger.entry(wallet_id).or_default();
+        let Some(debit) = LedgerEntry::unrefunded_debit(entries, event_id) else { return Ok(None) };
+        let amount = Money { amount: -debit.wallet_delta(), currency: wallet.balance.currency };
+        let next = wallet.refund(&amount, debit.created_at, Utc::now())?;
+        let entry = LedgerEntry::refund(&next, entries.len() as u64 + 1, debit)?;
+        entries.push(entry);
+        *wallet = next;
+        Ok(Some(wallet.clone()))
+    }
+
     async fn create(&self, wallet: Wallet) -> Result<(), DomainError> {
-        self.inner
-            .lock()
-            .map_err(|e| DomainError::Internal(e.to_string()))?
-            .insert(wallet.wallet_id.0, wallet);
-        Ok(())
+        self.insert(wallet)
     }
-}
 
-/// Helper: build a test wallet with a given balance.
-pub fn test_wallet(id: Uuid, balance: f64) -> Wallet {
-    let currency = Currency::AUD;
-    Wallet {
-        wallet_id: SessionId(id),
-        balance: Money { amount: balance, currency },
-        daily_limit: Money { amount: 1000.0, currency },
-        daily_spent: Money { amount: 0.0, currency },
+    async fn list_transactions(
+        &self,
+        wallet_id: Uuid,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<LedgerEntry>, DomainError> {
+        let ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(ledger
+            .get(&wallet_id)
+            .into_iter()
+            .flatten()
+            .rev()
+            .filter(|e| before.is_none_or(|b| e.sequence < b))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_session_entries(&self, wallet_id: Uuid, session_id: Uuid) -> Result<Vec<LedgerEntry>, DomainError> {
+        let ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(ledger
+            .get(&wallet_id)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.session_id == Some(session_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn ledger_balance(&self, wallet_id: Uuid) -> Result<Decimal, DomainError> {
+        let ledger = self.ledger.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(ledger.get(&wallet_id).into_iter().flatten().map(LedgerEntry::wallet_delta).sum())
+    }
+
+    async fn list(&self, after: Option<Uuid>, limit: u32) -> Result<Vec<Wallet>, DomainError> {
+        let guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let now = Utc::now();
+        let mut wallets: Vec<Wallet> = guard
+            .values()
+            .filter(|w| after.is_none_or(|a| w.wallet_id.0 > a))
+            .map(|w| w.at(now))
+            .collect();
+        wallets.sort_by_key(|w| w.wallet_id.0);
+        wallets.truncate(limit as usize);
+        Ok(wallets)
+    }
+
+    async fn administer(
+        &self,
+        wallet_id: Uuid,
+        action: WalletAdminAction,
+        actor: String,
+        reason: Option<String>,
+    ) -> Result<Wallet, DomainError> {
+        let mut guard = self.inner.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let mut audit = self.audit.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        let wallet = guard.get_mut(&wallet_id).ok_or(DomainError::NotFound(wallet_id))?;
+        let now = Utc::now();
+        let before = wallet.at(now);
+        let after = before.administer(&action)?;
+        audit.entry(wallet_id).or_default().push(WalletAuditRecord {
+            wallet_id,
+            action,
+            actor,
+            reason,
+            before,
+            after: after.clone(),
+            timestamp: now,
+        });
+        *wallet = after.clone();
+        Ok(after)
+    }
+
+    async fn list_audit(&self, wallet_id: Uuid) -> Result<Vec<WalletAuditRecord>, DomainError> {
+        let audit = self.audit.lock().map_err(|e| DomainError::Internal(e.to_string()))?;
+        Ok(audit.get(&wallet_id).cloned().unwrap_or_default())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::{GameId, SessionMetrics};
+    use crate::api::{Currency, GameId, SessionId};
+    use crate::ledger::LedgerEntryKind;
+    use crate::test_support::{test_game, test_wallet};
+
+    fn cause(action: &str) -> TransitionCause {
+        TransitionCause { action: action.into(), actor: Some("key-1".into()), ..Default::default() }
+    }
 
     fn make_session(id: Uuid) -> Session {
         Session {
@@ -138,6 +369,12 @@ mod tests {
             game_id: GameId(Uuid::new_v4()),
             state: GameState::Initialized,
             metrics: SessionMetrics::default(),
+            player_profile: Default::default(),
+            wallet_id: None,
+            version: 1,
+            updated_at: Some(chrono::Utc::now()),
+            created_at: Some(chrono::Utc::now()),
+            tags: Vec::new(),
         }
     }
 
@@ -157,50 +394,240 @@ mod tests {
         let store = InMemorySessionStore::new();
         let id = Uuid::new_v4();
         store.create(make_session(id)).await.unwrap();
-        let updated = store.update_state(id, GameState::Playing).await.unwrap();
+        let updated = store.update_state(id, 1, GameState::Playing, None, cause("PlaceBet")).await.unwrap();
         assert_eq!(updated.state, GameState::Playing);
+        assert_eq!(updated.version, 2);
+    }
+
+    #[tokio::test]
+    async fn update_state_with_stale_version_conflicts() {
+        let store = InMemorySessionStore::new();
+        let id = Uuid::new_v4();
+        store.create(make_session(id)).await.unwrap();
+        store.update_state(id, 1, GameState::Playing, None, cause("PlaceBet")).await.unwrap();
+        let result = store.update_state(id, 1, GameState::Evaluating, None, cause("Spin")).await;
+        assert!(matches!(result, Err(DomainError::VersionConflict { expected: 1, current: 2 })));
+        assert_eq!(store.get(id).unwrap().state, GameState::Playing);
+    }
+
+    #[tokio::test]
+    async fn update_state_records_transitions_in_order() {
+        let store = InMemorySessionStore::new();
+        let id = Uuid::new_v4();
+        store.create(make_session(id)).await.unwrap();
+        store.update_state(id, 1, GameState::Playing, None, cause("PlaceBet")).await.unwrap();
+        // A lost compare-and-swap leaves no trace.
+        let _ = store.update_state(id, 1, GameState::Evaluating, None, cause("Spin")).await;
+        store.update_state(id, 2, GameState::Evaluating, None, cause("Spin")).await.unwrap();
+        let history = store.list_transitions(id).await.unwrap();
+        let steps: Vec<_> = history.iter().map(|t| (t.from, t.to, t.action.as_str(), t.version)).collect();
+        assert_eq!(
+            steps,
+            vec![
+                (GameState::Initialized, GameState::Playing, "PlaceBet", 2),
+                (GameState::Playing, GameState::Evaluating, "Spin", 3),
+            ]
+        );
+        assert_eq!(history[0].actor.as_deref(), Some("key-1"));
+        assert!(store.list_transitions(Uuid::new_v4()).await.unwrap().is_empty());
     }
 
     #[tokio::test]
     async fn update_state_unknown_id_returns_not_found() {
         let store = InMemorySessionStore::new();
         let id = Uuid::new_v4();
-        let result = store.update_state(id, GameState::Playing).await;
+        let result = store.update_state(id, 1, GameState::Playing, None, cause("PlaceBet")).await;
         assert!(matches!(result, Err(DomainError::NotFound(_))));
     }
 
+    #[tokio::test]
+    async fn list_idle_skips_recent_and_terminal_sessions() {
+        let store = InMemorySessionStore::new();
+        let hour_ago = chrono::Utc::now() - chrono::Duration::hours(1);
+        let mut stale = make_session(Uuid::new_v4());
+        stale.updated_at = Some(hour_ago);
+        let mut done = make_session(Uuid::new_v4());
+        done.updated_at = Some(hour_ago);
+        done.state = GameState::Completed;
+        store.create(stale.clone()).await.unwrap();
+        store.create(done).await.unwrap();
+        store.create(make_session(Uuid::new_v4())).await.unwrap();
+        let cutoff = chrono::Utc::now() - chrono::Duration::minutes(30);
+        let idle = store.list_idle(cutoff).await.unwrap();
+        assert_eq!(idle.len(), 1);
+        assert_eq!(idle[0].session_id, stale.session_id);
+        // Every live session is active, however recently it changed.
+        assert_eq!(store.list_active().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn list_by_game_returns_only_that_games_sessions() {
+        let store = InMemorySessionStore::new();
+        let a = make_session(Uuid::new_v4());
+        let game_id = a.game_id.0;
+        store.create(a).await.unwrap();
+        store.create(make_session(Uuid::new_v4())).await.unwrap();
+        let sessions = store.list_by_game(game_id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].game_id.0, game_id);
+    }
+
+    #[tokio::test]
+    async fn list_filters_and_pages_by_cursor() {
+        use crate::app_state::{SessionCursor, SortOrder};
+
+        let store = InMemorySessionStore::new();
+        let start = chrono::Utc::now() - chrono::Duration::hours(1);
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let mut s = make_session(Uuid::new_v4());
+            // Two sessions share each creation time; the id breaks the tie.
+            s.created_at = Some(start + chrono::Duration::minutes(i / 2));
+            if i % 2 == 0 {
+                s.tags = vec!["bot".into(), "eu".into()];
+            }
+            ids.push((s.created_at.unwrap(), s.session_id.0));
+            store.create(s).await.unwrap();
+        }
+        let mut done = make_session(Uuid::new_v4());
+        done.state = GameState::Completed;
+        store.create(done).await.unwrap();
+        ids.sort();
+
+        let mut query = SessionQuery {
+            state: Some(GameState::Initialized),
+            order: SortOrder::Asc,
+            limit: 2,
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+        loop {
+            let page = store.list(&query).await.unwrap();
+            let Some(last) = page.last() else { break };
+            query.after = Some(SessionCursor::after(last, query.sort));
+            seen.extend(page.iter().map(|s| (s.created_at.unwrap(), s.session_id.0)));
+        }
+        assert_eq!(seen, ids);
+
+        let tagged = SessionQuery {
+            tags: vec!["eu".into(), "bot".into()],
+            created_to: Some(start + chrono::Duration::minutes(2)),
+            limit: 10,
+            ..Default::default()
+        };
+        let sessions = store.list(&tagged).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[0].created_at > sessions[1].created_at);
+    }
+
     #[tokio::test]
     async fn debit_reduces_balance() {
         let store = InMemoryWalletStore::new();
         let id = Uuid::new_v4();
-        store.seed(test_wallet(id, 100.0));
+        store.seed(test_wallet(id, Decimal::from(100)));
         let wallet = store
-            .apply_operation(id, WalletOperationType::Debit, Money { amount: 10.0, currency: Currency::AUD })
+            .apply_operation(id, WalletOperationType::Debit, Money { amount: Decimal::new(1010, 2), currency: Currency::AUD }, LedgerCause::default())
             .await
             .unwrap();
-        assert!((wallet.balance.amount - 90.0).abs() < 0.001);
+        assert_eq!(wallet.balance.amount, Decimal::new(8990, 2));
+        assert_eq!(wallet.daily_spent.amount, Decimal::new(1010, 2));
     }
 
     #[tokio::test]
     async fn debit_exceeding_balance_returns_wallet_limit_exceeded() {
         let store = InMemoryWalletStore::new();
         let id = Uuid::new_v4();
-        store.seed(test_wallet(id, 5.0));
+        store.seed(test_wallet(id, Decimal::from(5)));
         let result = store
-            .apply_operation(id, WalletOperationType::Debit, Money { amount: 10.0, currency: Currency::AUD })
+            .apply_operation(id, WalletOperationType::Debit, Money { amount: Decimal::from(10), currency: Currency::AUD }, LedgerCause::default())
             .await;
         assert!(matches!(result, Err(DomainError::WalletLimitExceeded)));
+        let result = store
+            .apply_operation(id, WalletOperationType::Debit, Money { amount: Decimal::ONE, currency: Currency::USD }, LedgerCause::default())
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        assert_eq!(store.get_by_id(id).await.unwrap().unwrap().balance.amount, Decimal::from(5));
     }
 
     #[tokio::test]
     async fn credit_increases_balance() {
         let store = InMemoryWalletStore::new();
         let id = Uuid::new_v4();
-        store.seed(test_wallet(id, 50.0));
+        store.seed(test_wallet(id, Decimal::from(50)));
         let wallet = store
-            .apply_operation(id, WalletOperationType::Credit, Money { amount: 25.0, currency: Currency::AUD })
+            .apply_operation(id, WalletOperationType::Credit, Money { amount: Decimal::from(25), currency: Currency::AUD }, LedgerCause::default())
             .await
             .unwrap();
-        assert!((wallet.balance.amount - 75.0).abs() < 0.001);
+        assert_eq!(wallet.balance.amount, Decimal::from(75));
+    }
+
+    #[tokio::test]
+    async fn operations_are_booked_in_the_ledger() {
+        let store = InMemoryWalletStore::new();
+        let id = Uuid::new_v4();
+        store.create(test_wallet(id, Decimal::from(50))).await.unwrap();
+        let aud = |amount: i64| Money { amount: Decimal::from(amount), currency: Currency::AUD };
+        let session = Uuid::new_v4();
+        let cause = LedgerCause { session_id: Some(session), ..Default::default() };
+        store.apply_operation(id, WalletOperationType::Debit, aud(20), cause).await.unwrap();
+        store.apply_operation(id, WalletOperationType::Credit, aud(5), LedgerCause::default()).await.unwrap();
+        // A refused operation books nothing.
+        assert!(store.apply_operation(id, WalletOperationType::Debit, aud(100), LedgerCause::default()).await.is_err());
+        // Creating it again is refused and books nothing.
+        assert!(matches!(store.create(test_wallet(id, Decimal::from(500))).await, Err(DomainError::Conflict(_))));
+
+        let entries = store.list_transactions(id, None, 10).await.unwrap();
+        let sequences: Vec<u64> = entries.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![3, 2, 1]);
+        assert!(entries.iter().all(LedgerEntry::is_balanced));
+        assert_eq!(entries[1].session_id, Some(session));
+        assert_eq!(entries[1].balance_after, aud(30));
+        assert_eq!(entries[0].wallet_delta(), Decimal::from(5));
+        assert_eq!(entries[2].kind, LedgerEntryKind::Opening);
+        assert_eq!(store.ledger_balance(id).await.unwrap(), Decimal::from(35));
+        assert_eq!(store.get_by_id(id).await.unwrap().unwrap().balance, aud(35));
+
+        let older = store.list_transactions(id, Some(3), 1).await.unwrap();
+        assert_eq!(older[0].sequence, 2);
+    }
+
+    #[tokio::test]
+    async fn game_create_rejects_duplicate_id() {
+        let store = InMemoryGameStore::new();
+        let id = Uuid::new_v4();
+        store.create(test_game(id, "Classic")).await.unwrap();
+        let result = store.create(test_game(id, "Again")).await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn game_update_applies_only_present_fields() {
+        let store = InMemoryGameStore::new();
+        let id = Uuid::new_v4();
+        store.seed(test_game(id, "Classic"));
+        let patch = UpdateGameRequest {
+            name: Some("Classic Deluxe".into()),
+            symbol_map: Some(serde_json::json!({"Cherry": 0.25})),
+            ..Default::default()
+        };
+        let updated = store.update(id, patch).await.unwrap();
+        assert_eq!(updated.name, "Classic Deluxe");
+        assert_eq!(updated.symbol_map["Cherry"], 0.25);
+        assert_eq!(updated.rng_signature, serde_json::json!({}));
+        let missing = store.update(Uuid::new_v4(), UpdateGameRequest::default()).await;
+        assert!(matches!(missing, Err(DomainError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn game_list_pages_in_creation_order() {
+        let store = InMemoryGameStore::new();
+        for i in 0..3 {
+            let mut game = test_game(Uuid::new_v4(), &format!("g{i}"));
+            game.created_at = chrono::DateTime::from_timestamp(1_700_000_000 + i, 0).unwrap();
+            store.seed(game);
+        }
+        let page = store.list(2, 1).await.unwrap();
+        let names: Vec<_> = page.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["g1", "g2"]);
     }
 }
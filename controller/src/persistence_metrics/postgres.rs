@@ -0,0 +1,1082 @@
+//! Postgres-backed repositories (tables from `database/migrations`).
+
+use crate::api::{
+    Currency, Game, GameId, Money, PlayerProfile, Session, SessionId, SessionMetrics, SessionTransition,
+    UpdateGameRequest, Wallet, WalletAdminAction, WalletAuditRecord, WalletOperationType,
+};
+use crate::app_state::{
+    DomainError, GameRepository, SessionQuery, SessionRepository, SessionSort, SortOrder,
+    TransitionCause, WalletRepository,
+};
+use crate::ledger::{LedgerAccount, LedgerCause, LedgerEntry, Posting};
+use crate::state_engine::GameState;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::Internal(e.to_string())
+}
+
+/// Decodes a TEXT column holding a serde unit variant (`GameState`, `Currency`).
+fn parse_variant<T: DeserializeOwned>(s: &str) -> Result<T, DomainError> {
+    serde_json::from_value(serde_json::Value::String(s.to_string()))
+        .map_err(|e| DomainError::Internal(format!("unexpected value {s:?}: {e}")))
+}
+
+fn variant_name<T: serde::Serialize>(v: &T) -> String {
+    serde_json::to_value(v)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[derive(sqlx::FromRow)]
+struct GameRow {
+    game_id: Uuid,
+    name: String,
+    rng_signature: serde_json::Value,
+    symbol_map: serde_json::Value,
+    statistical_profile: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<GameRow> for Game {
+    fn from(r: GameRow) -> Self {
+        Game {
+            game_id: GameId(r.game_id),
+            name: r.name,
+            rng_signature: r.rng_signature,
+            symbol_map: r.symbol_map,
+            statistical_profile: r.statistical_profile,
+            created_at: r.created_at,
+        }
+    }
+}
+
+const GAME_COLUMNS: &str = "game_id, name, rng_signature, symbol_map, statistical_profile, created_at";
+
+/// Game registry persisted in the `games` table.
+pub struct PostgresGameStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresGameStore {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GameRepository for PostgresGameStore {
+    async fn create(&self, game: Game) -> Result<(), DomainError> {
+        let inserted = sqlx::query(
+            "INSERT INTO games (game_id, name, rng_signature, symbol_map, statistical_profile, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (game_id) DO NOTHING",
+        )
+        .bind(game.game_id.0)
+        .bind(&game.name)
+        .bind(&game.rng_signature)
+        .bind(&game.symbol_map)
+        .bind(&game.statistical_profile)
+        .bind(game.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+        if inserted.rows_affected() == 0 {
+            return Err(DomainError::Conflict(format!("game {} already exists", game.game_id)));
+        }
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Game>, DomainError> {
+        let row: Option<GameRow> =
+            sqlx::query_as(&format!("SELECT {GAME_COLUMNS} FROM games WHERE game_id = $1"))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+        Ok(row.map(Game::from))
+    }
+
+    async fn list(&self, limit: u32, offset: u32) -> Result<Vec<Game>, DomainError> {
+        let rows: Vec<GameRow> = sqlx::query_as(&format!(
+            "SELECT {GAME_COLUMNS} FROM games ORDER BY created_at ASC, game_id ASC LIMIT $1 OFFSET $2"
+        ))
+        .bind(i64::from(limit))
+        .bind(i64::from(offset))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(rows.into_iter().map(Game::from).collect())
+    }
+
+    async fn update(&self, id: Uuid, patch: UpdateGameRequest) -> Result<Game, DomainError> {
+        let row: Option<GameRow> = sqlx::query_as(&format!(
+            "UPDATE games SET
+                 name = COALESCE($2, name),
+                 rng_signature = COALESCE($3, rng_signature),
+                 symbol_map = COALESCE($4, symbol_map),
+                 statistical_profile = COALESCE($5, statistical_profile)
+             WHERE game_id = $1
+             RETURNING {GAME_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(patch.name)
+        .bind(patch.rng_signature)
+        .bind(patch.symbol_map)
+        .bind(patch.statistical_profile)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        row.map(Game::from).ok_or(DomainError::NotFound(id))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionRow {
+    session_id: Uuid,
+    game_id: Uuid,
+    state: String,
+    metrics: serde_json::Value,
+    player_profile: serde_json::Value,
+    wallet_id: Option<Uuid>,
+    version: i64,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    tags: Vec<String>,
+}
+
+impl TryFrom<SessionRow> for Session {
+    type Error = DomainError;
+
+    fn try_from(r: SessionRow) -> Result<Self, DomainError> {
+        Ok(Session {
+            session_id: SessionId(r.session_id),
+            game_id: GameId(r.game_id),
+            state: parse_variant(&r.state)?,
+            metrics: serde_json::from_value::<SessionMetrics>(r.metrics).unwrap_or_default(),
+            // Rows written before profiles were stored hold '{}'.
+            player_profile: serde_json::from_value::<PlayerProfile>(r.player_profile).unwrap_or_default(),
+            wallet_id: r.wallet_id,
+            version: r.version as u64,
+            updated_at: Some(r.updated_at),
+            created_at: Some(r.created_at),
+            tags: r.tags,
+        })
+    }
+}
+
+const SESSION_COLUMNS: &str =
+    "session_id, game_id, state::TEXT AS state, metrics, player_profile, current_wallet_id AS wallet_id, version, updated_at, created_at, tags";
+
+/// Sessions persisted in the `sessions` table.
+pub struct PostgresSessionStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresSessionStore {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionStore {
+    async fn create(&self, session: Session) -> Result<(), DomainError> {
+        let metrics = serde_json::to_value(&session.metrics).map_err(|e| DomainError::Internal(e.to_string()))?;
+        let profile =
+            serde_json::to_value(&session.player_profile).map_err(|e| DomainError::Internal(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO sessions
+                 (session_id, game_id, player_profile, state, metrics, version, updated_at, created_at, tags,
+                  current_wallet_id)
+             VALUES ($1, $2, $9, $3::game_state, $4, $5, COALESCE($6, NOW()), COALESCE($7, NOW()), $8, $10)",
+        )
+        .bind(session.session_id.0)
+        .bind(session.game_id.0)
+        .bind(variant_name(&session.state))
+        .bind(metrics)
+        .bind(session.version as i64)
+        .bind(session.updated_at)
+        .bind(session.created_at)
+        .bind(&session.tags)
+        .bind(profile)
+        .bind(session.wallet_id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Session>, DomainError> {
+        let row: Option<SessionRow> =
+            sqlx::query_as(&format!("SELECT {SESSION_COLUMNS} FROM sessions WHERE session_id = $1"))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+        row.map(Session::try_from).transpose()
+    }
+
+    async fn update_state(
+        &self,
+        id: Uuid,
+        expected_version: u64,
+        state: GameState,
+        metrics: Option<SessionMetrics>,
+        cause: TransitionCause,
+    ) -> Result<Session, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let current: Option<(String, i64)> = sqlx::query_as(
+            "SELECT state::TEXT, version FROM sessions WHERE session_id = $1 FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let (from, version) = current.ok_or(DomainError::NotFound(id))?;
+        if version as u64 != expected_version {
+            return Err(DomainError::VersionConflict {
+                expected: expected_version,
+                current: version as u64,
+            });
+        }
+
+        let metrics = metrics
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| DomainError::Internal(e.to_string()))?;
+        let row: SessionRow = sqlx::query_as(&format!(
+            "UPDATE sessions SET state = $2::game_state, metrics = COALESCE($3, metrics), version = version + 1
+             WHERE session_id = $1
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(variant_name(&state))
+        .bind(metrics)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "INSERT INTO session_transitions
+                 (session_id, version, from_state, to_state, action, actor, event_id, request)
+             VALUES ($1, $2, $3::game_state, $4::game_state, $5, $6, $7, $8)",
+        )
+        .bind(id)
+        .bind(row.version)
+        .bind(from)
+        .bind(variant_name(&state))
+        .bind(cause.action)
+        .bind(cause.actor)
+        .bind(cause.event_id)
+        .bind(cause.request)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+        row.try_into()
+    }
+
+    async fn list_by_game(&self, game_id: Uuid) -> Result<Vec<Session>, DomainError> {
+        let rows: Vec<SessionRow> = sqlx::query_as(&format!(
+            "SELECT {SESSION_COLUMNS} FROM sessions WHERE game_id = $1 ORDER BY created_at ASC, session_id ASC"
+        ))
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(Session::try_from).collect()
+    }
+
+    async fn list_idle(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<Session>, DomainError> {
+        let rows: Vec<SessionRow> = sqlx::query_as(&format!(
+            "SELECT {SESSION_COLUMNS} FROM sessions
+             WHERE state NOT IN ('Completed', 'Expired', 'Aborted') AND updated_at < $1
+             ORDER BY updated_at ASC"
+        ))
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(Session::try_from).collect()
+    }
+
+    async fn list_active(&self) -> Result<Vec<Session>, DomainError> {
+        let rows: Vec<SessionRow> = sqlx::query_as(&format!(
+            "SELECT {SESSION_COLUMNS} FROM sessions
+             WHERE state NOT IN ('Completed', 'Expired', 'Aborted')
+             ORDER BY updated_at ASC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(Session::try_from).collect()
+    }
+
+    async fn list(&self, query: &SessionQuery) -> Result<Vec<Session>, DomainError> {
+        // Sort column and direction come from enums, never from client text.
+        let column = match query.sort {
+            SessionSort::CreatedAt => "created_at",
+            SessionSort::UpdatedAt => "updated_at",
+        };
+        let (direction, past) = match query.order {
+            SortOrder::Asc => ("ASC", ">"),
+            SortOrder::Desc => ("DESC", "<"),
+        };
+        let rows: Vec<SessionRow> = sqlx::query_as(&format!(
+            "SELECT {SESSION_COLUMNS} FROM sessions
+             WHERE ($1::TEXT IS NULL OR state::TEXT = $1)
+               AND ($2::UUID IS NULL OR game_id = $2)
+               AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3)
+               AND ($4::TIMESTAMPTZ IS NULL OR created_at < $4)
+               AND tags @> $5
+               AND ($6::TIMESTAMPTZ IS NULL OR ({column}, session_id) {past} ($6, $7))
+             ORDER BY {column} {direction}, session_id {direction}
+             LIMIT $8"
+        ))
+        .bind(query.state.as_ref().map(variant_name))
+        .bind(query.game_id)
+        .bind(query.created_from)
+        .bind(query.created_to)
+        .bind(&query.tags)
+        .bind(query.after.map(|c| c.at))
+        .bind(query.after.map(|c| c.session_id))
+        .bind(i64::from(query.limit))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(Session::try_from).collect()
+    }
+
+    async fn list_transitions(&self, id: Uuid) -> Result<Vec<SessionTransition>, DomainError> {
+        let rows: Vec<TransitionRow> = sqlx::query_as(
+            "SELECT session_id, version, from_state::TEXT AS from_state, to_state::TEXT AS to_state,
+                    action, actor, timestamp, event_id, request
+             FROM session_transitions WHERE session_id = $1 ORDER BY version ASC",
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(SessionTransition::try_from).collect()
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TransitionRow {
+    session_id: Uuid,
+    version: i64,
+    from_state: String,
+    to_state: String,
+    action: String,
+    actor: Option<String>,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    event_id: Option<Uuid>,
+    request: Option<serde_json::Value>,
+}
+
+impl TryFrom<TransitionRow> for SessionTransition {
+    type Error = DomainError;
+
+    fn try_from(r: TransitionRow) -> Result<Self, DomainError> {
+        Ok(SessionTransition {
+            session_id: SessionId(r.session_id),
+            from: parse_variant(&r.from_state)?,
+            to: parse_variant(&r.to_state)?,
+            action: r.action,
+            actor: r.actor,
+            version: r.version as u64,
+            timestamp: r.timestamp,
+            event_id: r.event_id,
+            request: r.request,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WalletRow {
+    wallet_id: Uuid,
+    currency: String,
+    balance: Decimal,
+    daily_limit: Decimal,
+    daily_spent: Decimal,
+    weekly_limit: Option<Decimal>,
+    weekly_spent: Decimal,
+    monthly_limit: Option<Decimal>,
+    monthly_spent: Decimal,
+    timezone: String,
+    spent_at: Option<chrono::DateTime<chrono::Utc>>,
+    frozen: bool,
+}
+
+impl TryFrom<WalletRow> for Wallet {
+    type Error = DomainError;
+
+    fn try_from(r: WalletRow) -> Result<Self, DomainError> {
+        let currency: Currency = parse_variant(&r.currency)?;
+        Ok(Wallet {
+            wallet_id: SessionId(r.wallet_id),
+            balance: Money::new(r.balance, currency),
+            daily_limit: Money::new(r.daily_limit, currency),
+            daily_spent: Money::new(r.daily_spent, currency),
+            weekly_limit: r.weekly_limit.map(|l| Money::new(l, currency)),
+            weekly_spent: Money::new(r.weekly_spent, currency),
+            monthly_limit: r.monthly_limit.map(|l| Money::new(l, currency)),
+            monthly_spent: Money::new(r.monthly_spent, currency),
+            timezone: r
+                .timezone
+                .parse()
+                .map_err(|_| DomainError::Internal(format!("unexpected timezone {:?}", r.timezone)))?,
+            spent_at: r.spent_at,
+            frozen: r.frozen,
+        })
+    }
+}
+
+const WALLET_COLUMNS: &str = "wallet_id, currency, balance, daily_limit, daily_spent, weekly_limit, weekly_spent,
+     monthly_limit, monthly_spent, timezone, spent_at, frozen";
+
+/// Wallets persisted in the `wallets` table.
+pub struct PostgresWalletStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresWalletStore {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WalletRepository for PostgresWalletStore {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Wallet>, DomainError> {
+        let row: Option<WalletRow> =
+            sqlx::query_as(&format!("SELECT {WALLET_COLUMNS} FROM wallets WHERE wallet_id = $1"))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+        Ok(row.map(Wallet::try_from).transpose()?.map(|w| w.at(chrono::Utc::now())))
+    }
+
+    /// Locks the wallet row (`FOR UPDATE`) for the check-and-write, so concurrent debits
+    /// serialise and cannot overdraw the balance or daily limit; the new amounts are computed
+    /// by `Wallet::apply` and written back as they are.
+    async fn apply_operation(
+        &self,
+        wallet_id: Uuid,
+        operation: WalletOperationType,
+        amount: Money,
+        cause: LedgerCause,
+    ) -> Result<Wallet, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let row: Option<WalletRow> = sqlx::query_as(&format!(
+            "SELECT {WALLET_COLUMNS} FROM wallets WHERE wallet_id = $1 FOR UPDATE"
+        ))
+        .bind(wallet_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let wallet = Wallet::try_from(row.ok_or(DomainError::NotFound(wallet_id))?)?;
+
+        let next = wallet.apply(&operation, &amount, chrono::Utc::now())?;
+        let row: WalletRow = sqlx::query_as(&format!(
+            "UPDATE wallets
+             SET balance = $2, daily_spent = $3, weekly_spent = $4, monthly_spent = $5, spent_at = $6
+             WHERE wallet_id = $1
+             RETURNING {WALLET_COLUMNS}"
+        ))
+        .bind(wallet_id)
+        .bind(next.balance.amount)
+        .bind(next.daily_spent.amount)
+        .bind(next.weekly_spent.amount)
+        .bind(next.monthly_spent.amount)
+        .bind(next.spent_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let sequence = last_sequence(&mut tx, wallet_id).await? + 1;
+        insert_entry(&mut tx, &LedgerEntry::operation(&next, sequence, &operation, &amount, &cause)?).await?;
+        tx.commit().await.map_err(db_error)?;
+        row.try_into()
+    }
+
+    /// Locks the wallet row like `apply_operation`, so a refund racing its retry books once.
+    async fn refund_debit(&self, wallet_id: Uuid, event_id: Uuid) -> Result<Option<Wallet>, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let row: Option<WalletRow> = sqlx::query_as(&format!(
+            "SELECT {WALLET_COLUMNS} FROM wallets WHERE wallet_id = $1 FOR UPDATE"
+        ))
+        .bind(wallet_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let wallet = Wallet::try_from(row.ok_or(DomainError::NotFound(wallet_id))?)?;
+        let rows: Vec<LedgerEntryRow> = sqlx::query_as(&format!(
+            "SELECT {LEDGER_ENTRY_COLUMNS} FROM ledger_entries
+             WHERE wallet_id = $1 AND event_id = $2
+             ORDER BY sequence"
+        ))
+        .bind(wallet_id)
+        .bind(event_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let entries = with_postings(&mut tx, rows).await?;
+        let Some(debit) = LedgerEntry::unrefunded_debit(&entries, event_id) else { return Ok(None) };
+
+        let amount = Money { amount: -debit.wallet_delta(), currency: wallet.balance.currency };
+        let next = wallet.refund(&amount, debit.created_at, chrono::Utc::now())?;
+        let row: WalletRow = sqlx::query_as(&format!(
+            "UPDATE wallets
+             SET balance = $2, daily_spent = $3, weekly_spent = $4, monthly_spent = $5
+             WHERE wallet_id = $1
+             RETURNING {WALLET_COLUMNS}"
+        ))
+        .bind(wallet_id)
+        .bind(next.balance.amount)
+        .bind(next.daily_spent.amount)
+        .bind(next.weekly_spent.amount)
+        .bind(next.monthly_spent.amount)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let sequence = last_sequence(&mut tx, wallet_id).await? + 1;
+        insert_entry(&mut tx, &LedgerEntry::refund(&next, sequence, debit)?).await?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(Some(row.try_into()?))
+    }
+
+    /// Inserts the wallet and books its opening entry; Conflict, writing nothing, if its id is
+    /// taken.
+    async fn create(&self, wallet: Wallet) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let inserted = sqlx::query(
+            "INSERT INTO wallets
+                 (wallet_id, currency, balance, daily_limit, daily_spent, weekly_limit, weekly_spent,
+                  monthly_limit, monthly_spent, timezone, spent_at, frozen)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             ON CONFLICT (wallet_id) DO NOTHING",
+        )
+        .bind(wallet.wallet_id.0)
+        .bind(variant_name(&wallet.balance.currency))
+        .bind(wallet.balance.amount)
+        .bind(wallet.daily_limit.amount)
+        .bind(wallet.daily_spent.amount)
+        .bind(wallet.weekly_limit.as_ref().map(|l| l.amount))
+        .bind(wallet.weekly_spent.amount)
+        .bind(wallet.monthly_limit.as_ref().map(|l| l.amount))
+        .bind(wallet.monthly_spent.amount)
+        .bind(wallet.timezone.name())
+        .bind(wallet.spent_at)
+        .bind(wallet.frozen)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        if inserted.rows_affected() == 0 {
+            return Err(DomainError::Conflict(format!("wallet {} already exists", wallet.wallet_id)));
+        }
+        insert_entry(&mut tx, &LedgerEntry::opening(&wallet, 1, &wallet.balance)).await?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn list_transactions(
+        &self,
+        wallet_id: Uuid,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<LedgerEntry>, DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(db_error)?;
+        let rows: Vec<LedgerEntryRow> = sqlx::query_as(&format!(
+            "SELECT {LEDGER_ENTRY_COLUMNS} FROM ledger_entries
+             WHERE wallet_id = $1 AND ($2::BIGINT IS NULL OR sequence < $2)
+             ORDER BY sequence DESC
+             LIMIT $3"
+        ))
+        .bind(wallet_id)
+        .bind(before.map(|b| b as i64))
+        .bind(i64::from(limit))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(db_error)?;
+        with_postings(&mut conn, rows).await
+    }
+
+    async fn list_session_entries(&self, wallet_id: Uuid, session_id: Uuid) -> Result<Vec<LedgerEntry>, DomainError> {
+        let mut conn = self.pool.acquire().await.map_err(db_error)?;
+        let rows: Vec<LedgerEntryRow> = sqlx::query_as(&format!(
+            "SELECT {LEDGER_ENTRY_COLUMNS} FROM ledger_entries
+             WHERE wallet_id = $1 AND session_id = $2
+             ORDER BY sequence"
+        ))
+        .bind(wallet_id)
+        .bind(session_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(db_error)?;
+        with_postings(&mut conn, rows).await
+    }
+
+    async fn ledger_balance(&self, wallet_id: Uuid) -> Result<Decimal, DomainError> {
+        let (sum,): (Option<Decimal>,) =
+            sqlx::query_as("SELECT SUM(amount) FROM ledger_postings WHERE account = $1")
+                .bind(LedgerAccount::Wallet(wallet_id).to_string())
+                .fetch_one(&self.pool)
+                .await
+                .map_err(db_error)?;
+        Ok(sum.unwrap_or_default())
+    }
+
+    async fn list(&self, after: Option<Uuid>, limit: u32) -> Result<Vec<Wallet>, DomainError> {
+        let rows: Vec<WalletRow> = sqlx::query_as(&format!(
+            "SELECT {WALLET_COLUMNS} FROM wallets
+             WHERE ($1::UUID IS NULL OR wallet_id > $1)
+             ORDER BY wallet_id
+             LIMIT $2"
+        ))
+        .bind(after)
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        let now = chrono::Utc::now();
+        rows.into_iter().map(|r| Ok(Wallet::try_from(r)?.at(now))).collect()
+    }
+
+    /// Locks the wallet row like `apply_operation`, so the audited before/after are exact.
+    async fn administer(
+        &self,
+        wallet_id: Uuid,
+        action: WalletAdminAction,
+        actor: String,
+        reason: Option<String>,
+    ) -> Result<Wallet, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let row: Option<WalletRow> = sqlx::query_as(&format!(
+            "SELECT {WALLET_COLUMNS} FROM wallets WHERE wallet_id = $1 FOR UPDATE"
+        ))
+        .bind(wallet_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let now = chrono::Utc::now();
+        let before = Wallet::try_from(row.ok_or(DomainError::NotFound(wallet_id))?)?.at(now);
+        let after = before.administer(&action)?;
+        sqlx::query(
+            "UPDATE wallets
+             SET daily_limit = $2, daily_spent = $3, weekly_limit = $4, weekly_spent = $5,
+                 monthly_limit = $6, monthly_spent = $7, frozen = $8
+             WHERE wallet_id = $1",
+        )
+        .bind(wallet_id)
+        .bind(after.daily_limit.amount)
+        .bind(after.daily_spent.amount)
+        .bind(after.weekly_limit.as_ref().map(|l| l.amount))
+        .bind(after.weekly_spent.amount)
+        .bind(after.monthly_limit.as_ref().map(|l| l.amount))
+        .bind(after.monthly_spent.amount)
+        .bind(after.frozen)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let to_json = |v: serde_json::Result<serde_json::Value>| v.map_err(|e| DomainError::Internal(e.to_string()));
+        sqlx::query(
+            "INSERT INTO wallet_audit (wallet_id, action, actor, reason, before, after, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(wallet_id)
+        .bind(to_json(serde_json::to_value(&action))?)
+        .bind(&actor)
+        .bind(&reason)
+        .bind(to_json(serde_json::to_value(&before))?)
+        .bind(to_json(serde_json::to_value(&after))?)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(after)
+    }
+
+    async fn list_audit(&self, wallet_id: Uuid) -> Result<Vec<WalletAuditRecord>, DomainError> {
+        let rows: Vec<WalletAuditRow> = sqlx::query_as(
+            "SELECT wallet_id, action, actor, reason, before, after, created_at FROM wallet_audit
+             WHERE wallet_id = $1
+             ORDER BY audit_id",
+        )
+        .bind(wallet_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.into_iter().map(WalletAuditRecord::try_from).collect()
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WalletAuditRow {
+    wallet_id: Uuid,
+    action: serde_json::Value,
+    actor: String,
+    reason: Option<String>,
+    before: serde_json::Value,
+    after: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<WalletAuditRow> for WalletAuditRecord {
+    type Error = DomainError;
+
+    fn try_from(r: WalletAuditRow) -> Result<Self, DomainError> {
+        let decode = |e: serde_json::Error| DomainError::Internal(e.to_string());
+        Ok(WalletAuditRecord {
+            wallet_id: r.wallet_id,
+            action: serde_json::from_value(r.action).map_err(decode)?,
+            actor: r.actor,
+            reason: r.reason,
+            before: serde_json::from_value(r.before).map_err(decode)?,
+            after: serde_json::from_value(r.after).map_err(decode)?,
+            timestamp: r.created_at,
+        })
+    }
+}
+
+/// Highest ledger sequence of a wallet, 0 if it has no entries. Callers hold the wallet's row
+/// lock, so the next sequence is theirs alone.
+async fn last_sequence(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, wallet_id: Uuid) -> Result<u64, DomainError> {
+    let (last,): (i64,) =
+        sqlx::query_as("SELECT COALESCE(MAX(sequence), 0) FROM ledger_entries WHERE wallet_id = $1")
+            .bind(wallet_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(db_error)?;
+    Ok(last as u64)
+}
+
+async fn insert_entry(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, entry: &LedgerEntry) -> Result<(), DomainError> {
+    if !entry.is_balanced() {
+        return Err(DomainError::Internal(format!("unbalanced ledger entry {}", entry.entry_id)));
+    }
+    sqlx::query(
+        "INSERT INTO ledger_entries
+             (entry_id, wallet_id, sequence, kind, balance_after, currency, session_id, game_id, event_id, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+    )
+    .bind(entry.entry_id)
+    .bind(entry.wallet_id)
+    .bind(entry.sequence as i64)
+    .bind(variant_name(&entry.kind))
+    .bind(entry.balance_after.amount)
+    .bind(variant_name(&entry.balance_after.currency))
+    .bind(entry.session_id)
+    .bind(entry.game_id)
+    .bind(entry.event_id)
+    .bind(entry.created_at)
+    .execute(&mut **tx)
+    .await
+    .map_err(db_error)?;
+    for (position, posting) in entry.postings.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO ledger_postings (entry_id, position, account, amount, currency)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(entry.entry_id)
+        .bind(position as i16)
+        .bind(posting.account.to_string())
+        .bind(posting.amount.amount)
+        .bind(variant_name(&posting.amount.currency))
+        .execute(&mut **tx)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+const LEDGER_ENTRY_COLUMNS: &str =
+    "entry_id, wallet_id, sequence, kind, balance_after, currency, session_id, game_id, event_id, created_at";
+
+/// Loads the postings of `rows` and assembles their entries, in the order of `rows`.
+async fn with_postings(conn: &mut sqlx::PgConnection, rows: Vec<LedgerEntryRow>) -> Result<Vec<LedgerEntry>, DomainError> {
+    let ids: Vec<Uuid> = rows.iter().map(|r| r.entry_id).collect();
+    let postings: Vec<PostingRow> = sqlx::query_as(
+        "SELECT entry_id, account, amount, currency FROM ledger_postings
+         WHERE entry_id = ANY($1)
+         ORDER BY entry_id, position",
+    )
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(db_error)?;
+    let mut by_entry: HashMap<Uuid, Vec<Posting>> = HashMap::new();
+    for p in postings {
+        by_entry.entry(p.entry_id).or_default().push(p.try_into()?);
+    }
+    rows.into_iter()
+        .map(|r| {
+            let postings = by_entry.remove(&r.entry_id).unwrap_or_default();
+            r.into_entry(postings)
+        })
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct LedgerEntryRow {
+    entry_id: Uuid,
+    wallet_id: Uuid,
+    sequence: i64,
+    kind: String,
+    balance_after: Decimal,
+    currency: String,
+    session_id: Option<Uuid>,
+    game_id: Option<Uuid>,
+    event_id: Option<Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl LedgerEntryRow {
+    fn into_entry(self, postings: Vec<Posting>) -> Result<LedgerEntry, DomainError> {
+        Ok(LedgerEntry {
+            entry_id: self.entry_id,
+            wallet_id: self.wallet_id,
+            sequence: self.sequence as u64,
+            kind: parse_variant(&self.kind)?,
+            postings,
+            balance_after: Money::new(self.balance_after, parse_variant(&self.currency)?),
+            session_id: self.session_id,
+            game_id: self.game_id,
+            event_id: self.event_id,
+            created_at: self.created_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PostingRow {
+    entry_id: Uuid,
+    account: String,
+    amount: Decimal,
+    currency: String,
+}
+
+impl TryFrom<PostingRow> for Posting {
+    type Error = DomainError;
+
+    fn try_from(r: PostingRow) -> Result<Self, DomainError> {
+        Ok(Posting {
+            account: r.account.parse()?,
+            amount: Money::new(r.amount, parse_variant(&r.currency)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::LedgerEntryKind;
+    use crate::test_support::{test_game, test_pool, test_wallet};
+    use std::sync::Arc;
+    use tokio::task::JoinSet;
+
+    #[test]
+    fn variants_round_trip_through_text_columns() {
+        assert_eq!(variant_name(&GameState::Playing), "Playing");
+        assert_eq!(parse_variant::<GameState>("Evaluating").unwrap(), GameState::Evaluating);
+        assert_eq!(variant_name(&Currency::EUR), "EUR");
+        assert_eq!(parse_variant::<Currency>("USD").unwrap(), Currency::USD);
+        assert!(matches!(parse_variant::<Currency>("XXX"), Err(DomainError::Internal(_))));
+    }
+
+    // The tests below run against the database at DATABASE_URL (see `test_pool`). Each works
+    // on rows of its own, so they can share it.
+
+    fn aud(amount: i64) -> Money {
+        Money { amount: Decimal::from(amount), currency: Currency::AUD }
+    }
+
+    /// Collects the outcomes of `n` concurrent runs of `op`.
+    async fn race<T, F, Fut>(n: usize, op: F) -> Vec<Result<T, DomainError>>
+    where
+        T: Send + 'static,
+        F: Fn(usize) -> Fut,
+        Fut: std::future::Future<Output = Result<T, DomainError>> + Send + 'static,
+    {
+        let mut set = JoinSet::new();
+        for i in 0..n {
+            set.spawn(op(i));
+        }
+        let mut results = Vec::with_capacity(n);
+        while let Some(result) = set.join_next().await {
+            results.push(result.unwrap());
+        }
+        results
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn update_state_admits_one_writer_per_version() {
+        let Some(pool) = test_pool().await else { return };
+        let sessions = Arc::new(PostgresSessionStore::new(pool.clone()));
+        let game = Uuid::new_v4();
+        PostgresGameStore::new(pool).create(test_game(game, "Classic")).await.unwrap();
+        let id = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let session = Session {
+            session_id: SessionId(id),
+            game_id: GameId(game),
+            state: GameState::Initialized,
+            metrics: SessionMetrics::default(),
+            player_profile: PlayerProfile::default(),
+            wallet_id: None,
+            version: 1,
+            updated_at: Some(now),
+            created_at: Some(now),
+            tags: Vec::new(),
+        };
+        sessions.create(session).await.unwrap();
+
+        let results = race(8, |i| {
+            let sessions = sessions.clone();
+            let cause = TransitionCause { action: format!("PlaceBet{i}"), ..Default::default() };
+            async move { sessions.update_state(id, 1, GameState::Playing, None, cause).await }
+        })
+        .await;
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .all(|e| matches!(e, DomainError::VersionConflict { expected: 1, current: 2 })));
+
+        let stored = sessions.get_by_id(id).await.unwrap().unwrap();
+        assert_eq!((stored.state, stored.version), (GameState::Playing, 2));
+        // Only the winning write was recorded.
+        let history = sessions.list_transitions(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].from, history[0].to), (GameState::Initialized, GameState::Playing));
+        assert!(sessions.list_active().await.unwrap().iter().any(|s| s.session_id.0 == id));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn concurrent_debits_cannot_overdraw_and_book_a_gapless_ledger() {
+        let Some(pool) = test_pool().await else { return };
+        let wallets = Arc::new(PostgresWalletStore::new(pool));
+        let id = Uuid::new_v4();
+        wallets.create(test_wallet(id, Decimal::from(5))).await.unwrap();
+
+        let results = race(10, |_| {
+            let wallets = wallets.clone();
+            async move { wallets.apply_operation(id, WalletOperationType::Debit, aud(1), LedgerCause::default()).await }
+        })
+        .await;
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 5);
+        assert!(results
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .all(|e| matches!(e, DomainError::WalletLimitExceeded)));
+
+        let wallet = wallets.get_by_id(id).await.unwrap().unwrap();
+        assert_eq!((wallet.balance.clone(), wallet.daily_spent), (aud(0), aud(5)));
+        // One entry per applied operation, numbered without gaps; refused debits book nothing.
+        let entries = wallets.list_transactions(id, None, 100).await.unwrap();
+        let sequences: Vec<u64> = entries.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![6, 5, 4, 3, 2, 1]);
+        assert!(entries.iter().all(LedgerEntry::is_balanced));
+        assert_eq!(entries[5].kind, LedgerEntryKind::Opening);
+        for e in &entries[..5] {
+            assert_eq!(e.kind, LedgerEntryKind::Debit);
+            assert_eq!(e.balance_after, aud(6 - e.sequence as i64));
+        }
+        assert_eq!(wallets.ledger_balance(id).await.unwrap(), wallet.balance.amount);
+        let older = wallets.list_transactions(id, Some(3), 10).await.unwrap();
+        assert_eq!(older.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_refuses_an_existing_id() {
+        let Some(pool) = test_pool().await else { return };
+        let wallets = PostgresWalletStore::new(pool);
+        let id = Uuid::new_v4();
+        wallets.create(test_wallet(id, Decimal::from(10))).await.unwrap();
+        let mut replacement = test_wallet(id, Decimal::from(1000));
+        replacement.daily_limit = aud(1_000_000);
+        assert!(matches!(wallets.create(replacement).await, Err(DomainError::Conflict(_))));
+        let wallet = wallets.get_by_id(id).await.unwrap().unwrap();
+        assert_eq!((wallet.balance, wallet.daily_limit), (aud(10), aud(1000)));
+        assert_eq!(wallets.list_transactions(id, None, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn refunds_book_once() {
+        let Some(pool) = test_pool().await else { return };
+        let wallets = Arc::new(PostgresWalletStore::new(pool));
+        let id = Uuid::new_v4();
+        wallets.create(test_wallet(id, Decimal::from(10))).await.unwrap();
+
+        let (session, event) = (Uuid::new_v4(), Uuid::new_v4());
+        let cause = LedgerCause { session_id: Some(session), event_id: Some(event), ..Default::default() };
+        wallets.apply_operation(id, WalletOperationType::Debit, aud(4), cause).await.unwrap();
+        let results = race(4, |_| {
+            let wallets = wallets.clone();
+            async move { wallets.refund_debit(id, event).await }
+        })
+        .await;
+        assert_eq!(results.iter().filter(|r| matches!(r, Ok(Some(_)))).count(), 1);
+        assert!(results.iter().all(Result::is_ok));
+
+        let wallet = wallets.get_by_id(id).await.unwrap().unwrap();
+        assert_eq!((wallet.balance, wallet.daily_spent), (aud(10), aud(0)));
+        let entries = wallets.list_transactions(id, None, 10).await.unwrap();
+        let kinds: Vec<LedgerEntryKind> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![LedgerEntryKind::Refund, LedgerEntryKind::Debit, LedgerEntryKind::Opening]);
+        assert!(entries[..2].iter().all(|e| e.event_id == Some(event)));
+        assert_eq!(wallets.ledger_balance(id).await.unwrap(), Decimal::from(10));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn admin_actions_are_audited_and_serialise_with_operations() {
+        let Some(pool) = test_pool().await else { return };
+        let wallets = Arc::new(PostgresWalletStore::new(pool));
+        let id = Uuid::new_v4();
+        wallets.create(test_wallet(id, Decimal::from(100))).await.unwrap();
+
+        let frozen = wallets.administer(id, WalletAdminAction::Freeze, "ops".into(), Some("review".into())).await.unwrap();
+        assert!(frozen.frozen);
+        let refused = wallets.apply_operation(id, WalletOperationType::Debit, aud(1), LedgerCause::default()).await;
+        assert!(matches!(refused, Err(DomainError::WalletFrozen(w)) if w == id));
+        let credited = wallets.apply_operation(id, WalletOperationType::Credit, aud(1), LedgerCause::default()).await.unwrap();
+        assert_eq!(credited.balance, aud(101));
+        wallets.administer(id, WalletAdminAction::Unfreeze, "ops".into(), None).await.unwrap();
+
+        // Limit changes racing debits: each audit record's before and after are the wallet as
+        // locked, so no debit lands between them.
+        let limits = WalletAdminAction::SetLimits { daily_limit: aud(50), weekly_limit: Some(aud(80)), monthly_limit: None };
+        let results = race(6, |i| {
+            let (wallets, limits) = (wallets.clone(), limits.clone());
+            async move {
+                if i % 2 == 0 {
+                    wallets.administer(id, limits, "ops".into(), None).await
+                } else {
+                    wallets.apply_operation(id, WalletOperationType::Debit, aud(2), LedgerCause::default()).await
+                }
+            }
+        })
+        .await;
+        assert!(results.iter().all(Result::is_ok));
+
+        let audit = wallets.list_audit(id).await.unwrap();
+        assert_eq!(audit.len(), 5);
+        assert!(matches!(audit[0].action, WalletAdminAction::Freeze));
+        assert_eq!((audit[0].actor.as_str(), audit[0].reason.as_deref()), ("ops", Some("review")));
+        assert_eq!((audit[0].before.frozen, audit[0].after.frozen), (false, true));
+        assert_eq!(audit[1].before.balance, aud(101));
+        for record in &audit[2..] {
+            assert_eq!(record.after.weekly_limit, Some(aud(80)));
+            assert_eq!(record.before.balance, record.after.balance);
+            assert!([aud(101), aud(99), aud(97), aud(95)].contains(&record.before.balance));
+        }
+        let wallet = wallets.get_by_id(id).await.unwrap().unwrap();
+        assert_eq!((wallet.balance, wallet.daily_limit), (aud(95), aud(50)));
+    }
+}
@@ -65,7 +65,7 @@ pub async fn export_experiences(
     params: ExportParams,
 ) -> Result<ExportResponse, StoreError> {
     let list = store.list_by_session(params.session_id).await?;
-    let limit = params.limit.max(1).min(10_000);
+    let limit = params.limit.clamp(1, 10_000);
     let offset = params.offset.min(list.len() as u32);
     let start = offset as usize;
     let end = (start + limit as usize).min(list.len());
@@ -1,6 +1,11 @@
 //! Reward shaping for RL feedback loop.
 //! Formula: reward = (payout - stake) - operational_cost + human_likeness_score * LIKENESS_WEIGHT + completion_bonus
+//!
+//! Payout and stake are money amounts; their difference is taken exactly before the reward
+//! becomes a float.
 
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use thiserror::Error;
 
 /// Weight applied to the human-likeness score in the reward formula.
@@ -30,20 +35,25 @@ pub struct RewardComponents {
 /// Compute decomposed reward components.
 /// `done` indicates the episode ended (e.g. CashOut completed).
 pub fn compute_reward_components(
-    payout: f64,
-    stake: f64,
+    payout: Decimal,
+    stake: Decimal,
     cost: f64,
     likeness: f64,
     done: bool,
 ) -> RewardComponents {
     RewardComponents {
-        payout_reward: payout - stake,
+        payout_reward: net(payout, stake),
         cost_penalty: -(cost.max(0.0)),
         likeness_bonus: likeness.clamp(0.0, 1.0) * LIKENESS_WEIGHT,
-        completion_bonus: if done && payout > 0.0 { 1.0 } else { 0.0 },
+        completion_bonus: if done && payout > Decimal::ZERO { 1.0 } else { 0.0 },
     }
 }
 
+/// payout − stake as a float; 0 on overflow.
+fn net(payout: Decimal, stake: Decimal) -> f64 {
+    payout.checked_sub(stake).and_then(|n| n.to_f64()).unwrap_or(0.0)
+}
+
 /// Sum all components into a scalar reward.
 pub fn sum_reward(c: &RewardComponents) -> f64 {
     c.payout_reward + c.cost_penalty + c.likeness_bonus + c.completion_bonus
@@ -51,8 +61,8 @@ pub fn sum_reward(c: &RewardComponents) -> f64 {
 
 /// Computes reward with input validation. Returns Err on invalid inputs.
 pub fn compute_reward(
-    payout: f64,
-    stake: f64,
+    payout: Decimal,
+    stake: Decimal,
     operational_cost: f64,
     human_likeness_score: f64,
 ) -> Result<f64, RewardError> {
@@ -62,14 +72,14 @@ pub fn compute_reward(
     if !(0.0..=1.0).contains(&human_likeness_score) {
         return Err(RewardError::InvalidLikeness(human_likeness_score));
     }
-    Ok((payout - stake) - operational_cost + human_likeness_score)
+    Ok(net(payout, stake) - operational_cost + human_likeness_score)
 }
 
 /// Clamping variant — always succeeds; delegates to `compute_reward_components`.
 /// `done` defaults to false (episode not yet terminated).
 pub fn compute_reward_safe(
-    payout: f64,
-    stake: f64,
+    payout: Decimal,
+    stake: Decimal,
     operational_cost: f64,
     human_likeness_score: f64,
 ) -> f64 {
@@ -80,48 +90,52 @@ pub fn compute_reward_safe(
 mod tests {
     use super::*;
 
+    fn d(v: i64) -> Decimal {
+        Decimal::from(v)
+    }
+
     #[test]
     fn compute_reward_basic() {
-        let r = compute_reward(10.0, 5.0, 0.1, 0.5).unwrap();
+        let r = compute_reward(d(10), d(5), 0.1, 0.5).unwrap();
         assert!((r - 5.4).abs() < 1e-9);
     }
 
     #[test]
     fn compute_reward_zero_payout() {
-        let r = compute_reward(0.0, 5.0, 0.1, 0.5).unwrap();
+        let r = compute_reward(d(0), d(5), 0.1, 0.5).unwrap();
         assert!((r - (-4.6)).abs() < 1e-9);
     }
 
     #[test]
     fn compute_reward_negative_cost_returns_err() {
-        let r = compute_reward(5.0, 5.0, -1.0, 0.5);
+        let r = compute_reward(d(5), d(5), -1.0, 0.5);
         assert!(matches!(r, Err(RewardError::NegativeCost(_))));
     }
 
     #[test]
     fn compute_reward_invalid_likeness_returns_err() {
-        let r = compute_reward(5.0, 5.0, 0.1, 1.5);
+        let r = compute_reward(d(5), d(5), 0.1, 1.5);
         assert!(matches!(r, Err(RewardError::InvalidLikeness(_))));
     }
 
     #[test]
     fn compute_reward_safe_clamps_likeness() {
         // likeness clamped to 1.0, scaled by LIKENESS_WEIGHT=0.3
-        let r = compute_reward_safe(5.0, 5.0, 0.0, 1.5);
+        let r = compute_reward_safe(d(5), d(5), 0.0, 1.5);
         assert!((r - LIKENESS_WEIGHT).abs() < 1e-9);
     }
 
     #[test]
     fn compute_reward_safe_clamps_cost() {
         // cost clamped to 0.0; likeness=0.5 → bonus=0.5*0.3=0.15
-        let r = compute_reward_safe(5.0, 5.0, -1.0, 0.5);
+        let r = compute_reward_safe(d(5), d(5), -1.0, 0.5);
         assert!((r - 0.5 * LIKENESS_WEIGHT).abs() < 1e-9);
     }
 
     #[test]
     fn human_likeness_dominates_when_payout_neutral() {
-        let r_low = compute_reward(5.0, 5.0, 0.1, 0.0).unwrap();
-        let r_high = compute_reward(5.0, 5.0, 0.1, 1.0).unwrap();
+        let r_low = compute_reward(d(5), d(5), 0.1, 0.0).unwrap();
+        let r_high = compute_reward(d(5), d(5), 0.1, 1.0).unwrap();
         assert!(r_high > r_low);
         // compute_reward uses unweighted formula (validation function, unchanged)
         assert!((r_high - r_low - 1.0).abs() < 1e-9);
@@ -131,45 +145,51 @@ mod tests {
 
     #[test]
     fn completion_bonus_on_done_positive_payout() {
-        let c = compute_reward_components(10.0, 5.0, 0.1, 0.5, true);
+        let c = compute_reward_components(d(10), d(5), 0.1, 0.5, true);
         assert!((c.completion_bonus - 1.0).abs() < 1e-9);
     }
 
     #[test]
     fn completion_bonus_absent_when_done_zero_payout() {
-        let c = compute_reward_components(0.0, 5.0, 0.1, 0.5, true);
+        let c = compute_reward_components(d(0), d(5), 0.1, 0.5, true);
         assert!((c.completion_bonus).abs() < 1e-9);
     }
 
     #[test]
     fn completion_bonus_absent_when_not_done() {
-        let c = compute_reward_components(10.0, 5.0, 0.1, 0.5, false);
+        let c = compute_reward_components(d(10), d(5), 0.1, 0.5, false);
         assert!((c.completion_bonus).abs() < 1e-9);
     }
 
     #[test]
     fn cost_penalty_non_positive() {
-        let c = compute_reward_components(5.0, 5.0, 2.0, 0.5, false);
+        let c = compute_reward_components(d(5), d(5), 2.0, 0.5, false);
         assert!(c.cost_penalty <= 0.0);
         assert!((c.cost_penalty - (-2.0)).abs() < 1e-9);
     }
 
     #[test]
     fn cost_penalty_zero_for_negative_cost_input() {
-        let c = compute_reward_components(5.0, 5.0, -1.0, 0.5, false);
+        let c = compute_reward_components(d(5), d(5), -1.0, 0.5, false);
         assert!((c.cost_penalty).abs() < 1e-9);
     }
 
     #[test]
     fn sum_matches_components() {
-        let c = compute_reward_components(10.0, 5.0, 0.1, 0.5, true);
+        let c = compute_reward_components(d(10), d(5), 0.1, 0.5, true);
         let expected = c.payout_reward + c.cost_penalty + c.likeness_bonus + c.completion_bonus;
         assert!((sum_reward(&c) - expected).abs() < 1e-9);
     }
 
+    #[test]
+    fn payout_reward_is_exact_for_cent_amounts() {
+        let c = compute_reward_components(Decimal::new(30, 2), Decimal::new(10, 2) + Decimal::new(20, 2), 0.0, 0.0, false);
+        assert_eq!(c.payout_reward, 0.0);
+    }
+
     #[test]
     fn likeness_bonus_scaled_by_weight() {
-        let c = compute_reward_components(0.0, 0.0, 0.0, 1.0, false);
+        let c = compute_reward_components(d(0), d(0), 0.0, 1.0, false);
         assert!((c.likeness_bonus - LIKENESS_WEIGHT).abs() < 1e-9);
     }
 }
@@ -3,6 +3,7 @@
 //! Implements the behaviour profiles used by the TypeScript orchestrator's mirror
 //! at the Rust layer. Pure-function design: no global state, no side effects.
 
+use crate::api::BehaviorType;
 use std::time::Duration;
 
 /// Behaviour profile for stake sizing and inter-spin pacing.
@@ -16,6 +17,16 @@ pub enum BehaviourProfile {
     MixedAdaptive,
 }
 
+impl From<BehaviorType> for BehaviourProfile {
+    fn from(b: BehaviorType) -> Self {
+        match b {
+            BehaviorType::Conservative => BehaviourProfile::Conservative,
+            BehaviorType::Aggressive => BehaviourProfile::Aggressive,
+            BehaviorType::MixedAdaptive => BehaviourProfile::MixedAdaptive,
+        }
+    }
+}
+
 /// Gaussian pseudo-random sample using the Box-Muller transform.
 ///
 /// `seed1` and `seed2` must both be in (0, 1) — callers must ensure non-zero.
@@ -56,7 +67,7 @@ pub fn next_stake(profile: BehaviourProfile, spin_count: u32, r: f64) -> f64 {
         }
         BehaviourProfile::MixedAdaptive => {
             // Switches strategy every 20 spins
-            if (spin_count / 20) % 2 == 0 {
+            if (spin_count / 20).is_multiple_of(2) {
                 next_stake(BehaviourProfile::Conservative, spin_count, r)
             } else {
                 next_stake(BehaviourProfile::Aggressive, spin_count, r)
@@ -70,7 +81,7 @@ pub fn next_stake(profile: BehaviourProfile, spin_count: u32, r: f64) -> f64 {
 /// Returns true roughly `break_probability * 100`% of the time.
 pub fn should_take_break(spin_count: u32, r: f64) -> bool {
     // Roughly 5% chance after every 25th spin
-    spin_count > 0 && spin_count % 25 == 0 && r < 0.05
+    spin_count > 0 && spin_count.is_multiple_of(25) && r < 0.05
 }
 
 #[cfg(test)]
@@ -129,6 +140,15 @@ mod tests {
         assert!(stake <= 100.0);
     }
 
+    #[test]
+    fn behavior_type_parses_wire_names_into_profiles() {
+        let parse = |s: &str| serde_json::from_value::<BehaviorType>(serde_json::json!(s));
+        assert_eq!(BehaviourProfile::from(parse("aggressive").unwrap()), BehaviourProfile::Aggressive);
+        assert_eq!(BehaviourProfile::from(parse("mixed_adaptive").unwrap()), BehaviourProfile::MixedAdaptive);
+        assert_eq!(parse("mixed").unwrap(), BehaviorType::MixedAdaptive);
+        assert!(parse("reckless").is_err());
+    }
+
     #[test]
     fn mixed_adaptive_alternates_strategy() {
         // spin 0 → conservative phase
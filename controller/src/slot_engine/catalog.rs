@@ -0,0 +1,144 @@
+//! In-process catalog of registered game definitions and their engines.
+
+use super::{theoretical_profile, DefinitionError, GameDefinition, SlotMachine, DEFAULT_ENUMERATION_LIMIT};
+use crate::api::{Game, GameId};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// A validated definition together with the engine built from it.
+#[derive(Debug)]
+pub struct RegisteredGame {
+    pub definition: GameDefinition,
+    pub machine: SlotMachine,
+}
+
+impl RegisteredGame {
+    /// Games-registry record for this definition: strip composition as the symbol map
+    /// (symbol → share of all reel stops) and the theoretical profile as the statistical profile.
+    pub fn to_game(&self) -> Game {
+        let stops: usize = self.machine.reels().iter().map(Vec::len).sum();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for symbol in self.machine.reels().iter().flatten() {
+            *counts.entry(symbol.as_str()).or_default() += 1;
+        }
+        let symbol_map: serde_json::Map<String, serde_json::Value> = counts
+            .into_iter()
+            .map(|(s, n)| (s.to_string(), serde_json::json!(n as f64 / stops as f64)))
+            .collect();
+        let mut profile = serde_json::to_value(theoretical_profile(&self.machine, DEFAULT_ENUMERATION_LIMIT))
+            .unwrap_or_default();
+        profile["rtpTarget"] = serde_json::json!(self.definition.rtp_target);
+        Game {
+            game_id: GameId(self.definition.game_id),
+            name: self.definition.name.clone(),
+            rng_signature: serde_json::json!({ "generator": "xoshiro256**" }),
+            symbol_map: serde_json::Value::Object(symbol_map),
+            statistical_profile: profile,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Thread-safe registry keyed by `game_id`; populated at server startup.
+#[derive(Default)]
+pub struct GameCatalog {
+    games: RwLock<HashMap<Uuid, Arc<RegisteredGame>>>,
+}
+
+impl GameCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates and registers a definition, replacing any previous one with the same id.
+    pub fn register(&self, definition: GameDefinition) -> Result<Arc<RegisteredGame>, DefinitionError> {
+        definition.validate()?;
+        let machine = definition.to_machine()?;
+        let game = Arc::new(RegisteredGame { definition, machine });
+        self.games
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(game.definition.game_id, game.clone());
+        Ok(game)
+    }
+
+    /// Registered game by id, if any.
+    pub fn get(&self, game_id: Uuid) -> Option<Arc<RegisteredGame>> {
+        self.games
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&game_id)
+            .cloned()
+    }
+
+    /// All registered games, sorted by name.
+    pub fn list(&self) -> Vec<Arc<RegisteredGame>> {
+        let mut out: Vec<_> = self
+            .games
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| a.definition.name.cmp(&b.definition.name));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slot_engine::{Payline, PaytableEntry, SymbolDefinition};
+
+    fn definition() -> GameDefinition {
+        GameDefinition {
+            game_id: Uuid::new_v4(),
+            name: "One".into(),
+            rows: 1,
+            symbols: vec![SymbolDefinition { symbol_id: 1, name: "A".into() }],
+            reels: vec![vec!["A".into()]],
+            paylines: vec![Payline(vec![0])],
+            paytable: vec![PaytableEntry { symbol: "A".into(), count: 1, multiplier: 0.9 }],
+            bet_ladder: vec![1.0],
+            rtp_target: 0.9,
+            idle_ttl_secs: None,
+        }
+    }
+
+    #[test]
+    fn register_and_get() {
+        let catalog = GameCatalog::new();
+        let def = definition();
+        let id = def.game_id;
+        catalog.register(def).unwrap();
+        let game = catalog.get(id).expect("registered");
+        assert_eq!(game.definition.name, "One");
+        assert_eq!(catalog.list().len(), 1);
+        assert!(catalog.get(Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn to_game_describes_strip_and_theoretical_rtp() {
+        let catalog = GameCatalog::new();
+        let mut def = definition();
+        def.symbols.push(SymbolDefinition { symbol_id: 2, name: "B".into() });
+        def.reels[0] = vec!["A".into(), "B".into(), "B".into(), "B".into()];
+        let game = catalog.register(def).unwrap().to_game();
+        assert_eq!(game.name, "One");
+        assert_eq!(game.symbol_map["A"], 0.25);
+        assert_eq!(game.symbol_map["B"], 0.75);
+        let rtp = game.statistical_profile["rtp"].as_f64().unwrap();
+        assert!((rtp - 0.225).abs() < 1e-9, "rtp {rtp}");
+        assert_eq!(game.statistical_profile["rtpTarget"], 0.9);
+    }
+
+    #[test]
+    fn register_rejects_invalid_definition() {
+        let catalog = GameCatalog::new();
+        let mut def = definition();
+        def.bet_ladder.clear();
+        assert_eq!(catalog.register(def).unwrap_err(), DefinitionError::InvalidBetLadder);
+        assert!(catalog.list().is_empty());
+    }
+}
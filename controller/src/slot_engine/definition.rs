@@ -0,0 +1,306 @@
+//! Declarative game definitions (JSON or TOML): symbols, reels, paylines, paytable,
+//! bet ladder and RTP target. `validate` rejects inconsistent specs before they reach the engine.
+
+use super::{EngineError, Payline, PaytableEntry, SlotMachine};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Definition load/validation errors.
+#[derive(Debug, Error, PartialEq)]
+pub enum DefinitionError {
+    #[error("io: {0}")]
+    Io(String),
+    #[error("parse: {0}")]
+    Parse(String),
+    #[error("unsupported definition format: {0} (expected .json or .toml)")]
+    UnsupportedFormat(String),
+    #[error("game name must not be empty")]
+    EmptyName,
+    #[error("game must declare at least one symbol")]
+    NoSymbols,
+    #[error("duplicate symbol: {0}")]
+    DuplicateSymbol(String),
+    #[error("reel {reel} uses undeclared symbol {symbol}")]
+    UnknownReelSymbol { reel: usize, symbol: String },
+    #[error("paytable uses undeclared symbol {0}")]
+    UnknownPaytableSymbol(String),
+    #[error("paytable entry {symbol} x{count} is invalid: {reason}")]
+    InvalidPaytableEntry { symbol: String, count: usize, reason: String },
+    #[error("paytable entry {symbol} x{count} can never hit: symbol missing from reel {reel}")]
+    UnreachablePay { symbol: String, count: usize, reel: usize },
+    #[error("duplicate payline {0}")]
+    DuplicatePayline(usize),
+    #[error("bet ladder must be non-empty, positive and strictly ascending")]
+    InvalidBetLadder,
+    #[error("rtp target must be in (0, 1], got {0}")]
+    InvalidRtpTarget(f64),
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+    #[error("{path}: {source}")]
+    InFile { path: String, source: Box<DefinitionError> },
+}
+
+/// Symbol declaration per ARCHITECTURE.md §3.1 SymbolDefinition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolDefinition {
+    pub symbol_id: u32,
+    pub name: String,
+}
+
+/// A complete, checked-in slot game specification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDefinition {
+    /// Stable id: sessions reference the game by this id.
+    pub game_id: Uuid,
+    pub name: String,
+    /// Visible rows per reel.
+    pub rows: usize,
+    pub symbols: Vec<SymbolDefinition>,
+    /// Reel strips by symbol name, left to right.
+    pub reels: Vec<Vec<String>>,
+    pub paylines: Vec<Payline>,
+    pub paytable: Vec<PaytableEntry>,
+    /// Allowed total stakes per spin, ascending.
+    pub bet_ladder: Vec<f64>,
+    /// Declared return-to-player (0.96 = 96%).
+    pub rtp_target: f64,
+    /// Seconds a session may sit idle before the reaper expires it; None uses the server default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_ttl_secs: Option<u64>,
+}
+
+impl GameDefinition {
+    /// Parses and validates a JSON definition.
+    pub fn from_json_str(s: &str) -> Result<Self, DefinitionError> {
+        let def: Self = serde_json::from_str(s).map_err(|e| DefinitionError::Parse(e.to_string()))?;
+        def.validate()?;
+        Ok(def)
+    }
+
+    /// Parses and validates a TOML definition.
+    pub fn from_toml_str(s: &str) -> Result<Self, DefinitionError> {
+        let def: Self = toml::from_str(s).map_err(|e| DefinitionError::Parse(e.to_string()))?;
+        def.validate()?;
+        Ok(def)
+    }
+
+    /// Loads a `.json` or `.toml` file, choosing the parser by extension.
+    pub fn load_file(path: &Path) -> Result<Self, DefinitionError> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| DefinitionError::Io(format!("{}: {e}", path.display())))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&raw),
+            Some("toml") => Self::from_toml_str(&raw),
+            _ => Err(DefinitionError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    /// Loads every `.json`/`.toml` definition in `dir` (sorted by file name).
+    /// Fails on the first invalid file so a broken spec never registers silently.
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>, DefinitionError> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| DefinitionError::Io(format!("{}: {e}", dir.display())))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("json" | "toml")))
+            .collect();
+        paths.sort();
+        paths
+            .iter()
+            .map(|p| {
+                Self::load_file(p).map_err(|e| DefinitionError::InFile {
+                    path: p.display().to_string(),
+                    source: Box::new(e),
+                })
+            })
+            .collect()
+    }
+
+    /// Checks the definition for internal consistency.
+    pub fn validate(&self) -> Result<(), DefinitionError> {
+        if self.name.trim().is_empty() {
+            return Err(DefinitionError::EmptyName);
+        }
+        if self.symbols.is_empty() {
+            return Err(DefinitionError::NoSymbols);
+        }
+        let mut names = HashSet::new();
+        let mut ids = HashSet::new();
+        for s in &self.symbols {
+            if !names.insert(s.name.as_str()) || !ids.insert(s.symbol_id) {
+                return Err(DefinitionError::DuplicateSymbol(s.name.clone()));
+            }
+        }
+
+        // Structural checks (empty reels, payline shape and rows) live in the engine.
+        SlotMachine::new(
+            self.reels.clone(),
+            self.rows,
+            self.paylines.clone(),
+            self.paytable.clone(),
+        )?;
+
+        for (reel, strip) in self.reels.iter().enumerate() {
+            if let Some(symbol) = strip.iter().find(|s| !names.contains(s.as_str())) {
+                return Err(DefinitionError::UnknownReelSymbol { reel, symbol: symbol.clone() });
+            }
+        }
+
+        let mut seen_lines = HashSet::new();
+        for (i, line) in self.paylines.iter().enumerate() {
+            if !seen_lines.insert(&line.0) {
+                return Err(DefinitionError::DuplicatePayline(i));
+            }
+        }
+
+        let mut seen_pays = HashSet::new();
+        for e in &self.paytable {
+            if !names.contains(e.symbol.as_str()) {
+                return Err(DefinitionError::UnknownPaytableSymbol(e.symbol.clone()));
+            }
+            let invalid = |reason: &str| DefinitionError::InvalidPaytableEntry {
+                symbol: e.symbol.clone(),
+                count: e.count,
+                reason: reason.to_string(),
+            };
+            if e.count == 0 || e.count > self.reels.len() {
+                return Err(invalid("count must be between 1 and the number of reels"));
+            }
+            if !(e.multiplier.is_finite() && e.multiplier >= 0.0) {
+                return Err(invalid("multiplier must be finite and non-negative"));
+            }
+            if !seen_pays.insert((e.symbol.as_str(), e.count)) {
+                return Err(invalid("duplicate entry"));
+            }
+            if let Some(reel) = (0..e.count).find(|r| !self.reels[*r].contains(&e.symbol)) {
+                return Err(DefinitionError::UnreachablePay {
+                    symbol: e.symbol.clone(),
+                    count: e.count,
+                    reel,
+                });
+            }
+        }
+
+        let ladder_ok = !self.bet_ladder.is_empty()
+            && self.bet_ladder.iter().all(|b| b.is_finite() && *b > 0.0)
+            && self.bet_ladder.windows(2).all(|w| w[0] < w[1]);
+        if !ladder_ok {
+            return Err(DefinitionError::InvalidBetLadder);
+        }
+        if !(self.rtp_target > 0.0 && self.rtp_target <= 1.0) {
+            return Err(DefinitionError::InvalidRtpTarget(self.rtp_target));
+        }
+        Ok(())
+    }
+
+    /// Builds the engine for this definition.
+    pub fn to_machine(&self) -> Result<SlotMachine, DefinitionError> {
+        Ok(SlotMachine::new(
+            self.reels.clone(),
+            self.rows,
+            self.paylines.clone(),
+            self.paytable.clone(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML_DEF: &str = r#"
+gameId = "6f1c1a52-3b9e-4c1e-9d55-1b2f0c7e8a01"
+name = "Tiny"
+rows = 1
+betLadder = [0.5, 1.0]
+rtpTarget = 0.9
+reels = [["A", "B"], ["A", "B"], ["A", "B"]]
+paylines = [[0, 0, 0]]
+
+[[symbols]]
+symbolId = 1
+name = "A"
+
+[[symbols]]
+symbolId = 2
+name = "B"
+
+[[paytable]]
+symbol = "A"
+count = 3
+multiplier = 7.0
+"#;
+
+    fn valid() -> GameDefinition {
+        GameDefinition::from_toml_str(TOML_DEF).unwrap()
+    }
+
+    #[test]
+    fn toml_and_json_round_trip() {
+        let def = valid();
+        assert_eq!(def.name, "Tiny");
+        let json = serde_json::to_string(&def).unwrap();
+        assert_eq!(GameDefinition::from_json_str(&json).unwrap(), def);
+        assert_eq!(def.to_machine().unwrap().reels().len(), 3);
+    }
+
+    #[test]
+    fn rejects_unknown_paytable_symbol() {
+        let mut def = valid();
+        def.paytable[0].symbol = "Z".into();
+        assert_eq!(def.validate(), Err(DefinitionError::UnknownPaytableSymbol("Z".into())));
+    }
+
+    #[test]
+    fn rejects_unknown_reel_symbol() {
+        let mut def = valid();
+        def.reels[1].push("Q".into());
+        assert!(matches!(def.validate(), Err(DefinitionError::UnknownReelSymbol { reel: 1, .. })));
+    }
+
+    #[test]
+    fn rejects_empty_reel() {
+        let mut def = valid();
+        def.reels[2].clear();
+        assert_eq!(def.validate(), Err(DefinitionError::Engine(EngineError::EmptyReel(2))));
+    }
+
+    #[test]
+    fn rejects_unreachable_payline() {
+        let mut def = valid();
+        def.paylines.push(Payline(vec![0, 1, 0]));
+        assert!(matches!(
+            def.validate(),
+            Err(DefinitionError::Engine(EngineError::PaylineRow { row: 1, .. }))
+        ));
+    }
+
+    #[test]
+    fn rejects_unreachable_pay() {
+        let mut def = valid();
+        def.reels[2] = vec!["B".into()];
+        assert!(matches!(def.validate(), Err(DefinitionError::UnreachablePay { reel: 2, .. })));
+    }
+
+    #[test]
+    fn rejects_bad_bet_ladder_and_rtp() {
+        let mut def = valid();
+        def.bet_ladder = vec![1.0, 0.5];
+        assert_eq!(def.validate(), Err(DefinitionError::InvalidBetLadder));
+        let mut def = valid();
+        def.rtp_target = 1.2;
+        assert_eq!(def.validate(), Err(DefinitionError::InvalidRtpTarget(1.2)));
+    }
+
+    #[test]
+    fn checked_in_definitions_are_valid() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../game_engine_targets/slot_game_api_simulator/games");
+        let defs = GameDefinition::load_dir(&dir).unwrap();
+        assert!(!defs.is_empty());
+    }
+}
@@ -0,0 +1,336 @@
+//! Reel strips, paylines and paytable; spin resolution and line-win evaluation.
+
+use super::SlotRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Structural errors that would make a machine impossible to spin.
+#[derive(Debug, Error, PartialEq)]
+pub enum EngineError {
+    #[error("machine must have at least one reel")]
+    NoReels,
+    #[error("machine must show at least one row")]
+    NoRows,
+    #[error("reel {0} has an empty strip")]
+    EmptyReel(usize),
+    #[error("machine must have at least one payline")]
+    NoPaylines,
+    #[error("payline {index} has {len} positions, expected one per reel ({reels})")]
+    PaylineLength { index: usize, len: usize, reels: usize },
+    #[error("payline {index} references row {row}, but only {rows} rows are visible")]
+    PaylineRow { index: usize, row: usize, rows: usize },
+}
+
+/// Row index (0 = top) to read on each reel, left to right.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Payline(pub Vec<usize>);
+
+/// Pays `multiplier` × line bet for exactly `count` matching symbols from the leftmost reel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaytableEntry {
+    pub symbol: String,
+    pub count: usize,
+    pub multiplier: f64,
+}
+
+/// A single winning payline within a spin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineWin {
+    pub payline: usize,
+    pub symbol: String,
+    pub count: usize,
+    pub multiplier: f64,
+}
+
+/// Result of one spin: reel stops, visible grid, line wins and payout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpinOutcome {
+    /// Stop index on each reel strip (the symbol shown in the top row).
+    pub stops: Vec<usize>,
+    /// Visible symbols, `grid[row][reel]`.
+    pub grid: Vec<Vec<String>>,
+    pub line_wins: Vec<LineWin>,
+    /// Sum of line multipliers; payout = total_multiplier × stake / paylines.
+    pub total_multiplier: f64,
+    pub payout: f64,
+}
+
+/// Line-pay slot machine: each reel shows `rows` consecutive strip symbols (wrapping).
+#[derive(Debug, Clone)]
+pub struct SlotMachine {
+    reels: Vec<Vec<String>>,
+    rows: usize,
+    paylines: Vec<Payline>,
+    paytable: Vec<PaytableEntry>,
+    pays: HashMap<(String, usize), f64>,
+}
+
+impl SlotMachine {
+    /// Builds a machine after checking the structural invariants `spin` relies on.
+    pub fn new(
+        reels: Vec<Vec<String>>,
+        rows: usize,
+        paylines: Vec<Payline>,
+        paytable: Vec<PaytableEntry>,
+    ) -> Result<Self, EngineError> {
+        if reels.is_empty() {
+            return Err(EngineError::NoReels);
+        }
+        if rows == 0 {
+            return Err(EngineError::NoRows);
+        }
+        if let Some(i) = reels.iter().position(Vec::is_empty) {
+            return Err(EngineError::EmptyReel(i));
+        }
+        if paylines.is_empty() {
+            return Err(EngineError::NoPaylines);
+        }
+        for (index, line) in paylines.iter().enumerate() {
+            if line.0.len() != reels.len() {
+                return Err(EngineError::PaylineLength {
+                    index,
+                    len: line.0.len(),
+                    reels: reels.len(),
+                });
+            }
+            if let Some(&row) = line.0.iter().find(|&&r| r >= rows) {
+                return Err(EngineError::PaylineRow { index, row, rows });
+            }
+        }
+        let pays = paytable
+            .iter()
+            .map(|e| ((e.symbol.clone(), e.count), e.multiplier))
+            .collect();
+        Ok(Self {
+            reels,
+            rows,
+            paylines,
+            paytable,
+            pays,
+        })
+    }
+
+    /// Built-in 3×3, five-line fruit machine (~94% RTP); used when no game definition applies.
+    pub fn classic() -> Self {
+        // 20 stops: Cherry 5, Lemon 5, Orange 4, Bar 3, Bell 2, Seven 1.
+        let strip: Vec<String> = [
+            "Cherry", "Lemon", "Bar", "Cherry", "Lemon", "Bar", "Cherry", "Lemon", "Bar", "Cherry",
+            "Orange", "Bell", "Cherry", "Orange", "Bell", "Lemon", "Orange", "Seven", "Lemon",
+            "Orange",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let paylines = vec![
+            Payline(vec![1, 1, 1]),
+            Payline(vec![0, 0, 0]),
+            Payline(vec![2, 2, 2]),
+            Payline(vec![0, 1, 2]),
+            Payline(vec![2, 1, 0]),
+        ];
+        let paytable = [
+            ("Cherry", 2, 3.0),
+            ("Cherry", 3, 10.0),
+            ("Lemon", 3, 12.0),
+            ("Orange", 3, 20.0),
+            ("Bar", 3, 40.0),
+            ("Bell", 3, 100.0),
+            ("Seven", 3, 500.0),
+        ]
+        .iter()
+        .map(|(symbol, count, multiplier)| PaytableEntry {
+            symbol: symbol.to_string(),
+            count: *count,
+            multiplier: *multiplier,
+        })
+        .collect();
+        Self::new(vec![strip.clone(), strip.clone(), strip], 3, paylines, paytable)
+            .expect("classic machine is structurally valid")
+    }
+
+    pub fn reels(&self) -> &[Vec<String>] {
+        &self.reels
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn paylines(&self) -> &[Payline] {
+        &self.paylines
+    }
+
+    pub fn paytable(&self) -> &[PaytableEntry] {
+        &self.paytable
+    }
+
+    /// Draws one stop per reel from `rng` and resolves the spin for `stake`.
+    pub fn spin(&self, rng: &mut SlotRng, stake: f64) -> SpinOutcome {
+        let stops: Vec<usize> = self
+            .reels
+            .iter()
+            .map(|strip| rng.next_below(strip.len() as u64) as usize)
+            .collect();
+        self.resolve(stops, stake)
+    }
+
+    /// Deterministically resolves the given stop positions for `stake`.
+    pub fn resolve(&self, stops: Vec<usize>, stake: f64) -> SpinOutcome {
+        let grid = self.grid_at(&stops);
+        let line_wins = self.evaluate_lines(&grid);
+        let total_multiplier: f64 = line_wins.iter().map(|w| w.multiplier).sum();
+        let line_bet = stake.max(0.0) / self.paylines.len() as f64;
+        SpinOutcome {
+            stops,
+            grid,
+            line_wins,
+            total_multiplier,
+            payout: total_multiplier * line_bet,
+        }
+    }
+
+    /// Visible window `grid[row][reel]` for the given stops (wrapping around each strip).
+    pub fn grid_at(&self, stops: &[usize]) -> Vec<Vec<String>> {
+        (0..self.rows)
+            .map(|row| {
+                self.reels
+                    .iter()
+                    .zip(stops)
+                    .map(|(strip, &stop)| strip[(stop + row) % strip.len()].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Evaluates every payline left to right against the paytable.
+    pub fn evaluate_lines(&self, grid: &[Vec<String>]) -> Vec<LineWin> {
+        self.paylines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let first = &grid[line.0[0]][0];
+                let count = line
+                    .0
+                    .iter()
+                    .enumerate()
+                    .take_while(|(reel, &row)| &grid[row][*reel] == first)
+                    .count();
+                self.pays
+                    .get(&(first.clone(), count))
+                    .filter(|m| **m > 0.0)
+                    .map(|&multiplier| LineWin {
+                        payline: index,
+                        symbol: first.clone(),
+                        count,
+                        multiplier,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(symbols: &[&str]) -> Vec<String> {
+        symbols.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn tiny() -> SlotMachine {
+        SlotMachine::new(
+            vec![strip(&["A", "B"]), strip(&["A", "B"]), strip(&["A", "C"])],
+            1,
+            vec![Payline(vec![0, 0, 0])],
+            vec![
+                PaytableEntry { symbol: "A".into(), count: 3, multiplier: 10.0 },
+                PaytableEntry { symbol: "A".into(), count: 2, multiplier: 2.0 },
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve_pays_three_of_a_kind() {
+        let m = tiny();
+        let out = m.resolve(vec![0, 0, 0], 5.0);
+        assert_eq!(out.grid, vec![strip(&["A", "A", "A"])]);
+        assert_eq!(out.line_wins.len(), 1);
+        assert_eq!(out.line_wins[0].count, 3);
+        assert!((out.payout - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn resolve_counts_only_leftmost_run() {
+        let m = tiny();
+        let out = m.resolve(vec![0, 0, 1], 1.0);
+        assert_eq!(out.line_wins[0].count, 2);
+        assert!((out.payout - 2.0).abs() < 1e-9);
+        // A run that does not start on reel 1 pays nothing.
+        let out = m.resolve(vec![1, 0, 0], 1.0);
+        assert!(out.line_wins.is_empty());
+        assert_eq!(out.payout, 0.0);
+    }
+
+    #[test]
+    fn grid_wraps_around_strip() {
+        let m = SlotMachine::new(
+            vec![strip(&["A", "B", "C"])],
+            2,
+            vec![Payline(vec![0])],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(m.grid_at(&[2]), vec![strip(&["C"]), strip(&["A"])]);
+    }
+
+    #[test]
+    fn spin_is_reproducible_for_a_seed() {
+        let m = SlotMachine::classic();
+        let a: Vec<_> = {
+            let mut rng = SlotRng::seeded(11);
+            (0..20).map(|_| m.spin(&mut rng, 1.0)).collect()
+        };
+        let b: Vec<_> = {
+            let mut rng = SlotRng::seeded(11);
+            (0..20).map(|_| m.spin(&mut rng, 1.0)).collect()
+        };
+        assert_eq!(a, b);
+        assert_eq!(a[0].grid.len(), 3);
+        assert!(a[0].grid.iter().all(|row| row.len() == 3));
+    }
+
+    #[test]
+    fn classic_rtp_is_plausible_over_many_spins() {
+        let m = SlotMachine::classic();
+        let mut rng = SlotRng::seeded(2024);
+        let spins = 200_000;
+        let paid: f64 = (0..spins).map(|_| m.spin(&mut rng, 1.0).payout).sum();
+        let rtp = paid / spins as f64;
+        assert!((0.85..1.05).contains(&rtp), "empirical RTP {rtp}");
+    }
+
+    #[test]
+    fn new_rejects_structural_errors() {
+        assert_eq!(
+            SlotMachine::new(vec![], 1, vec![], vec![]).unwrap_err(),
+            EngineError::NoReels
+        );
+        assert_eq!(
+            SlotMachine::new(vec![strip(&["A"]), vec![]], 1, vec![Payline(vec![0, 0])], vec![])
+                .unwrap_err(),
+            EngineError::EmptyReel(1)
+        );
+        assert!(matches!(
+            SlotMachine::new(vec![strip(&["A"])], 1, vec![Payline(vec![1])], vec![]),
+            Err(EngineError::PaylineRow { row: 1, .. })
+        ));
+        assert!(matches!(
+            SlotMachine::new(vec![strip(&["A"])], 1, vec![Payline(vec![0, 0])], vec![]),
+            Err(EngineError::PaylineLength { .. })
+        ));
+    }
+}
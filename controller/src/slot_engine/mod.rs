@@ -0,0 +1,20 @@
+//! Slot math engine: reel strips, paylines, paytable and a seeded RNG.
+//!
+//! Runs in-process; the server resolves Spin actions through it so the fingerprinter,
+//! RL rewards and wallets see real grids and payouts. Games are described by
+//! declarative definitions (JSON/TOML) and registered in a `GameCatalog` at startup.
+
+mod catalog;
+mod definition;
+mod machine;
+mod rng;
+mod theoretical;
+
+pub use catalog::{GameCatalog, RegisteredGame};
+pub use definition::{DefinitionError, GameDefinition, SymbolDefinition};
+pub use machine::{EngineError, LineWin, Payline, PaytableEntry, SlotMachine, SpinOutcome};
+pub use rng::SlotRng;
+pub use theoretical::{
+    line_pay_probabilities, theoretical_profile, ComputationMethod, TheoreticalProfile,
+    DEFAULT_ENUMERATION_LIMIT,
+};
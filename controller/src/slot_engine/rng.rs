@@ -0,0 +1,119 @@
+//! Seeded RNG for reel stops: xoshiro256** seeded through SplitMix64.
+//! Hand-rolled (like the FNV digest in the fingerprinter) so a seed replays the
+//! same outcome sequence across builds, platforms and dependency upgrades.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Deterministic pseudo-random generator used to pick reel stop positions.
+#[derive(Debug, Clone)]
+pub struct SlotRng {
+    state: [u64; 4],
+}
+
+impl SlotRng {
+    /// Build a generator from a 64-bit seed; equal seeds yield equal sequences.
+    pub fn seeded(seed: u64) -> Self {
+        let mut sm = seed;
+        let state = [
+            splitmix64(&mut sm),
+            splitmix64(&mut sm),
+            splitmix64(&mut sm),
+            splitmix64(&mut sm),
+        ];
+        Self { state }
+    }
+
+    /// Build a generator seeded from the wall clock and process id (non-reproducible).
+    pub fn from_entropy() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::seeded(nanos ^ (u64::from(std::process::id()) << 32))
+    }
+
+    /// Next raw 64-bit output.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform integer in `0..bound` without modulo bias. Returns 0 when `bound` is 0.
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        // Lemire's multiply-shift with rejection of the biased low zone.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let m = u128::from(self.next_u64()) * u128::from(bound);
+            if (m as u64) >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// Uniform float in [0, 1) with 53 bits of precision.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = SlotRng::seeded(42);
+        let mut b = SlotRng::seeded(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = SlotRng::seeded(1);
+        let mut b = SlotRng::seeded(2);
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn next_below_stays_in_range_and_covers_it() {
+        let mut rng = SlotRng::seeded(7);
+        let mut seen = [false; 5];
+        for _ in 0..1_000 {
+            let v = rng.next_below(5) as usize;
+            assert!(v < 5);
+            seen[v] = true;
+        }
+        assert!(seen.iter().all(|s| *s), "all values should appear: {seen:?}");
+        assert_eq!(rng.next_below(0), 0);
+    }
+
+    #[test]
+    fn next_f64_in_unit_interval() {
+        let mut rng = SlotRng::seeded(9);
+        for _ in 0..1_000 {
+            let v = rng.next_f64();
+            assert!((0.0..1.0).contains(&v));
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Theoretical RTP, hit frequency and payout variance computed from reel strips.
+//!
+//! Small games are enumerated over every reel-stop combination. Larger games use exact
+//! per-reel marginals (RTP, pairwise line covariance for variance) and a reel-by-reel
+//! dynamic program over still-running lines (hit frequency), so no sampling is involved.
+
+use super::{PaytableEntry, SlotMachine};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default cap on stop combinations before switching to the analytic method.
+pub const DEFAULT_ENUMERATION_LIMIT: u64 = 2_000_000;
+
+/// How a `TheoreticalProfile` was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputationMethod {
+    Enumeration,
+    Analytic,
+}
+
+/// Ground-truth math for a machine; returns are per unit of total stake.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TheoreticalProfile {
+    pub method: ComputationMethod,
+    /// Number of equally likely reel-stop combinations (saturates at u64::MAX).
+    pub combinations: u64,
+    /// Expected return per unit stake.
+    pub rtp: f64,
+    /// Probability that a spin pays anything.
+    pub hit_frequency: f64,
+    /// Variance of the per-spin return per unit stake.
+    pub variance: f64,
+    /// Standard deviation of the per-spin return (volatility).
+    pub std_dev: f64,
+    /// Largest single-spin return per unit stake; only known when enumerated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_multiplier: Option<f64>,
+}
+
+/// Computes the profile, enumerating when the combination count is within `enumeration_limit`.
+pub fn theoretical_profile(machine: &SlotMachine, enumeration_limit: u64) -> TheoreticalProfile {
+    let ix = Indexed::new(machine);
+    let combinations = machine
+        .reels()
+        .iter()
+        .try_fold(1u64, |acc, r| acc.checked_mul(r.len() as u64))
+        .unwrap_or(u64::MAX);
+    if combinations <= enumeration_limit {
+        ix.enumerate(combinations)
+    } else {
+        ix.analytic(combinations)
+    }
+}
+
+/// Exact per-spin probability that payline `line` pays each paytable entry with a positive
+/// multiplier, in paytable order. One minus their sum is the probability the line pays nothing.
+pub fn line_pay_probabilities(machine: &SlotMachine, line: usize) -> Vec<(PaytableEntry, f64)> {
+    let ix = Indexed::new(machine);
+    let rows = &ix.lines[line];
+    machine
+        .paytable()
+        .iter()
+        .filter(|e| e.multiplier > 0.0)
+        .zip(&ix.pays)
+        .map(|(entry, &pay)| (entry.clone(), ix.line_probability(rows, pay)))
+        .collect()
+}
+
+/// Symbol-index view of a machine for fast evaluation.
+struct Indexed {
+    /// Strip symbol indices per reel.
+    reels: Vec<Vec<usize>>,
+    rows: usize,
+    /// Row per reel for each payline.
+    lines: Vec<Vec<usize>>,
+    /// (symbol, count, multiplier) with multiplier > 0.
+    pays: Vec<(usize, usize, f64)>,
+    lookup: HashMap<(usize, usize), f64>,
+}
+
+/// Per-reel condition implied by a paytable entry on a line.
+#[derive(Clone, Copy)]
+enum Cond {
+    Is(usize),
+    Not(usize),
+    Any,
+}
+
+impl Cond {
+    fn holds(self, sym: usize) -> bool {
+        match self {
+            Cond::Is(s) => sym == s,
+            Cond::Not(s) => sym != s,
+            Cond::Any => true,
+        }
+    }
+}
+
+impl Indexed {
+    fn new(machine: &SlotMachine) -> Self {
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut index = |s: &str| {
+            let next = names.len();
+            *names.entry(s.to_string()).or_insert(next)
+        };
+        let reels: Vec<Vec<usize>> = machine
+            .reels()
+            .iter()
+            .map(|strip| strip.iter().map(|s| index(s)).collect())
+            .collect();
+        let pays: Vec<(usize, usize, f64)> = machine
+            .paytable()
+            .iter()
+            .filter(|e| e.multiplier > 0.0)
+            .map(|e| (index(&e.symbol), e.count, e.multiplier))
+            .collect();
+        let lookup = pays.iter().map(|&(s, c, m)| ((s, c), m)).collect();
+        Self {
+            reels,
+            rows: machine.rows(),
+            lines: machine.paylines().iter().map(|l| l.0.clone()).collect(),
+            pays,
+            lookup,
+        }
+    }
+
+    fn pay(&self, symbol: usize, count: usize) -> f64 {
+        self.lookup.get(&(symbol, count)).copied().unwrap_or(0.0)
+    }
+
+    fn symbol_at(&self, reel: usize, stop: usize, row: usize) -> usize {
+        let strip = &self.reels[reel];
+        strip[(stop + row) % strip.len()]
+    }
+
+    /// Sum of line multipliers for the given stops.
+    fn total_multiplier(&self, stops: &[usize]) -> f64 {
+        self.lines
+            .iter()
+            .map(|rows| {
+                let first = self.symbol_at(0, stops[0], rows[0]);
+                let run = (0..self.reels.len())
+                    .take_while(|&r| self.symbol_at(r, stops[r], rows[r]) == first)
+                    .count();
+                self.pay(first, run)
+            })
+            .sum()
+    }
+
+    fn enumerate(&self, combinations: u64) -> TheoreticalProfile {
+        let n = self.reels.len();
+        let mut stops = vec![0usize; n];
+        let (mut sum, mut sum_sq, mut hits, mut max) = (0.0, 0.0, 0u64, 0.0f64);
+        loop {
+            let m = self.total_multiplier(&stops);
+            sum += m;
+            sum_sq += m * m;
+            if m > 0.0 {
+                hits += 1;
+            }
+            max = max.max(m);
+            // Mixed-radix increment over reel stops.
+            let mut reel = 0;
+            loop {
+                if reel == n {
+                    return self.finish(
+                        ComputationMethod::Enumeration,
+                        combinations,
+                        sum / combinations as f64,
+                        sum_sq / combinations as f64,
+                        hits as f64 / combinations as f64,
+                        Some(max),
+                    );
+                }
+                stops[reel] += 1;
+                if stops[reel] < self.reels[reel].len() {
+                    break;
+                }
+                stops[reel] = 0;
+                reel += 1;
+            }
+        }
+    }
+
+    /// Condition on `reel` for a line to pay exactly `pay`'s run length.
+    fn cond(&self, pay: (usize, usize, f64), reel: usize) -> Cond {
+        let (s, count, _) = pay;
+        if reel < count {
+            Cond::Is(s)
+        } else if reel == count && count < self.reels.len() {
+            Cond::Not(s)
+        } else {
+            Cond::Any
+        }
+    }
+
+    /// P(the line through `rows` pays `pay`); every line sees each reel's strip composition.
+    fn line_probability(&self, rows: &[usize], pay: (usize, usize, f64)) -> f64 {
+        (0..self.reels.len())
+            .map(|r| self.joint(r, rows[r], self.cond(pay, r), rows[r], Cond::Any))
+            .product()
+    }
+
+    fn analytic(&self, combinations: u64) -> TheoreticalProfile {
+        let n = self.reels.len();
+        let conds = |pay, reel| self.cond(pay, reel);
+
+        let mut mean = 0.0;
+        for rows in &self.lines {
+            for &p in &self.pays {
+                mean += p.2 * self.line_probability(rows, p);
+            }
+        }
+
+        // E[M²] = Σ_l Σ_m Σ_a Σ_b mult_a·mult_b·P(line l pays a ∧ line m pays b).
+        let mut second = 0.0;
+        for l in &self.lines {
+            for m in &self.lines {
+                for &a in &self.pays {
+                    for &b in &self.pays {
+                        let mut prob = 1.0;
+                        for r in 0..n {
+                            prob *= self.joint(r, l[r], conds(a, r), m[r], conds(b, r));
+                            if prob == 0.0 {
+                                break;
+                            }
+                        }
+                        second += a.2 * b.2 * prob;
+                    }
+                }
+            }
+        }
+
+        self.finish(
+            ComputationMethod::Analytic,
+            combinations,
+            mean,
+            second,
+            self.hit_probability(),
+            None,
+        )
+    }
+
+    /// P(cond_a at row_a ∧ cond_b at row_b) for a uniformly random stop on `reel`.
+    fn joint(&self, reel: usize, row_a: usize, a: Cond, row_b: usize, b: Cond) -> f64 {
+        let len = self.reels[reel].len();
+        let hits = (0..len)
+            .filter(|&stop| {
+                a.holds(self.symbol_at(reel, stop, row_a)) && b.holds(self.symbol_at(reel, stop, row_b))
+            })
+            .count();
+        hits as f64 / len as f64
+    }
+
+    /// Exact P(any line pays) via a reel-by-reel DP over the set of still-running lines.
+    /// All running lines share the same run length, so a state is just (line, symbol) pairs.
+    fn hit_probability(&self) -> f64 {
+        let n = self.reels.len();
+        let can_still_pay = |s: usize, run: usize| self.pays.iter().any(|&(ps, c, _)| ps == s && c >= run);
+        let mut hit = 0.0;
+        let mut states: HashMap<Vec<(usize, usize)>, f64> = HashMap::new();
+        for (col, p) in self.columns(0) {
+            let alive: Vec<(usize, usize)> = self
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(l, rows)| (l, col[rows[0]]))
+                .filter(|&(_, s)| can_still_pay(s, 1))
+                .collect();
+            if !alive.is_empty() {
+                *states.entry(alive).or_default() += p;
+            }
+        }
+        for reel in 1..n {
+            let columns = self.columns(reel);
+            let mut next: HashMap<Vec<(usize, usize)>, f64> = HashMap::new();
+            for (alive, p) in &states {
+                for (col, q) in &columns {
+                    let mut won = false;
+                    let mut still = Vec::with_capacity(alive.len());
+                    for &(l, s) in alive {
+                        if col[self.lines[l][reel]] == s {
+                            if can_still_pay(s, reel + 1) {
+                                still.push((l, s));
+                            }
+                        } else if self.pay(s, reel) > 0.0 {
+                            won = true;
+                            break;
+                        }
+                    }
+                    if won {
+                        hit += p * q;
+                    } else if !still.is_empty() {
+                        *next.entry(still).or_default() += p * q;
+                    }
+                }
+            }
+            states = next;
+        }
+        hit + states
+            .iter()
+            .filter(|(alive, _)| alive.iter().any(|&(_, s)| self.pay(s, n) > 0.0))
+            .map(|(_, p)| p)
+            .sum::<f64>()
+    }
+
+    /// Distinct visible columns of a reel with their probabilities.
+    fn columns(&self, reel: usize) -> Vec<(Vec<usize>, f64)> {
+        let len = self.reels[reel].len();
+        let mut grouped: HashMap<Vec<usize>, usize> = HashMap::new();
+        for stop in 0..len {
+            let col = (0..self.rows).map(|row| self.symbol_at(reel, stop, row)).collect();
+            *grouped.entry(col).or_default() += 1;
+        }
+        grouped
+            .into_iter()
+            .map(|(col, k)| (col, k as f64 / len as f64))
+            .collect()
+    }
+
+    /// Converts line-multiplier moments into per-unit-stake figures.
+    fn finish(
+        &self,
+        method: ComputationMethod,
+        combinations: u64,
+        mean: f64,
+        second: f64,
+        hit_frequency: f64,
+        max: Option<f64>,
+    ) -> TheoreticalProfile {
+        let lines = self.lines.len() as f64;
+        let variance = ((second - mean * mean) / (lines * lines)).max(0.0);
+        TheoreticalProfile {
+            method,
+            combinations,
+            rtp: mean / lines,
+            hit_frequency,
+            variance,
+            std_dev: variance.sqrt(),
+            max_multiplier: max.map(|m| m / lines),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slot_engine::{GameDefinition, Payline, PaytableEntry, SlotRng};
+
+    fn assert_close(a: f64, b: f64, what: &str) {
+        assert!((a - b).abs() < 1e-9, "{what}: {a} vs {b}");
+    }
+
+    #[test]
+    fn classic_enumeration_matches_hand_computed_rtp() {
+        let p = theoretical_profile(&SlotMachine::classic(), DEFAULT_ENUMERATION_LIMIT);
+        assert_eq!(p.method, ComputationMethod::Enumeration);
+        assert_eq!(p.combinations, 8_000);
+        // Σ p³·mult over 3-of-a-kinds + P(exactly two cherries)·3.
+        assert_close(p.rtp, 0.941875, "rtp");
+        assert!(p.max_multiplier.unwrap() >= 100.0);
+    }
+
+    #[test]
+    fn analytic_agrees_with_enumeration() {
+        let m = SlotMachine::classic();
+        let exact = theoretical_profile(&m, u64::MAX);
+        let analytic = theoretical_profile(&m, 0);
+        assert_eq!(analytic.method, ComputationMethod::Analytic);
+        assert_close(analytic.rtp, exact.rtp, "rtp");
+        assert_close(analytic.variance, exact.variance, "variance");
+        assert_close(analytic.hit_frequency, exact.hit_frequency, "hit frequency");
+        assert!(analytic.max_multiplier.is_none());
+    }
+
+    #[test]
+    fn analytic_agrees_on_uneven_reels_and_overlapping_lines() {
+        let strip = |s: &[&str]| s.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        let m = SlotMachine::new(
+            vec![strip(&["A", "B", "A", "C"]), strip(&["B", "A", "A"]), strip(&["A", "C", "B", "B", "A"])],
+            2,
+            vec![Payline(vec![0, 0, 0]), Payline(vec![1, 1, 1]), Payline(vec![0, 1, 0])],
+            vec![
+                PaytableEntry { symbol: "A".into(), count: 2, multiplier: 1.0 },
+                PaytableEntry { symbol: "A".into(), count: 3, multiplier: 5.0 },
+                PaytableEntry { symbol: "B".into(), count: 3, multiplier: 8.0 },
+            ],
+        )
+        .unwrap();
+        let exact = theoretical_profile(&m, u64::MAX);
+        let analytic = theoretical_profile(&m, 0);
+        assert_close(analytic.rtp, exact.rtp, "rtp");
+        assert_close(analytic.variance, exact.variance, "variance");
+        assert_close(analytic.hit_frequency, exact.hit_frequency, "hit frequency");
+    }
+
+    #[test]
+    fn line_pay_probabilities_reproduce_rtp() {
+        let m = SlotMachine::classic();
+        let rtp: f64 = (0..m.paylines().len())
+            .flat_map(|l| line_pay_probabilities(&m, l))
+            .map(|(e, p)| e.multiplier * p)
+            .sum::<f64>()
+            / m.paylines().len() as f64;
+        assert_close(rtp, 0.941875, "rtp");
+        let total: f64 = line_pay_probabilities(&m, 0).iter().map(|(_, p)| p).sum();
+        assert!(total > 0.0 && total < 1.0);
+    }
+
+    #[test]
+    fn large_checked_in_game_uses_analytic_and_matches_simulation() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../game_engine_targets/slot_game_api_simulator/games/lucky_sevens.json");
+        let def = GameDefinition::load_file(&path).unwrap();
+        let m = def.to_machine().unwrap();
+        let p = theoretical_profile(&m, DEFAULT_ENUMERATION_LIMIT);
+        assert_eq!(p.method, ComputationMethod::Analytic);
+        assert!((p.rtp - def.rtp_target).abs() < 0.01, "rtp {} vs target {}", p.rtp, def.rtp_target);
+
+        let mut rng = SlotRng::seeded(5);
+        let spins = 100_000;
+        let hits = (0..spins).filter(|_| m.spin(&mut rng, 1.0).payout > 0.0).count();
+        let empirical = hits as f64 / spins as f64;
+        assert!((empirical - p.hit_frequency).abs() < 0.01, "{empirical} vs {}", p.hit_frequency);
+    }
+}
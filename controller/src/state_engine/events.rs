@@ -0,0 +1,299 @@
+//! Event-driven transitions: a typed `GameEvent` applied to a state under guard conditions,
+//! yielding the next state and the effects the session manager must execute.
+//!
+//! | From                                      | Event          | To          | Guards                           |
+//! | ----------------------------------------- | -------------- | ----------- | -------------------------------- |
+//! | Idle                                      | Start          | Initialized |                                  |
+//! | Initialized                               | Probe          | Probing     |                                  |
+//! | Probing                                   | ProbeComplete  | Playing     |                                  |
+//! | Initialized, Playing, Evaluating          | PlaceBet       | Playing     | stake > 0, stake ≤ balance       |
+//! | Playing, Evaluating                       | Spin           | Evaluating  | a bet is placed, stake ≤ balance |
+//! | Evaluating                                | CashOut        | Completed   | balance > 0                      |
+//! | Initialized, Probing, Playing, Evaluating | Suspend        | Suspended   |                                  |
+//! | Suspended                                 | Resume { to }  | `to`        | `to` is a state Suspend accepts  |
+//! | any non-terminal state                    | Expire         | Expired     |                                  |
+//! | any non-terminal state                    | Abort          | Aborted     |                                  |
+//!
+//! Balance guards only apply when the context carries a wallet balance.
+
+use super::{GameState, StateError};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Input driving the session state machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GameEvent {
+    Start,
+    Probe,
+    ProbeComplete,
+    /// Sets the stake for the following spins.
+    PlaceBet {
+        #[serde(with = "rust_decimal::serde::float")]
+        amount: Decimal,
+    },
+    /// Spins at `stake`, or at the current bet when absent.
+    Spin {
+        #[serde(with = "rust_decimal::serde::float_option")]
+        stake: Option<Decimal>,
+    },
+    CashOut,
+    /// Closes an idle session (issued by the reaper).
+    Expire,
+    /// Pauses a live session.
+    Suspend,
+    /// Returns a suspended session to `to`, the state it was suspended from.
+    Resume { to: GameState },
+    /// Abandons the session, voiding any open bet.
+    Abort,
+}
+
+/// States a session can be suspended from and resumed into.
+const RESUMABLE: &[GameState] = &[
+    GameState::Initialized,
+    GameState::Probing,
+    GameState::Playing,
+    GameState::Evaluating,
+];
+
+impl GameEvent {
+    /// Variant name, as recorded in transition history.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameEvent::Start => "Start",
+            GameEvent::Probe => "Probe",
+            GameEvent::ProbeComplete => "ProbeComplete",
+            GameEvent::PlaceBet { .. } => "PlaceBet",
+            GameEvent::Spin { .. } => "Spin",
+            GameEvent::CashOut => "CashOut",
+            GameEvent::Expire => "Expire",
+            GameEvent::Suspend => "Suspend",
+            GameEvent::Resume { .. } => "Resume",
+            GameEvent::Abort => "Abort",
+        }
+    }
+
+    /// State the event leads to when allowed.
+    pub fn target(&self) -> GameState {
+        match self {
+            GameEvent::Start => GameState::Initialized,
+            GameEvent::Probe => GameState::Probing,
+            GameEvent::ProbeComplete | GameEvent::PlaceBet { .. } => GameState::Playing,
+            GameEvent::Spin { .. } => GameState::Evaluating,
+            GameEvent::CashOut => GameState::Completed,
+            GameEvent::Expire => GameState::Expired,
+            GameEvent::Suspend => GameState::Suspended,
+            GameEvent::Resume { to } => *to,
+            GameEvent::Abort => GameState::Aborted,
+        }
+    }
+
+    /// States the event may be applied in.
+    fn sources(&self) -> &'static [GameState] {
+        use GameState::*;
+        match self {
+            GameEvent::Start => &[Idle],
+            GameEvent::Probe => &[Initialized],
+            GameEvent::ProbeComplete => &[Probing],
+            GameEvent::PlaceBet { .. } => &[Initialized, Playing, Evaluating],
+            GameEvent::Spin { .. } => &[Playing, Evaluating],
+            GameEvent::CashOut => &[Evaluating],
+            GameEvent::Expire | GameEvent::Abort => &[Idle, Initialized, Probing, Playing, Evaluating, Suspended],
+            GameEvent::Suspend => RESUMABLE,
+            GameEvent::Resume { .. } => &[Suspended],
+        }
+    }
+}
+
+/// Session facts the guards depend on.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransitionContext {
+    /// Stake of the session's most recent PlaceBet, if any.
+    pub current_bet: Option<Decimal>,
+    /// Balance of the wallet backing the session, when one is bound.
+    pub balance: Option<Decimal>,
+}
+
+/// A guard condition that rejected an otherwise valid transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Guard {
+    NoBetPlaced,
+    NonPositiveStake,
+    InsufficientBalance,
+    ZeroBalance,
+    NotResumable,
+}
+
+impl std::fmt::Display for Guard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Guard::NoBetPlaced => "no bet placed",
+            Guard::NonPositiveStake => "stake must be positive",
+            Guard::InsufficientBalance => "stake exceeds wallet balance",
+            Guard::ZeroBalance => "wallet balance is zero",
+            Guard::NotResumable => "sessions resume only into Initialized, Probing, Playing or Evaluating",
+        })
+    }
+}
+
+/// Work the session manager carries out once a transition is persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Effect {
+    /// Make `amount` the session's current bet.
+    RecordBet {
+        #[serde(with = "rust_decimal::serde::float")]
+        amount: Decimal,
+    },
+    /// Resolve one spin on the game's slot machine.
+    ResolveSpin {
+        #[serde(with = "rust_decimal::serde::float")]
+        stake: Decimal,
+    },
+    /// Close the session and settle it.
+    Settle,
+    /// Close the session, voiding any open bet.
+    Void,
+}
+
+/// Outcome of applying an event: the next state and the effects to execute, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub state: GameState,
+    pub effects: Vec<Effect>,
+}
+
+/// Applies `event` in `state`. InvalidTransition if the event is not accepted in `state`,
+/// GuardFailed if it is but a guard rejects it.
+pub fn apply(state: GameState, event: &GameEvent, ctx: &TransitionContext) -> Result<Transition, StateError> {
+    if !event.sources().contains(&state) {
+        return Err(StateError::InvalidTransition { from: state, to: event.target() });
+    }
+    let guard = |g: Guard| Err(StateError::GuardFailed { state, guard: g });
+    let over_balance = |stake: Decimal| ctx.balance.is_some_and(|b| stake > b);
+
+    let effects = match *event {
+        GameEvent::Start | GameEvent::Probe | GameEvent::ProbeComplete => vec![],
+        GameEvent::PlaceBet { amount } => {
+            if amount <= Decimal::ZERO {
+                return guard(Guard::NonPositiveStake);
+            }
+            if over_balance(amount) {
+                return guard(Guard::InsufficientBalance);
+            }
+            vec![Effect::RecordBet { amount }]
+        }
+        GameEvent::Spin { stake } => {
+            let Some(stake) = stake.or(ctx.current_bet) else {
+                return guard(Guard::NoBetPlaced);
+            };
+            if stake <= Decimal::ZERO {
+                return guard(Guard::NonPositiveStake);
+            }
+            if over_balance(stake) {
+                return guard(Guard::InsufficientBalance);
+            }
+            vec![Effect::ResolveSpin { stake }]
+        }
+        GameEvent::CashOut => {
+            if ctx.balance.is_some_and(|b| b <= Decimal::ZERO) {
+                return guard(Guard::ZeroBalance);
+            }
+            vec![Effect::Settle]
+        }
+        GameEvent::Expire => vec![Effect::Settle],
+        GameEvent::Suspend => vec![],
+        GameEvent::Resume { to } => {
+            if !RESUMABLE.contains(&to) {
+                return guard(Guard::NotResumable);
+            }
+            vec![]
+        }
+        GameEvent::Abort => vec![Effect::Void],
+    };
+    Ok(Transition { state: event.target(), effects })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_bet(bet: i64) -> TransitionContext {
+        TransitionContext { current_bet: Some(bet.into()), balance: None }
+    }
+
+    #[test]
+    fn happy_path_through_probe_and_play() {
+        let ctx = TransitionContext::default();
+        let mut s = GameState::Idle;
+        for event in [GameEvent::Start, GameEvent::Probe, GameEvent::ProbeComplete] {
+            s = apply(s, &event, &ctx).unwrap().state;
+        }
+        assert_eq!(s, GameState::Playing);
+
+        let bet = apply(s, &GameEvent::PlaceBet { amount: Decimal::from(2) }, &ctx).unwrap();
+        assert_eq!(bet.effects, vec![Effect::RecordBet { amount: Decimal::from(2) }]);
+        let spin = apply(bet.state, &GameEvent::Spin { stake: None }, &with_bet(2)).unwrap();
+        assert_eq!(spin.state, GameState::Evaluating);
+        assert_eq!(spin.effects, vec![Effect::ResolveSpin { stake: Decimal::from(2) }]);
+        // Spinning again re-uses the bet.
+        let again = apply(spin.state, &GameEvent::Spin { stake: None }, &with_bet(2)).unwrap();
+        assert_eq!(again.state, GameState::Evaluating);
+        let done = apply(again.state, &GameEvent::CashOut, &with_bet(2)).unwrap();
+        assert_eq!((done.state, done.effects), (GameState::Completed, vec![Effect::Settle]));
+    }
+
+    #[test]
+    fn events_outside_their_states_are_invalid_transitions() {
+        let ctx = with_bet(1);
+        assert_eq!(
+            apply(GameState::Playing, &GameEvent::CashOut, &ctx),
+            Err(StateError::InvalidTransition { from: GameState::Playing, to: GameState::Completed })
+        );
+        assert!(apply(GameState::Completed, &GameEvent::Spin { stake: None }, &ctx).is_err());
+        assert!(apply(GameState::Probing, &GameEvent::PlaceBet { amount: Decimal::from(1) }, &ctx).is_err());
+        assert!(apply(GameState::Completed, &GameEvent::Expire, &ctx).is_err());
+        let expired = apply(GameState::Playing, &GameEvent::Expire, &ctx).unwrap();
+        assert_eq!((expired.state, expired.effects), (GameState::Expired, vec![Effect::Settle]));
+    }
+
+    #[test]
+    fn suspend_resume_and_abort() {
+        let ctx = TransitionContext::default();
+        let paused = apply(GameState::Evaluating, &GameEvent::Suspend, &ctx).unwrap();
+        assert_eq!(paused.state, GameState::Suspended);
+        assert!(apply(paused.state, &GameEvent::Spin { stake: Some(Decimal::from(1)) }, &ctx).is_err());
+        assert!(apply(paused.state, &GameEvent::Suspend, &ctx).is_err());
+        let back = apply(paused.state, &GameEvent::Resume { to: GameState::Evaluating }, &ctx).unwrap();
+        assert_eq!(back.state, GameState::Evaluating);
+        assert!(matches!(
+            apply(paused.state, &GameEvent::Resume { to: GameState::Completed }, &ctx),
+            Err(StateError::GuardFailed { guard: Guard::NotResumable, .. })
+        ));
+        assert!(apply(GameState::Playing, &GameEvent::Resume { to: GameState::Playing }, &ctx).is_err());
+        let aborted = apply(paused.state, &GameEvent::Abort, &ctx).unwrap();
+        assert_eq!((aborted.state, aborted.effects), (GameState::Aborted, vec![Effect::Void]));
+        assert!(apply(GameState::Completed, &GameEvent::Abort, &ctx).is_err());
+    }
+
+    #[test]
+    fn guards_reject_spin_without_bet_and_cash_out_at_zero_balance() {
+        let spin = apply(GameState::Playing, &GameEvent::Spin { stake: None }, &TransitionContext::default());
+        assert_eq!(
+            spin,
+            Err(StateError::GuardFailed { state: GameState::Playing, guard: Guard::NoBetPlaced })
+        );
+        let broke = TransitionContext { current_bet: Some(Decimal::from(1)), balance: Some(Decimal::from(0)) };
+        assert!(matches!(
+            apply(GameState::Evaluating, &GameEvent::CashOut, &broke),
+            Err(StateError::GuardFailed { guard: Guard::ZeroBalance, .. })
+        ));
+        assert!(matches!(
+            apply(GameState::Playing, &GameEvent::PlaceBet { amount: Decimal::from(5) }, &TransitionContext { balance: Some(Decimal::from(4)), ..broke }),
+            Err(StateError::GuardFailed { guard: Guard::InsufficientBalance, .. })
+        ));
+        assert!(matches!(
+            apply(GameState::Initialized, &GameEvent::PlaceBet { amount: Decimal::from(0) }, &broke),
+            Err(StateError::GuardFailed { guard: Guard::NonPositiveStake, .. })
+        ));
+    }
+}
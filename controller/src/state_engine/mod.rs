@@ -1,5 +1,12 @@
 //! Pure state machine: Idle → Initialized → Probing → Playing → Evaluating → Completed.
+//! Any non-terminal state may instead end in Expired when the session idles out, or in Aborted
+//! when an operator abandons it; live sessions can be Suspended and later resumed.
 //! All transitions return Result; invalid transitions yield StateError::InvalidTransition (no panic).
+//! `transition` checks the static adjacency table; `apply` drives the machine with typed events.
+
+mod events;
+
+pub use events::{apply, Effect, GameEvent, Guard, Transition, TransitionContext};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -14,6 +21,19 @@ pub enum GameState {
     Playing,
     Evaluating,
     Completed,
+    /// Closed by the idle reaper.
+    Expired,
+    /// Paused; resumes into the state it was suspended from.
+    Suspended,
+    /// Abandoned; open bets are voided.
+    Aborted,
+}
+
+impl GameState {
+    /// Completed, Expired and Aborted accept no further transitions.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, GameState::Completed | GameState::Expired | GameState::Aborted)
+    }
 }
 
 /// State transition errors.
@@ -23,16 +43,49 @@ pub enum StateError {
     InvalidTransition { from: GameState, to: GameState },
     #[error("Session not found")]
     NotFound,
+    #[error("Action not allowed in {state:?}: {guard}")]
+    GuardFailed { state: GameState, guard: Guard },
 }
 
 /// Allowed next states from each state (canonical machine).
 const ALLOWED: &[(GameState, &[GameState])] = &[
-    (GameState::Idle, &[GameState::Initialized]),
-    (GameState::Initialized, &[GameState::Probing, GameState::Playing]),
-    (GameState::Probing, &[GameState::Playing]),
-    (GameState::Playing, &[GameState::Evaluating]),
-    (GameState::Evaluating, &[GameState::Playing, GameState::Completed]),
+    (GameState::Idle, &[GameState::Initialized, GameState::Expired, GameState::Aborted]),
+    (
+        GameState::Initialized,
+        &[GameState::Probing, GameState::Playing, GameState::Expired, GameState::Suspended, GameState::Aborted],
+    ),
+    (
+        GameState::Probing,
+        &[GameState::Playing, GameState::Expired, GameState::Suspended, GameState::Aborted],
+    ),
+    (
+        GameState::Playing,
+        &[GameState::Evaluating, GameState::Expired, GameState::Suspended, GameState::Aborted],
+    ),
+    (
+        GameState::Evaluating,
+        &[
+            GameState::Playing,
+            GameState::Completed,
+            GameState::Expired,
+            GameState::Suspended,
+            GameState::Aborted,
+        ],
+    ),
+    (
+        GameState::Suspended,
+        &[
+            GameState::Initialized,
+            GameState::Probing,
+            GameState::Playing,
+            GameState::Evaluating,
+            GameState::Expired,
+            GameState::Aborted,
+        ],
+    ),
     (GameState::Completed, &[]),
+    (GameState::Expired, &[]),
+    (GameState::Aborted, &[]),
 ];
 
 /// Checks if a transition from `from` to `to` is valid; returns Ok(to) or Err(StateError).
@@ -78,6 +131,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn only_live_states_expire() {
+        assert_eq!(transition(GameState::Evaluating, GameState::Expired), Ok(GameState::Expired));
+        assert!(transition(GameState::Completed, GameState::Expired).is_err());
+        assert!(transition(GameState::Expired, GameState::Playing).is_err());
+        assert!(GameState::Expired.is_terminal() && !GameState::Playing.is_terminal());
+    }
+
+    #[test]
+    fn suspended_resumes_or_aborts() {
+        assert_eq!(transition(GameState::Playing, GameState::Suspended), Ok(GameState::Suspended));
+        assert_eq!(transition(GameState::Suspended, GameState::Evaluating), Ok(GameState::Evaluating));
+        assert_eq!(transition(GameState::Suspended, GameState::Aborted), Ok(GameState::Aborted));
+        assert!(transition(GameState::Suspended, GameState::Completed).is_err());
+        assert!(transition(GameState::Aborted, GameState::Playing).is_err());
+        assert!(GameState::Aborted.is_terminal() && !GameState::Suspended.is_terminal());
+    }
+
     #[test]
     fn initialized_to_playing_ok() {
         assert_eq!(
@@ -0,0 +1,79 @@
+//! Fixtures shared by the controller's and the CLI's tests: games, wallets, an in-memory
+//! `AppState` and session requests.
+
+use crate::api::{
+    CreateSessionRequest, Currency, Game, GameId, Money, PlayerProfile, SessionId, Wallet,
+};
+use crate::app_state::AppState;
+use crate::event_store::InMemoryEventStore;
+use crate::fingerprinter::InMemoryFingerprintStore;
+use crate::persistence_metrics::{InMemoryGameStore, InMemorySessionStore, InMemoryWalletStore};
+use crate::rl_feedback_loop::InMemoryStore;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Helper: build a test game with empty JSON documents.
+pub fn test_game(id: Uuid, name: &str) -> Game {
+    Game {
+        game_id: GameId(id),
+        name: name.to_string(),
+        rng_signature: serde_json::json!({}),
+        symbol_map: serde_json::json!({}),
+        statistical_profile: serde_json::json!({}),
+        created_at: chrono::Utc::now(),
+    }
+}
+
+/// Helper: build a test wallet with a given balance.
+pub fn test_wallet(id: Uuid, balance: Decimal) -> Wallet {
+    let currency = Currency::AUD;
+    Wallet {
+        wallet_id: SessionId(id),
+        balance: Money { amount: balance, currency },
+        daily_limit: Money { amount: Decimal::from(1000), currency },
+        daily_spent: Money::zero(currency),
+        weekly_limit: None,
+        weekly_spent: Money::zero(currency),
+        monthly_limit: None,
+        monthly_spent: Money::zero(currency),
+        timezone: chrono_tz::Tz::UTC,
+        spent_at: None,
+        frozen: false,
+    }
+}
+
+/// Helper: AppState over empty in-memory stores with one game, `game_id`, registered.
+pub fn test_state(game_id: Uuid) -> AppState {
+    let games = InMemoryGameStore::new();
+    games.seed(test_game(game_id, "Test Game"));
+    AppState::new(
+        Arc::new(InMemorySessionStore::new()),
+        Arc::new(games),
+        Arc::new(InMemoryWalletStore::new()),
+        Arc::new(InMemoryEventStore::new()),
+        Arc::new(InMemoryFingerprintStore::new()),
+        Arc::new(InMemoryStore::new()),
+        None,
+    )
+}
+
+/// Helper: pool over the database at DATABASE_URL, with the migrations applied. Without one
+/// the Postgres tests pass vacuously.
+pub async fn test_pool() -> Option<sqlx::PgPool> {
+    let url = std::env::var("DATABASE_URL").ok()?;
+    let pool = sqlx::postgres::PgPoolOptions::new().max_connections(16).connect(&url).await.unwrap();
+    sqlx::migrate!("../database/migrations").run(&pool).await.unwrap();
+    Some(pool)
+}
+
+/// Helper: request for an untagged session of `game_id` with the default profile, bound to
+/// `wallet_id` if given.
+pub fn session_request(game_id: Uuid, wallet_id: Option<Uuid>) -> CreateSessionRequest {
+    CreateSessionRequest {
+        game_id: GameId(game_id),
+        player_profile: PlayerProfile::default(),
+        wallet_id,
+        tags: Vec::new(),
+    }
+}
@@ -0,0 +1,20 @@
+-- 0010_session_wallet_persistence.sql
+-- Sessions and wallets are now persisted to Postgres when DATABASE_URL is set.
+-- Wallets gain the currency and daily spend the API reports.
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS currency TEXT NOT NULL DEFAULT 'AUD';
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS daily_spent NUMERIC(20,4) NOT NULL DEFAULT 0;
+
+-- Re-add the rl_store session FK dropped by 0008. NOT VALID: rows written while sessions were
+-- in-memory are kept; new rows are checked.
+DO $$
+BEGIN
+  IF NOT EXISTS (
+    SELECT 1 FROM information_schema.table_constraints
+    WHERE table_name = 'rl_store'
+      AND constraint_name = 'rl_store_session_id_fkey'
+  ) THEN
+    ALTER TABLE rl_store
+      ADD CONSTRAINT rl_store_session_id_fkey
+      FOREIGN KEY (session_id) REFERENCES sessions (session_id) NOT VALID;
+  END IF;
+END $$;
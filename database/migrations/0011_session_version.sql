@@ -0,0 +1,3 @@
+-- 0011_session_version.sql
+-- Optimistic concurrency: state updates compare-and-swap on version.
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
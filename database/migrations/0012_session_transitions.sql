@@ -0,0 +1,12 @@
+-- 0012_session_transitions.sql
+-- Audit trail of session state changes; one row per version, written with the state update.
+CREATE TABLE IF NOT EXISTS session_transitions (
+    session_id UUID NOT NULL REFERENCES sessions (session_id) ON DELETE CASCADE,
+    version BIGINT NOT NULL,
+    from_state game_state NOT NULL,
+    to_state game_state NOT NULL,
+    action TEXT NOT NULL,
+    actor TEXT NULL,
+    timestamp TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    PRIMARY KEY (session_id, version)
+);
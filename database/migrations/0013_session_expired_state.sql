@@ -0,0 +1,6 @@
+-- 0013_session_expired_state.sql
+-- Terminal state for sessions closed by the idle reaper.
+ALTER TYPE game_state ADD VALUE IF NOT EXISTS 'Expired';
+
+CREATE INDEX IF NOT EXISTS idx_sessions_state_updated_at
+ON sessions (state, updated_at);
@@ -0,0 +1,8 @@
+-- 0014_session_suspend_abort.sql
+-- Suspended/Aborted session states; transitions record the gameplay event they await so
+-- startup recovery can resolve actions interrupted by a crash.
+ALTER TYPE game_state ADD VALUE IF NOT EXISTS 'Suspended';
+ALTER TYPE game_state ADD VALUE IF NOT EXISTS 'Aborted';
+
+ALTER TABLE session_transitions ADD COLUMN IF NOT EXISTS event_id UUID NULL;
+ALTER TABLE session_transitions ADD COLUMN IF NOT EXISTS request JSONB NULL;
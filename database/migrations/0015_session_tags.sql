@@ -0,0 +1,12 @@
+-- 0015_session_tags.sql
+-- Free-form session labels, and indexes backing GET /sessions filters and keyset pagination.
+ALTER TABLE sessions ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_sessions_tags
+ON sessions USING GIN (tags);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_created_at_id
+ON sessions (created_at, session_id);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_updated_at_id
+ON sessions (updated_at, session_id);
@@ -0,0 +1,49 @@
+-- 0016_wallet_ledger.sql
+-- Double-entry wallet ledger: one immutable entry per balance change, with postings between
+-- the wallet, house ('house:<game id>' or 'house') and 'fees' accounts that sum to zero.
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    entry_id UUID PRIMARY KEY,
+    wallet_id UUID NOT NULL REFERENCES wallets (wallet_id),
+    sequence BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    balance_after NUMERIC(20,4) NOT NULL,
+    currency TEXT NOT NULL,
+    session_id UUID NULL,
+    game_id UUID NULL,
+    event_id UUID NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    UNIQUE (wallet_id, sequence)
+);
+
+CREATE TABLE IF NOT EXISTS ledger_postings (
+    entry_id UUID NOT NULL REFERENCES ledger_entries (entry_id),
+    position SMALLINT NOT NULL,
+    account TEXT NOT NULL,
+    amount NUMERIC(20,4) NOT NULL,
+    currency TEXT NOT NULL,
+    PRIMARY KEY (entry_id, position)
+);
+
+-- Balance recomputation sums an account's postings.
+CREATE INDEX IF NOT EXISTS idx_ledger_postings_account
+ON ledger_postings (account);
+
+-- Entries are append-only.
+CREATE OR REPLACE FUNCTION ledger_immutable_trigger()
+RETURNS TRIGGER AS $$
+BEGIN
+   RAISE EXCEPTION 'ledger rows are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS ledger_entries_immutable ON ledger_entries;
+CREATE TRIGGER ledger_entries_immutable
+BEFORE UPDATE OR DELETE ON ledger_entries
+FOR EACH ROW
+EXECUTE PROCEDURE ledger_immutable_trigger();
+
+DROP TRIGGER IF EXISTS ledger_postings_immutable ON ledger_postings;
+CREATE TRIGGER ledger_postings_immutable
+BEFORE UPDATE OR DELETE ON ledger_postings
+FOR EACH ROW
+EXECUTE PROCEDURE ledger_immutable_trigger();
@@ -0,0 +1,10 @@
+-- 0017_wallet_spend_windows.sql
+-- Spend totals per local day, ISO week and month in the wallet's timezone. spent_at is the last
+-- debit counted; a total whose window has ended since then is treated as zero and reset by the
+-- next debit. Weekly and monthly limits are optional.
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS weekly_limit NUMERIC(20,4) NULL;
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS weekly_spent NUMERIC(20,4) NOT NULL DEFAULT 0;
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS monthly_limit NUMERIC(20,4) NULL;
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS monthly_spent NUMERIC(20,4) NOT NULL DEFAULT 0;
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'UTC';
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS spent_at TIMESTAMPTZ NULL;
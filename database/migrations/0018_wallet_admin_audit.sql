@@ -0,0 +1,32 @@
+-- 0018_wallet_admin_audit.sql
+-- Admin wallet management: frozen wallets refuse debits, and every admin change (limits,
+-- freeze/unfreeze, daily spend reset) is recorded with the wallet before and after it.
+ALTER TABLE wallets ADD COLUMN IF NOT EXISTS frozen BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS wallet_audit (
+    audit_id BIGSERIAL PRIMARY KEY,
+    wallet_id UUID NOT NULL REFERENCES wallets (wallet_id),
+    action JSONB NOT NULL,
+    actor TEXT NOT NULL,
+    reason TEXT NULL,
+    before JSONB NOT NULL,
+    after JSONB NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_wallet_audit_wallet
+ON wallet_audit (wallet_id, audit_id);
+
+-- Audit records are append-only.
+CREATE OR REPLACE FUNCTION wallet_audit_immutable_trigger()
+RETURNS TRIGGER AS $$
+BEGIN
+   RAISE EXCEPTION 'wallet audit records are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS wallet_audit_immutable ON wallet_audit;
+CREATE TRIGGER wallet_audit_immutable
+BEFORE UPDATE OR DELETE ON wallet_audit
+FOR EACH ROW
+EXECUTE PROCEDURE wallet_audit_immutable_trigger();
@@ -0,0 +1,6 @@
+-- 0019_ledger_event_index.sql
+-- Refunds look up the entries a wallet booked for one gameplay event: the debit to reverse,
+-- and any refund already booked for it.
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_event
+ON ledger_entries (wallet_id, event_id)
+WHERE event_id IS NOT NULL;
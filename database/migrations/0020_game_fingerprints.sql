@@ -0,0 +1,15 @@
+-- 0020_game_fingerprints.sql
+-- Fingerprint runs, kept per game as numbered versions (1-based, in run order) so runs can be
+-- diffed and a game's baseline survives restarts.
+CREATE TABLE IF NOT EXISTS game_fingerprints (
+    game_id UUID NOT NULL REFERENCES games (game_id),
+    version INTEGER NOT NULL CHECK (version > 0),
+    run_id UUID NOT NULL,
+    rng_signature TEXT NOT NULL,
+    symbol_map JSONB NOT NULL,
+    statistical_profile JSONB NOT NULL,
+    randomness JSONB NOT NULL DEFAULT 'null'::JSONB,
+    sample_size BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    PRIMARY KEY (game_id, version)
+);
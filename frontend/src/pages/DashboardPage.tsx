@@ -1,7 +1,7 @@
 import { useState, useEffect, useCallback } from "react";
 import { useNavigate } from "react-router-dom";
 import { api } from "../api/client";
-import type { CreateSessionResponse } from "../../../agents/ts-client";
+import type { BehaviorType, CreateSessionResponse } from "../../../agents/ts-client";
 
 const REWARD_HISTORY_KEY = "pokemon-rs:reward-history";
 const HEALTH_POLL_MS = 10_000;
@@ -61,7 +61,7 @@ export function DashboardPage() {
   const navigate = useNavigate();
   const [healthy, setHealthy] = useState<boolean | null>(null);
   const [gameId, setGameId] = useState("");
-  const [behavior, setBehavior] = useState("conservative");
+  const [behavior, setBehavior] = useState<BehaviorType>("conservative");
   const [creating, setCreating] = useState(false);
   const [createError, setCreateError] = useState<string | null>(null);
   const [rewards, setRewards] = useState<number[]>(() => getStoredRewards().slice(-20));
@@ -133,7 +133,7 @@ export function DashboardPage() {
             <label className="block text-sm text-slate-400 mb-1">Behavior</label>
             <select
               value={behavior}
-              onChange={(e) => setBehavior(e.target.value)}
+              onChange={(e) => setBehavior(e.target.value as BehaviorType)}
               className="px-3 py-2 rounded bg-slate-800 border border-slate-600 text-white"
             >
               <option value="conservative">Conservative</option>
@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { useSearchParams } from "react-router-dom";
 import { api } from "../api/client";
 import type {
+  BehaviorType,
   Session,
   CreateSessionRequest,
   CreateSessionResponse,
@@ -107,7 +108,7 @@ export function SessionsPage() {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [createGameId, setCreateGameId] = useState("");
-  const [createBehavior, setCreateBehavior] = useState("conservative");
+  const [createBehavior, setCreateBehavior] = useState<BehaviorType>("conservative");
   const [detailSession, setDetailSession] = useState<Session | null>(null);
   const [lastResult, setLastResult] = useState<PlayActionResponse | null>(null);
   const [events, setEvents] = useState<SessionEventRecord[]>([]);
@@ -215,7 +216,7 @@ export function SessionsPage() {
             <label className="block text-sm text-slate-400 mb-1">Behavior</label>
             <select
               value={createBehavior}
-              onChange={(e) => setCreateBehavior(e.target.value)}
+              onChange={(e) => setCreateBehavior(e.target.value as BehaviorType)}
               className="px-3 py-2 rounded bg-slate-800 border border-slate-600 text-white"
             >
               <option value="conservative">Conservative</option>
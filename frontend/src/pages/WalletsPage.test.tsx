@@ -8,12 +8,43 @@ import { api } from "../api/client";
 vi.mock("../api/client", () => ({
   api: {
     walletOperation: vi.fn(),
+    getWallet: vi.fn(),
   },
 }));
 
 describe("WalletsPage", () => {
   beforeEach(() => {
     vi.mocked(api.walletOperation).mockReset();
+    vi.mocked(api.getWallet).mockReset();
+  });
+
+  it("loads a wallet by id and flags it when frozen", async () => {
+    const user = userEvent.setup();
+    vi.mocked(api.getWallet).mockResolvedValue({
+      walletId: "w1",
+      balance: { amount: 250, currency: "AUD" },
+      dailyLimit: { amount: 500, currency: "AUD" },
+      dailySpent: { amount: 20, currency: "AUD" },
+      weeklySpent: { amount: 20, currency: "AUD" },
+      monthlySpent: { amount: 20, currency: "AUD" },
+      timezone: "UTC",
+      frozen: true,
+    });
+
+    render(
+      <MemoryRouter>
+        <WalletsPage />
+      </MemoryRouter>
+    );
+
+    await user.type(screen.getByPlaceholderText(/wallet uuid/i), "w1");
+    await user.click(screen.getByRole("button", { name: /load/i }));
+
+    await waitFor(() => {
+      expect(api.getWallet).toHaveBeenCalledWith("w1");
+      expect(screen.getByText(/250 AUD/)).toBeInTheDocument();
+      expect(screen.getByText(/wallet is frozen/i)).toBeInTheDocument();
+    });
   });
 
   it("renders balance and daily limit after successful operation", async () => {
@@ -6,7 +6,7 @@ import { ErrorDisplay } from "../components/ErrorBoundary";
 
 const formatMoney = (m: Money) => `${m.amount} ${m.currency}`;
 
-/** Wallets page: balance display, debit/credit form, limit alerts. */
+/** Wallets page: wallet lookup, balance display, debit/credit form, limit alerts. */
 export function WalletsPage() {
   const [walletId, setWalletId] = useState("");
   const [wallet, setWallet] = useState<Wallet | null>(null);
@@ -16,6 +16,21 @@ export function WalletsPage() {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
+  const handleLoad = async () => {
+    const wid = walletId.trim();
+    if (!wid) return;
+
+    setLoading(true);
+    setError(null);
+    try {
+      setWallet(await api.getWallet(wid));
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const handleOperation = async (e: React.FormEvent) => {
     e.preventDefault();
     const wid = walletId.trim();
@@ -54,6 +69,14 @@ export function WalletsPage() {
           placeholder="Enter wallet UUID"
           className="px-3 py-2 rounded bg-slate-800 border border-slate-600 text-white w-80"
         />
+        <button
+          type="button"
+          onClick={handleLoad}
+          disabled={loading || !walletId.trim()}
+          className="ml-2 px-4 py-2 rounded bg-slate-700 hover:bg-slate-600 disabled:opacity-50 text-white"
+        >
+          Load
+        </button>
       </section>
 
       <section>
@@ -112,7 +135,13 @@ export function WalletsPage() {
           <div className="grid grid-cols-2 gap-4 text-sm">
             <div>Balance: <span className="font-mono">{formatMoney(wallet.balance)}</span></div>
             <div>Daily Limit: <span className="font-mono">{formatMoney(wallet.dailyLimit)}</span></div>
+            <div>Spent Today: <span className="font-mono">{formatMoney(wallet.dailySpent)}</span></div>
           </div>
+          {wallet.frozen && (
+            <div className="mt-4 p-3 rounded text-sm bg-red-900/50 border border-red-600" role="status">
+              Wallet is frozen: debits are refused.
+            </div>
+          )}
           {(balanceLow || limitWarning) && (
             <div
               className={`mt-4 p-3 rounded text-sm ${